@@ -0,0 +1,572 @@
+use dxf::Drawing;
+
+use crate::constants::*;
+use crate::helpers::{add_line, add_rectangle, add_text, draw_dim_horizontal, draw_dim_vertical};
+use crate::layers::*;
+use crate::model::{BuildingModel, OpeningKind};
+
+// ============================================================================
+// SAUNA BENCHES
+// Tiered benches along the back (north) wall of the sauna and, for the L and U
+// layouts, down the side walls. Each tier is a band of seat measured out from
+// the wall behind it, the higher tiers nearest the wall. At a corner the back
+// run carries every tier across the end of the side run, so the bands meet
+// without overlapping; the lower tiers of a side run are shorter by the depth
+// of the back run in front of them, and any left shorter than
+// BENCH_MIN_SEAT_LENGTH are left out. Seats are boarded with slats running along the run, as
+// many BENCH_SLAT_WIDTH boards as fit with BENCH_SLAT_GAP between them and the
+// spare width shared out among the gaps. A step is a low tier in front of the
+// lowest seat; a backrest is a panel of boards on the wall above the top seat.
+// ============================================================================
+
+/// Wall a bench run stands against
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BenchWall {
+    North,
+    East,
+    West,
+}
+
+/// One tier of one bench run, in plan
+pub struct BenchSeat {
+    pub id: String, // bench-north-1, tiers numbered up from the lowest seat
+    pub wall: BenchWall,
+    pub step: bool, // Foot step rather than a seat
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+    pub height: f64, // Seat top above the floor
+    pub slats: u32,
+    pub gap: f64, // Between slats
+}
+
+/// Backrest boards on the wall above the top seat of a run
+pub struct Backrest {
+    pub wall: BenchWall,
+    pub x1: f64, // Plan box of the boards
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+    pub bottom: f64, // Top seat height
+    pub boards: u32,
+    pub gap: f64,
+}
+
+pub struct BenchCheck {
+    pub seats: Vec<BenchSeat>,
+    pub left_out: Vec<(String, f64)>, // Seats too short to sit on, with their length
+    pub backrests: Vec<Backrest>,
+    pub problems: Vec<String>,
+}
+
+impl BenchWall {
+    pub fn name(self) -> &'static str {
+        match self {
+            BenchWall::North => "NORTH",
+            BenchWall::East => "EAST",
+            BenchWall::West => "WEST",
+        }
+    }
+}
+
+/// Boards filling a width flush at both edges: count and gap between them
+fn board_layout(width: f64) -> (u32, f64) {
+    let boards = (((width + BENCH_SLAT_GAP) / (BENCH_SLAT_WIDTH + BENCH_SLAT_GAP) + 1e-9).floor() as u32).max(1);
+    let gap = if boards > 1 { (width - boards as f64 * BENCH_SLAT_WIDTH) / (boards - 1) as f64 } else { 0.0 };
+    (boards, gap)
+}
+
+impl BenchSeat {
+    /// True for the back run, whose slats run along x
+    fn along_x(&self) -> bool {
+        self.wall == BenchWall::North
+    }
+
+    pub fn length(&self) -> f64 {
+        if self.along_x() { self.x2 - self.x1 } else { self.y2 - self.y1 }
+    }
+
+    pub fn depth(&self) -> f64 {
+        if self.along_x() { self.y2 - self.y1 } else { self.x2 - self.x1 }
+    }
+
+    /// Plan x or y range across the seat of every slat
+    pub fn slat_bands(&self) -> Vec<(f64, f64)> {
+        let start = if self.along_x() { self.y1 } else { self.x1 };
+        (0..self.slats)
+            .map(|i| {
+                let from = start + i as f64 * (BENCH_SLAT_WIDTH + self.gap);
+                (from, from + BENCH_SLAT_WIDTH)
+            })
+            .collect()
+    }
+}
+
+impl Backrest {
+    pub fn length(&self) -> f64 {
+        if self.wall == BenchWall::North { self.x2 - self.x1 } else { self.y2 - self.y1 }
+    }
+}
+
+/// Tiers from the lowest up, the step first if there is one, with the
+/// distance of each from the wall behind it
+fn tiers() -> Vec<(BenchTier, bool, f64)> {
+    let mut tiers: Vec<(BenchTier, bool)> = BENCH_STEP.iter().map(|&step| (step, true)).collect();
+    tiers.extend(BENCH_TIERS.iter().map(|&tier| (tier, false)));
+    let mut from_wall = tiers.iter().map(|(tier, _)| tier.depth).sum::<f64>();
+    tiers
+        .into_iter()
+        .map(|(tier, step)| {
+            from_wall -= tier.depth;
+            (tier, step, from_wall)
+        })
+        .collect()
+}
+
+/// Runs in a layout
+fn walls(layout: BenchLayout) -> Vec<BenchWall> {
+    match layout {
+        BenchLayout::Straight => vec![BenchWall::North],
+        BenchLayout::L => vec![BenchWall::North, BenchWall::West],
+        BenchLayout::U => vec![BenchWall::North, BenchWall::East, BenchWall::West],
+    }
+}
+
+/// Every tier of every run in a sauna with its clear floor between (x1, y1)
+/// and (x2, y2), however short
+fn layout_seats(layout: BenchLayout, x1: f64, y1: f64, x2: f64, y2: f64) -> Vec<BenchSeat> {
+    let walls = walls(layout);
+    let east = walls.contains(&BenchWall::East);
+    let west = walls.contains(&BenchWall::West);
+    let side_end = (y2 - BENCH_SIDE_LENGTH).max(y1);
+
+    let mut seats = Vec::new();
+    for wall in walls {
+        let mut number = 0;
+        for (tier, step, near) in tiers() {
+            let far = near + tier.depth;
+            let (sx1, sy1, sx2, sy2) = match wall {
+                BenchWall::North => (
+                    if west { x1 + near } else { x1 },
+                    y2 - far,
+                    if east { x2 - near } else { x2 },
+                    y2 - near,
+                ),
+                BenchWall::East => (x2 - far, side_end, x2 - near, y2 - far),
+                BenchWall::West => (x1 + near, side_end, x1 + far, y2 - far),
+            };
+            if sx2 - sx1 <= 0.0 || sy2 - sy1 <= 0.0 {
+                continue;
+            }
+            let suffix = if step {
+                "step".to_string()
+            } else {
+                number += 1;
+                number.to_string()
+            };
+            let (slats, gap) = board_layout(tier.depth);
+            seats.push(BenchSeat {
+                id: format!("bench-{}-{}", wall.name().to_lowercase(), suffix),
+                wall,
+                step,
+                x1: sx1,
+                y1: sy1,
+                x2: sx2,
+                y2: sy2,
+                height: tier.height,
+                slats,
+                gap,
+            });
+        }
+    }
+    seats
+}
+
+/// Bench seats of a layout in a sauna with its clear floor between (x1, y1)
+/// and (x2, y2)
+pub fn bench_seats(layout: BenchLayout, x1: f64, y1: f64, x2: f64, y2: f64) -> Vec<BenchSeat> {
+    let mut seats = layout_seats(layout, x1, y1, x2, y2);
+    seats.retain(|seat| seat.length() >= BENCH_MIN_SEAT_LENGTH - 1e-9);
+    seats
+}
+
+/// Clear plan gap between two boxes, zero if they touch or overlap
+fn box_gap(a: (f64, f64, f64, f64), b: (f64, f64, f64, f64)) -> f64 {
+    let dx = (b.0 - a.2).max(a.0 - b.2).max(0.0);
+    let dy = (b.1 - a.3).max(a.1 - b.3).max(0.0);
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// Benches, backrests and the checks on tier heights, headroom, the heater
+/// and the doors
+pub fn bench_check(model: &BuildingModel) -> BenchCheck {
+    let room = model.sauna();
+    let seats = bench_seats(BENCH_LAYOUT, room.x1, room.y1, room.x2, room.y2);
+    let mut problems = Vec::new();
+
+    let left_out = layout_seats(BENCH_LAYOUT, room.x1, room.y1, room.x2, room.y2)
+        .into_iter()
+        .filter(|seat| seat.length() < BENCH_MIN_SEAT_LENGTH - 1e-9)
+        .map(|seat| (seat.id.to_uppercase(), seat.length()))
+        .collect();
+
+    // Backrest on the wall behind the top seat of each run
+    let mut backrests = Vec::new();
+    if let Some(height) = BENCH_BACKREST {
+        let (boards, gap) = board_layout(height);
+        let (near, far) = (BENCH_BACKREST_STANDOFF, BENCH_BACKREST_STANDOFF + BENCH_SLAT_THICKNESS);
+        for wall in walls(BENCH_LAYOUT) {
+            let Some(top) = seats.iter().rfind(|seat| seat.wall == wall && !seat.step) else { continue };
+            let (x1, y1, x2, y2) = match wall {
+                BenchWall::North => (top.x1, room.y2 - far, top.x2, room.y2 - near),
+                BenchWall::East => (room.x2 - far, top.y1, room.x2 - near, top.y2),
+                BenchWall::West => (room.x1 + near, top.y1, room.x1 + far, top.y2),
+            };
+            backrests.push(Backrest { wall, x1, y1, x2, y2, bottom: top.height, boards, gap });
+        }
+    }
+
+    // Every tier above the one below it
+    let tiers = tiers();
+    for pair in tiers.windows(2) {
+        if pair[1].0.height <= pair[0].0.height {
+            problems.push(format!(
+                "TIER AT {:.2} m NOT ABOVE THE TIER BELOW AT {:.2} m",
+                pair[1].0.height, pair[0].0.height
+            ));
+        }
+    }
+
+    // Depth of the tiers against the room, across both sides for a U
+    let depth: f64 = tiers.iter().map(|(tier, _, _)| tier.depth).sum();
+    let width = room.x2 - room.x1;
+    let across = if BENCH_LAYOUT == BenchLayout::U { depth * 2.0 } else { depth };
+    if depth >= room.y2 - room.y1 || across >= width {
+        problems.push(format!(
+            "TIERS {:.2} m DEEP DO NOT FIT THE {:.2} x {:.2} m SAUNA",
+            depth,
+            width,
+            room.y2 - room.y1
+        ));
+    }
+
+    // Headroom over the top seat
+    let ceiling = room.ceiling.unwrap_or(BUILDING_HEIGHT);
+    let top = tiers.iter().map(|(tier, _, _)| tier.height).fold(0.0, f64::max);
+    if ceiling - top < BENCH_MIN_HEADROOM {
+        problems.push(format!(
+            "TOP SEAT {:.2} m UNDER THE CEILING (MIN {:.2} m)",
+            ceiling - top,
+            BENCH_MIN_HEADROOM
+        ));
+    }
+
+    // Clear of the heater and of the door openings
+    let heater = model.furnishing("heater");
+    for seat in &seats {
+        if let Some(heater) = heater {
+            let (cx, cy) = heater.footprint.centre();
+            let clearance = heater.footprint.distance_to(cx.clamp(seat.x1, seat.x2), cy.clamp(seat.y1, seat.y2));
+            if clearance < model.heater.bench_clearance - 1e-9 {
+                problems.push(format!(
+                    "{}: {:.2} m FROM THE HEATER (MIN {:.2} m)",
+                    seat.id.to_uppercase(),
+                    clearance,
+                    model.heater.bench_clearance
+                ));
+            }
+        }
+        for wall in model.walls.iter().filter(|wall| wall.faces_room(room).is_some()) {
+            for opening in wall.openings.iter().filter(|opening| opening.kind == OpeningKind::Door) {
+                let door = if wall.is_horizontal() {
+                    (wall.x1 + opening.offset, wall.y1, wall.x1 + opening.offset + opening.width, wall.y2)
+                } else {
+                    (wall.x1, wall.y1 + opening.offset, wall.x2, wall.y1 + opening.offset + opening.width)
+                };
+                let clearance = box_gap((seat.x1, seat.y1, seat.x2, seat.y2), door);
+                if clearance < BENCH_DOOR_CLEARANCE - 1e-9 {
+                    problems.push(format!(
+                        "{}: {:.2} m FROM {} (MIN {:.2} m)",
+                        seat.id.to_uppercase(),
+                        clearance,
+                        opening.id.to_uppercase(),
+                        BENCH_DOOR_CLEARANCE
+                    ));
+                }
+            }
+        }
+    }
+
+    BenchCheck { seats, left_out, backrests, problems }
+}
+
+impl BenchCheck {
+    /// Report lines for the sheet and the console
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for seat in &self.seats {
+            lines.push(format!(
+                "{:<18} {:.2} x {:.2} m AT {:.2} m  {} SLATS  GAP {:.0} mm",
+                seat.id.to_uppercase(),
+                seat.length(),
+                seat.depth(),
+                seat.height,
+                seat.slats,
+                seat.gap * 1000.0
+            ));
+        }
+        for (id, length) in &self.left_out {
+            lines.push(format!("{:<18} LEFT OUT, {:.2} m UNDER THE {:.2} m MINIMUM", id, length, BENCH_MIN_SEAT_LENGTH));
+        }
+        for backrest in &self.backrests {
+            lines.push(format!(
+                "BACKREST {:<9} {:.2} m  {} BOARDS  GAP {:.0} mm",
+                backrest.wall.name(),
+                backrest.length(),
+                backrest.boards,
+                backrest.gap * 1000.0
+            ));
+        }
+        lines.push(format!(
+            "{} SLATS AND BOARDS {:.0}x{:.0}, {:.1} m",
+            self.seats.iter().map(|seat| seat.slats).sum::<u32>() + self.backrests.iter().map(|b| b.boards).sum::<u32>(),
+            BENCH_SLAT_WIDTH * 1000.0,
+            BENCH_SLAT_THICKNESS * 1000.0,
+            self.seats.iter().map(|seat| seat.length() * seat.slats as f64).sum::<f64>()
+                + self.backrests.iter().map(|b| b.length() * b.boards as f64).sum::<f64>()
+        ));
+        if self.problems.is_empty() {
+            lines.push("TIERS, HEADROOM AND CLEARANCES OK".to_string());
+        }
+        lines.extend(self.problems.iter().map(|problem| format!("WARNING: {}", problem)));
+        lines
+    }
+}
+
+/// Benches in the floor plan: a joint between each pair of slats, the seat
+/// outlines coming from the model furnishings, and the backrest boards
+pub fn draw_benches_plan(drawing: &mut Drawing, check: &BenchCheck) {
+    for seat in &check.seats {
+        let bands = seat.slat_bands();
+        for pair in bands.windows(2) {
+            let joint = (pair[0].1 + pair[1].0) / 2.0;
+            if seat.along_x() {
+                add_line(drawing, LAYER_FIXTURES, seat.x1, joint, seat.x2, joint);
+            } else {
+                add_line(drawing, LAYER_FIXTURES, joint, seat.y1, joint, seat.y2);
+            }
+        }
+    }
+    for backrest in &check.backrests {
+        add_rectangle(drawing, LAYER_FIXTURES, backrest.x1, backrest.y1, backrest.x2, backrest.y2);
+    }
+}
+
+/// A seat cut square to its run, with plan positions across it mapped to the
+/// view x and the floor at `floor`: slats, the bearer under them and the leg
+/// at the front edge
+fn draw_seat_profile(drawing: &mut Drawing, seat: &BenchSeat, view_x: impl Fn(f64) -> f64, floor: f64) {
+    let top = floor + seat.height;
+    let bottom = top - BENCH_SLAT_THICKNESS;
+    for (a, b) in seat.slat_bands() {
+        let (x1, x2) = (view_x(a), view_x(b));
+        add_rectangle(drawing, LAYER_FIXTURES, x1.min(x2), bottom, x1.max(x2), top);
+    }
+
+    let (front, back) = match seat.wall {
+        BenchWall::North => (seat.y1, seat.y2),
+        BenchWall::East => (seat.x1, seat.x2),
+        BenchWall::West => (seat.x2, seat.x1),
+    };
+    let (x1, x2) = (view_x(front), view_x(back));
+    let bearer = bottom - BENCH_FRAME.1;
+    add_rectangle(drawing, LAYER_FIXTURES, x1.min(x2), bearer, x1.max(x2), bottom);
+    if bearer > floor {
+        let inward = if x2 > x1 { 1.0 } else { -1.0 };
+        let leg = x1 + inward * BENCH_FRAME.0;
+        add_rectangle(drawing, LAYER_FIXTURES, x1.min(leg), floor, x1.max(leg), bearer);
+    }
+}
+
+/// Backrest boards cut square to the wall, `view_x` mapping the plan
+/// position across them
+fn draw_backrest_profile(drawing: &mut Drawing, backrest: &Backrest, view_x: impl Fn(f64) -> f64, floor: f64) {
+    let (a, b) = if backrest.wall == BenchWall::North { (backrest.y1, backrest.y2) } else { (backrest.x1, backrest.x2) };
+    let (x1, x2) = (view_x(a), view_x(b));
+    for i in 0..backrest.boards {
+        let z = floor + backrest.bottom + i as f64 * (BENCH_SLAT_WIDTH + backrest.gap);
+        add_rectangle(drawing, LAYER_FIXTURES, x1.min(x2), z, x1.max(x2), z + BENCH_SLAT_WIDTH);
+    }
+}
+
+/// Benches in section A-A, which looks south from the middle of the sauna so
+/// model x runs from right to left: side runs crossing the cut are drawn cut,
+/// seats beyond it as seen, with the height of each seat dimensioned
+pub fn draw_benches_section(drawing: &mut Drawing, model: &BuildingModel, check: &BenchCheck, offset_x: f64, offset_y: f64) {
+    let room = model.sauna();
+    let cut = (room.y1 + room.y2) / 2.0;
+    let floor = offset_y + DECK_ELEV_HEIGHT;
+    let section_x = |x: f64| offset_x + SAUNA_WIDTH - x;
+
+    let mut heights: Vec<f64> = Vec::new();
+    for seat in &check.seats {
+        if seat.y1 <= cut && seat.y2 >= cut {
+            if seat.along_x() {
+                add_rectangle(drawing, LAYER_FIXTURES, section_x(seat.x2), floor + seat.height - BENCH_SLAT_THICKNESS, section_x(seat.x1), floor + seat.height);
+            } else {
+                draw_seat_profile(drawing, seat, section_x, floor);
+            }
+        } else if seat.y2 < cut {
+            add_rectangle(drawing, LAYER_FIXTURES, section_x(seat.x2), floor + seat.height - BENCH_SLAT_THICKNESS, section_x(seat.x1), floor + seat.height);
+        } else {
+            continue;
+        }
+        if !heights.iter().any(|&height| (height - seat.height).abs() < 1e-9) {
+            heights.push(seat.height);
+        }
+    }
+    for backrest in check.backrests.iter().filter(|backrest| backrest.wall != BenchWall::North) {
+        if backrest.y1 <= cut && backrest.y2 >= cut {
+            draw_backrest_profile(drawing, backrest, section_x, floor);
+        }
+    }
+
+    heights.sort_by(f64::total_cmp);
+    for (i, height) in heights.into_iter().enumerate() {
+        draw_dim_vertical(drawing, offset_x - 0.50, floor, floor + height, -0.20 * (i + 1) as f64, "");
+    }
+}
+
+/// Section through the back run looking west, with the sauna floor at the
+/// south wall face at (x, y) and the back wall on the right: every tier with
+/// its slats, bearer and leg, the backrest, and the heights, depths and the
+/// headroom over the top seat
+pub fn create_bench_section(drawing: &mut Drawing, model: &BuildingModel, check: &BenchCheck, x: f64, y: f64) {
+    let room = model.sauna();
+    let ceiling = room.ceiling.unwrap_or(BUILDING_HEIGHT);
+    let view_x = |plan_y: f64| x + plan_y - room.y1;
+    let (south, north) = (view_x(room.y1), view_x(room.y2));
+
+    // Floor, ceiling and the wall faces
+    add_line(drawing, LAYER_SECTION, south, y, north, y);
+    add_line(drawing, LAYER_SECTION, south, y + ceiling, north, y + ceiling);
+    add_line(drawing, LAYER_SECTION, south, y, south, y + ceiling);
+    add_line(drawing, LAYER_SECTION, north, y, north, y + ceiling);
+
+    let back: Vec<&BenchSeat> = check.seats.iter().filter(|seat| seat.wall == BenchWall::North).collect();
+    for seat in &back {
+        draw_seat_profile(drawing, seat, view_x, y);
+    }
+    for backrest in check.backrests.iter().filter(|backrest| backrest.wall == BenchWall::North) {
+        draw_backrest_profile(drawing, backrest, view_x, y);
+    }
+
+    // Seat heights on the left, depths below, headroom on the right
+    for (i, seat) in back.iter().enumerate() {
+        draw_dim_vertical(drawing, south, y, y + seat.height, -0.20 * (i + 1) as f64, "");
+        draw_dim_horizontal(drawing, view_x(seat.y1), view_x(seat.y2), y, -0.30, "");
+    }
+    if let Some(top) = back.last() {
+        draw_dim_vertical(drawing, north, y + top.height, y + ceiling, 0.30, "");
+    }
+
+    add_text(drawing, LAYER_TEXT, south, y + ceiling + 0.50, LABEL_TEXT_HEIGHT, "BENCH SECTION".to_string());
+    add_text(
+        drawing,
+        LAYER_ANNO,
+        south,
+        y - 0.70,
+        DIM_TEXT_HEIGHT,
+        format!(
+            "THROUGH THE BACK RUN LOOKING WEST, SLATS {:.0}x{:.0}, FRAME {:.0}x{:.0}",
+            BENCH_SLAT_WIDTH * 1000.0,
+            BENCH_SLAT_THICKNESS * 1000.0,
+            BENCH_FRAME.0 * 1000.0,
+            BENCH_FRAME.1 * 1000.0
+        ),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Clear floor of a sauna 2.0 m wide and 3.0 m deep
+    const ROOM: (f64, f64, f64, f64) = (0.0, 0.0, 2.0, 3.0);
+
+    fn seats(layout: BenchLayout) -> Vec<BenchSeat> {
+        bench_seats(layout, ROOM.0, ROOM.1, ROOM.2, ROOM.3)
+    }
+
+    fn ids(seats: &[BenchSeat]) -> Vec<&str> {
+        seats.iter().map(|seat| seat.id.as_str()).collect()
+    }
+
+    fn assert_no_overlaps(seats: &[BenchSeat]) {
+        for (i, a) in seats.iter().enumerate() {
+            for b in &seats[i + 1..] {
+                let apart = a.x2 <= b.x1 + 1e-9 || b.x2 <= a.x1 + 1e-9 || a.y2 <= b.y1 + 1e-9 || b.y2 <= a.y1 + 1e-9;
+                assert!(apart, "{} overlaps {}", a.id, b.id);
+            }
+        }
+    }
+
+    #[test]
+    fn straight_runs_every_tier_across_the_back_wall() {
+        let seats = seats(BenchLayout::Straight);
+        assert_eq!(ids(&seats), ["bench-north-1", "bench-north-2"]);
+        for seat in &seats {
+            assert_eq!(seat.wall, BenchWall::North);
+            assert!((seat.length() - 2.0).abs() < 1e-9);
+        }
+        assert!((seats[1].y2 - ROOM.3).abs() < 1e-9);
+        assert_no_overlaps(&seats);
+    }
+
+    #[test]
+    fn l_side_run_fits_every_tier() {
+        let seats = seats(BenchLayout::L);
+        assert_eq!(ids(&seats), ["bench-north-1", "bench-north-2", "bench-west-1", "bench-west-2"]);
+
+        // The side run ends BENCH_SIDE_LENGTH from the back wall; the lower
+        // tier stops at the front of the back run's top tier
+        let (lower, top) = (&seats[2], &seats[3]);
+        assert!((top.y1 - (ROOM.3 - BENCH_SIDE_LENGTH)).abs() < 1e-9);
+        assert!((top.length() - (BENCH_SIDE_LENGTH - top.depth())).abs() < 1e-9);
+        assert!((lower.length() - (top.length() - lower.depth())).abs() < 1e-9);
+        assert!(lower.length() >= BENCH_MIN_SEAT_LENGTH);
+
+        // The back run's lower tier starts at the front of the west top tier
+        assert!((seats[0].x1 - top.x2).abs() < 1e-9);
+        assert_no_overlaps(&seats);
+    }
+
+    #[test]
+    fn short_room_leaves_out_the_lower_side_tier() {
+        // 1.70 m deep: the lower west tier would be only 0.50 m long
+        let all = layout_seats(BenchLayout::L, 0.0, 0.0, 2.0, 1.70);
+        let stub = all.iter().find(|seat| seat.id == "bench-west-1").expect("lower west tier laid out");
+        assert!((stub.length() - 0.50).abs() < 1e-9);
+        let seats = bench_seats(BenchLayout::L, 0.0, 0.0, 2.0, 1.70);
+        assert_eq!(ids(&seats), ["bench-north-1", "bench-north-2", "bench-west-2"]);
+    }
+
+    #[test]
+    fn u_side_runs_mirror_each_other() {
+        // 3.0 m wide, so both side runs fit all their tiers side by side
+        let seats = bench_seats(BenchLayout::U, 0.0, 0.0, 3.0, 3.0);
+        assert_eq!(
+            ids(&seats),
+            ["bench-north-1", "bench-north-2", "bench-east-1", "bench-east-2", "bench-west-1", "bench-west-2"]
+        );
+        let (east, west) = (&seats[3], &seats[5]);
+        assert!((east.x2 - 3.0).abs() < 1e-9 && west.x1.abs() < 1e-9);
+        assert!((east.length() - west.length()).abs() < 1e-9);
+        assert!((seats[0].length() - (3.0 - 2.0 * east.depth())).abs() < 1e-9);
+        for seat in &seats {
+            assert!(seat.length() >= BENCH_MIN_SEAT_LENGTH);
+        }
+        assert_no_overlaps(&seats);
+    }
+}
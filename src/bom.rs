@@ -0,0 +1,294 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+
+use crate::benches::bench_check;
+use crate::constants::*;
+use crate::foundation::{edge_beam_depth, edge_beam_length, foundation, pad_volume, pier_height, pier_volume};
+use crate::framing::{deck_framing, DeckFraming};
+use crate::model::BuildingModel;
+use crate::railings::{baluster_length, railing_check};
+use crate::stairs::stair_schedule;
+use crate::wall_framing::model_wall_framing;
+
+// ============================================================================
+// BILL OF MATERIALS
+// Cut lists from the wall and deck framing, the deck stairs, the railings and
+// the sauna benches, grouped into identical pieces, and the foundation with
+// its concrete volumes.
+// Lengths are rounded to the millimetre before pieces are counted together.
+// ============================================================================
+
+pub struct BomLine {
+    pub group: &'static str,
+    pub item: String,
+    pub section: String,
+    pub length: Option<f64>, // Cut length in m, None for items counted by the piece
+    pub quantity: u32,
+    pub volume: Option<f64>, // Concrete per piece in m³
+}
+
+impl BomLine {
+    /// Total running length in m
+    pub fn total_length(&self) -> Option<f64> {
+        self.length.map(|length| length * self.quantity as f64)
+    }
+
+    /// Total concrete volume in m³
+    pub fn total_volume(&self) -> Option<f64> {
+        self.volume.map(|volume| volume * self.quantity as f64)
+    }
+}
+
+/// Millimetres, for grouping equal cut lengths
+fn millimetres(length: f64) -> i64 {
+    (length * 1000.0).round() as i64
+}
+
+/// Wall framing cut list: identical pieces across all walls counted together,
+/// with plied headers counted by the ply
+fn wall_framing_lines(model: &BuildingModel) -> Vec<BomLine> {
+    let mut pieces: BTreeMap<(&'static str, String, i64), u32> = BTreeMap::new();
+    for framing in model_wall_framing(model) {
+        for member in &framing.members {
+            let (section, count) = member.pieces();
+            *pieces
+                .entry((member.kind.name(), section, millimetres(member.length())))
+                .or_default() += count;
+        }
+    }
+    pieces
+        .into_iter()
+        .map(|((item, section, length), quantity)| BomLine {
+            group: "WALL FRAMING",
+            item: item.to_string(),
+            section,
+            length: Some(length as f64 / 1000.0),
+            quantity,
+            volume: None,
+        })
+        .collect()
+}
+
+/// Deck framing cut list: joists and beam plies
+fn deck_framing_lines(framing: &DeckFraming) -> Vec<BomLine> {
+    let timber = |depth: f64| format!("{:.0}x{:.0} {}", JOIST_WIDTH * 1000.0, depth * 1000.0, TIMBER_GRADE);
+    vec![
+        BomLine {
+            group: "DECK FRAMING",
+            item: "JOIST".to_string(),
+            section: timber(JOIST_DEPTH),
+            length: Some(millimetres(framing.y2 - framing.y1) as f64 / 1000.0),
+            quantity: framing.joists.len() as u32,
+            volume: None,
+        },
+        BomLine {
+            group: "DECK FRAMING",
+            item: "BEAM PLY".to_string(),
+            section: timber(BEAM_DEPTH),
+            length: Some(millimetres(framing.x2 - framing.x1) as f64 / 1000.0),
+            quantity: framing.beams.len() as u32 * BEAM_PLIES,
+            volume: None,
+        },
+    ]
+}
+
+/// Foundation elements with their concrete volumes
+fn foundation_lines(framing: &DeckFraming) -> Vec<BomLine> {
+    let foundation = foundation(framing);
+    let count = foundation.elements.len() as u32;
+    let mm = |size: f64| format!("{:.0}", size * 1000.0);
+    let line = |item: &str, section: String, length: Option<f64>, quantity: u32, volume: Option<f64>| BomLine {
+        group: "FOUNDATION",
+        item: item.to_string(),
+        section,
+        length: length.map(|length| millimetres(length) as f64 / 1000.0),
+        quantity,
+        volume,
+    };
+    match foundation.kind {
+        FoundationType::Piers => vec![
+            line(
+                "PAD FOOTING",
+                format!("{}x{}x{}", mm(FOOTING_SIZE), mm(FOOTING_SIZE), mm(FOOTING_THICKNESS)),
+                None,
+                count,
+                Some(pad_volume()),
+            ),
+            line("PIER", format!("DIA {}", mm(PIER_DIAMETER)), Some(pier_height()), count, Some(pier_volume())),
+        ],
+        FoundationType::ScrewPiles => vec![line(
+            "SCREW PILE",
+            format!("DIA {}/{}", mm(SCREW_PILE_DIAMETER), mm(SCREW_PILE_HELIX)),
+            Some(SCREW_PILE_LENGTH),
+            count,
+            None,
+        )],
+        FoundationType::StripFooting => {
+            let length = framing.x2 - framing.x1;
+            vec![
+                line(
+                    "STRIP FOOTING",
+                    format!("{}x{}", mm(STRIP_WIDTH), mm(FOOTING_THICKNESS)),
+                    Some(length),
+                    count,
+                    Some(length * STRIP_WIDTH * FOOTING_THICKNESS),
+                ),
+                line(
+                    "STEM WALL",
+                    format!("{}x{}", mm(STEM_WALL_WIDTH), mm(pier_height())),
+                    Some(length),
+                    count,
+                    Some(length * STEM_WALL_WIDTH * pier_height()),
+                ),
+            ]
+        }
+        FoundationType::Slab => {
+            let (width, depth) = (framing.x2 - framing.x1, framing.y2 - framing.y1);
+            let perimeter = edge_beam_length(width, depth);
+            vec![
+                line("SLAB", format!("{} THK", mm(SLAB_THICKNESS)), None, 1, Some(width * depth * SLAB_THICKNESS)),
+                line(
+                    "EDGE BEAM",
+                    format!("{}x{}", mm(STRIP_WIDTH), mm(edge_beam_depth())),
+                    Some(perimeter),
+                    1,
+                    Some(perimeter * STRIP_WIDTH * edge_beam_depth()),
+                ),
+            ]
+        }
+    }
+}
+
+/// Stair stringers and treads, identical pieces across all stairs counted together
+fn stair_lines(model: &BuildingModel) -> Vec<BomLine> {
+    let mut pieces: BTreeMap<(&'static str, String, i64), u32> = BTreeMap::new();
+    for stair in stair_schedule(model).stairs {
+        let stringer = format!("{:.0}x{:.0} {}", JOIST_WIDTH * 1000.0, STAIR_STRINGER_DEPTH * 1000.0, TIMBER_GRADE);
+        *pieces.entry(("STRINGER", stringer, millimetres(stair.stringer_length()))).or_default() += stair.stringers;
+        let tread = format!("{:.0}x{:.0}", stair.going * 1000.0, STAIR_TREAD_THICKNESS * 1000.0);
+        *pieces.entry(("TREAD", tread, millimetres(stair.width))).or_default() += stair.treads();
+    }
+    pieces
+        .into_iter()
+        .map(|((item, section, length), quantity)| BomLine {
+            group: "STAIRS",
+            item: item.to_string(),
+            section,
+            length: Some(length as f64 / 1000.0),
+            quantity,
+            volume: None,
+        })
+        .collect()
+}
+
+/// Railing posts, rails and balusters; posts run down the rim joist to be bolted on
+fn railing_lines(model: &BuildingModel) -> Vec<BomLine> {
+    let check = railing_check(model);
+    let mm = |size: f64| format!("{:.0}", size * 1000.0);
+    let mut pieces: BTreeMap<(&'static str, String, i64), u32> = BTreeMap::new();
+    let post = format!("{}x{}", mm(RAILING_POST_SIZE), mm(RAILING_POST_SIZE));
+    *pieces.entry(("POST", post, millimetres(RAILING_HEIGHT + JOIST_DEPTH))).or_default() += check.post_positions().len() as u32;
+    for railing in &check.railings {
+        let rail = format!("{}x{}", mm(RAIL_SIZE.0), mm(RAIL_SIZE.1));
+        *pieces.entry(("RAIL", rail, millimetres(railing.length()))).or_default() += 2;
+        let baluster = format!("{}x{}", mm(BALUSTER_SIZE), mm(BALUSTER_SIZE));
+        *pieces.entry(("BALUSTER", baluster, millimetres(baluster_length()))).or_default() += railing.balusters();
+    }
+    pieces
+        .into_iter()
+        .filter(|(_, quantity)| *quantity > 0)
+        .map(|((item, section, length), quantity)| BomLine {
+            group: "RAILINGS",
+            item: item.to_string(),
+            section,
+            length: Some(length as f64 / 1000.0),
+            quantity,
+            volume: None,
+        })
+        .collect()
+}
+
+/// Bench slats and backrest boards, one slat per board run along each seat
+fn bench_lines(model: &BuildingModel) -> Vec<BomLine> {
+    let check = bench_check(model);
+    let board = format!("{:.0}x{:.0}", BENCH_SLAT_WIDTH * 1000.0, BENCH_SLAT_THICKNESS * 1000.0);
+    let mut pieces: BTreeMap<(&'static str, i64), u32> = BTreeMap::new();
+    for seat in &check.seats {
+        *pieces.entry(("SLAT", millimetres(seat.length()))).or_default() += seat.slats;
+    }
+    for backrest in &check.backrests {
+        *pieces.entry(("BACKREST", millimetres(backrest.length()))).or_default() += backrest.boards;
+    }
+    pieces
+        .into_iter()
+        .map(|((item, length), quantity)| BomLine {
+            group: "BENCHES",
+            item: item.to_string(),
+            section: board.clone(),
+            length: Some(length as f64 / 1000.0),
+            quantity,
+            volume: None,
+        })
+        .collect()
+}
+
+/// Bill of materials for the whole building
+pub fn bill_of_materials(model: &BuildingModel) -> Vec<BomLine> {
+    let framing = deck_framing(model);
+    let mut lines = wall_framing_lines(model);
+    lines.extend(deck_framing_lines(&framing));
+    lines.extend(stair_lines(model));
+    lines.extend(railing_lines(model));
+    lines.extend(bench_lines(model));
+    lines.extend(foundation_lines(&framing));
+    lines
+}
+
+/// Running length per timber section, for ordering stock
+pub fn section_totals(lines: &[BomLine]) -> Vec<(String, f64)> {
+    let mut totals: BTreeMap<String, f64> = BTreeMap::new();
+    for line in lines.iter().filter(|line| line.group != "FOUNDATION") {
+        if let Some(total) = line.total_length() {
+            *totals.entry(line.section.clone()).or_default() += total;
+        }
+    }
+    totals.into_iter().collect()
+}
+
+/// Concrete volume in m³, for ordering ready-mix
+pub fn concrete_total(lines: &[BomLine]) -> f64 {
+    lines.iter().filter_map(BomLine::total_volume).sum()
+}
+
+/// One line of the console listing
+pub fn bom_line(line: &BomLine) -> String {
+    let length = match line.length {
+        Some(length) => format!("{:>6.3} m", length),
+        None => "       -".to_string(),
+    };
+    let line_text = format!("{:<13} {:<13} {:<16} {}  x{}", line.group, line.item, line.section, length, line.quantity);
+    match line.total_volume() {
+        Some(volume) => format!("{}  {:.3} m³", line_text, volume),
+        None => line_text,
+    }
+}
+
+/// Bill of materials as CSV
+pub fn bom_csv(lines: &[BomLine]) -> String {
+    let mut csv = String::from("group,item,section,length_m,quantity,total_m,volume_m3\n");
+    for line in lines {
+        let length = line.length.map(|l| format!("{:.3}", l)).unwrap_or_default();
+        let total = line.total_length().map(|t| format!("{:.3}", t)).unwrap_or_default();
+        let volume = line.total_volume().map(|v| format!("{:.3}", v)).unwrap_or_default();
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            line.group, line.item, line.section, length, line.quantity, total, volume
+        ));
+    }
+    csv
+}
+
+pub fn save_bom(model: &BuildingModel, path: &str) -> io::Result<()> {
+    fs::write(path, bom_csv(&bill_of_materials(model)))
+}
@@ -0,0 +1,192 @@
+use dxf::Drawing;
+
+use crate::constants::*;
+use crate::electrical::draw_device_symbol;
+use crate::helpers::{add_circle, add_line, add_rectangle, add_text};
+use crate::layers::*;
+use crate::model::{BuildingModel, Device, DeviceKind, PipeService};
+use crate::plumbing::nominal_size;
+
+// ============================================================================
+// REFLECTED CEILING PLAN
+// The ceiling seen from below, drawn the right way round as a plan: walls cut
+// just under the ceiling, the roof edge beyond, ceiling heights, panelling
+// laid along the long side of each room, light fittings and pipes venting
+// through the roof. The lighting schedule counts the fittings by mark and
+// checks the ones in the sauna against the ceiling temperature and the heater.
+// ============================================================================
+
+/// Light fittings of one mark and where they are
+pub struct FixtureCount {
+    pub fixture: &'static LightFixture,
+    pub devices: Vec<&'static str>,
+}
+
+pub struct LightingSchedule {
+    pub fixtures: Vec<FixtureCount>,
+    pub problems: Vec<String>,
+}
+
+fn light_mark(device: &Device) -> Option<&'static str> {
+    match device.kind {
+        DeviceKind::Light(mark) => Some(mark),
+        _ => None,
+    }
+}
+
+/// Lighting fixture schedule with the sauna temperature and heater checks
+pub fn lighting_schedule(model: &BuildingModel) -> LightingSchedule {
+    let mut problems = Vec::new();
+    let fixtures = LIGHT_FIXTURES
+        .iter()
+        .map(|fixture| FixtureCount {
+            fixture,
+            devices: model.devices.iter().filter(|d| light_mark(d) == Some(fixture.mark)).map(|d| d.id).collect(),
+        })
+        .collect();
+
+    let sauna = model.sauna();
+    let heater = model.furnishing("heater");
+    for device in &model.devices {
+        let Some(mark) = light_mark(device) else { continue };
+        let name = device.id.to_uppercase();
+        let Some(fixture) = LIGHT_FIXTURES.iter().find(|f| f.mark == mark) else {
+            problems.push(format!("{}: NO FITTING {} IN THE SCHEDULE", name, mark));
+            continue;
+        };
+        if sauna.contains(device.x, device.y) && fixture.max_temperature < SAUNA_CEILING_TEMPERATURE {
+            problems.push(format!(
+                "{}: {} RATED {:.0} °C IN THE SAUNA (MIN {:.0} °C)",
+                name, mark, fixture.max_temperature, SAUNA_CEILING_TEMPERATURE
+            ));
+        }
+        if let Some(heater) = heater {
+            let clearance = heater.footprint.distance_to(device.x, device.y);
+            if clearance < HEATER_LIGHT_CLEARANCE {
+                problems.push(format!(
+                    "{}: {:.2} m FROM THE HEATER (MIN {:.2} m)",
+                    name, clearance, HEATER_LIGHT_CLEARANCE
+                ));
+            }
+        }
+    }
+
+    LightingSchedule { fixtures, problems }
+}
+
+impl LightingSchedule {
+    /// Report lines for the sheet and the console
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "{:<4} {:<36} {:>4} {:>6} {:<4} {:>3}",
+            "MARK", "DESCRIPTION", "W", "RATED", "IP", "QTY"
+        )];
+        for count in &self.fixtures {
+            let fixture = count.fixture;
+            lines.push(format!(
+                "{:<4} {:<36} {:>4.0} {:>3.0} °C {:<4} {:>3}",
+                fixture.mark,
+                fixture.description,
+                fixture.watts,
+                fixture.max_temperature,
+                fixture.ip_rating,
+                count.devices.len()
+            ));
+        }
+        for count in self.fixtures.iter().filter(|c| !c.devices.is_empty()) {
+            let devices: Vec<String> = count.devices.iter().map(|id| id.to_uppercase()).collect();
+            lines.push(format!("{}: {}", count.fixture.mark, devices.join(", ")));
+        }
+        if self.problems.is_empty() {
+            lines.push(format!(
+                "SAUNA FITTINGS RATED FOR {:.0} °C AND CLEAR OF THE HEATER",
+                SAUNA_CEILING_TEMPERATURE
+            ));
+        }
+        lines.extend(self.problems.iter().map(|problem| format!("WARNING: {}", problem)));
+        lines
+    }
+}
+
+/// Reflected ceiling plan with the model origin drawn at (x, y)
+pub fn create_reflected_ceiling_plan(drawing: &mut Drawing, model: &BuildingModel, x: f64, y: f64) {
+    // Roof edge beyond the walls
+    add_rectangle(drawing, LAYER_CEILING, x + model.roof.min.x, y + model.roof.min.y, x + model.roof.max.x, y + model.roof.max.y);
+    for wall in &model.walls {
+        let layer = if wall.exterior { LAYER_WALLS } else { LAYER_WALLS_INNER };
+        add_rectangle(drawing, layer, x + wall.x1, y + wall.y1, x + wall.x2, y + wall.y2);
+    }
+
+    // Panelling board joints along the long side, with the ceiling height and lining
+    let lining = &ROOF_ASSEMBLY.layers[ROOF_ASSEMBLY.layers.len() - 1];
+    for room in &model.rooms {
+        let Some(height) = room.ceiling else { continue };
+        let along_x = room.x2 - room.x1 >= room.y2 - room.y1;
+        let (start, end) = if along_x { (room.y1, room.y2) } else { (room.x1, room.x2) };
+        let mut joint = start + CEILING_BOARD_WIDTH;
+        while joint < end - 1e-9 {
+            if along_x {
+                add_line(drawing, LAYER_CEILING_PATTERN, x + room.x1, y + joint, x + room.x2, y + joint);
+            } else {
+                add_line(drawing, LAYER_CEILING_PATTERN, x + joint, y + room.y1, x + joint, y + room.y2);
+            }
+            joint += CEILING_BOARD_WIDTH;
+        }
+
+        let (tag_x, tag_y) = (x + room.label_x - 0.35, y + room.y2 - 0.35);
+        let label = if room.interior { "CLG" } else { "SOFFIT" };
+        add_text(drawing, LAYER_TEXT, tag_x, tag_y, DIM_TEXT_HEIGHT, format!("{} +{:.2}", label, height));
+        add_text(
+            drawing,
+            LAYER_ANNO,
+            tag_x,
+            tag_y - DIM_TEXT_HEIGHT * 1.5,
+            DIM_TEXT_HEIGHT * 0.8,
+            format!("{} {:.0}", lining.name, lining.thickness * 1000.0),
+        );
+    }
+
+    // Light fittings with their marks
+    for device in &model.devices {
+        let Some(mark) = light_mark(device) else { continue };
+        draw_device_symbol(drawing, device.kind, x + device.x, y + device.y);
+        add_text(drawing, LAYER_ELEC_ANNO, x + device.x + 0.14, y + device.y + 0.05, DIM_TEXT_HEIGHT, mark.to_string());
+    }
+
+    // Pipes venting through the roof
+    for pipe in model.pipes.iter().filter(|p| p.service == PipeService::Vent) {
+        let top = &pipe.points[pipe.points.len() - 1];
+        if top.z <= BUILDING_HEIGHT {
+            continue;
+        }
+        add_circle(drawing, LAYER_PLUMB_VENT, x + top.x, y + top.y, 0.08);
+        add_text(
+            drawing,
+            LAYER_PLUMB_ANNO,
+            x + top.x + 0.12,
+            y + top.y + 0.12,
+            DIM_TEXT_HEIGHT * 0.8,
+            format!("{} VTR", nominal_size(pipe.size)),
+        );
+    }
+
+    add_text(
+        drawing,
+        LAYER_TEXT,
+        x,
+        y + model.roof.max.y + 0.40,
+        LABEL_TEXT_HEIGHT,
+        "REFLECTED CEILING PLAN".to_string(),
+    );
+    add_text(
+        drawing,
+        LAYER_ANNO,
+        x,
+        y + model.roof.max.y + 0.15,
+        DIM_TEXT_HEIGHT,
+        format!(
+            "HEIGHTS ABOVE FINISHED FLOOR, {:.0} mm BOARDS ALONG THE LONG SIDE, VTR VENT THROUGH ROOF",
+            CEILING_BOARD_WIDTH * 1000.0
+        ),
+    );
+}
@@ -27,10 +27,42 @@ pub const WINDOW_HEIGHT: f64 = 0.80;       // Window height
 pub const WINDOW_SILL_HEIGHT: f64 = 1.00;  // Height from floor to window sill
 
 // Hot tub dimensions
-pub const HOT_TUB_DIAMETER: f64 = 2.00;    // Hot tub diameter
-pub const HOT_TUB_RADIUS: f64 = HOT_TUB_DIAMETER / 2.0;
+// The shell is a circle (Round) or a rectangle with rounded corners (Square,
+// Rectangular). Positions round the shell (seats, jets, steps) are fractions
+// of the way round it, counterclockwise from the middle of the south side.
+#[allow(dead_code)] // Only the variant chosen in HOT_TUB_SHAPE is constructed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HotTubShape {
+    Round,       // HOT_TUB_DIAMETER across
+    Square,      // HOT_TUB_DIAMETER a side
+    Rectangular, // HOT_TUB_LENGTH by HOT_TUB_WIDTH
+}
+
+pub const HOT_TUB_SHAPE: HotTubShape = HotTubShape::Round;
+pub const HOT_TUB_DIAMETER: f64 = 2.00;    // Round tub diameter, square tub side
+pub const HOT_TUB_LENGTH: f64 = 2.30;      // Rectangular tub, east-west
+pub const HOT_TUB_WIDTH: f64 = 2.00;       // Rectangular tub, north-south
+pub const HOT_TUB_CORNER_RADIUS: f64 = 0.30; // Square and rectangular shell corners
+pub const HOT_TUB_SIZE: (f64, f64) = match HOT_TUB_SHAPE {
+    HotTubShape::Round | HotTubShape::Square => (HOT_TUB_DIAMETER, HOT_TUB_DIAMETER),
+    HotTubShape::Rectangular => (HOT_TUB_LENGTH, HOT_TUB_WIDTH),
+}; // Shell east-west and north-south
 pub const HOT_TUB_OFFSET_X: f64 = 1.50;    // Hot tub center offset from right edge
 pub const HOT_TUB_OFFSET_Y: f64 = 0.20;    // Hot tub overlap with deck edge
+pub const HOT_TUB_WALL: f64 = 0.15;        // Shell wall thickness (outer to water line)
+pub const HOT_TUB_WATER_DEPTH: f64 = 0.80; // Water depth when filled
+pub const HOT_TUB_SHELL_MASS: f64 = 350.0; // Empty tub in kg
+pub const HOT_TUB_SEATS: u32 = 5;          // Seats spaced evenly round the shell, one occupant each
+pub const HOT_TUB_SEAT_WIDTH: f64 = 0.55;  // Along the shell
+pub const HOT_TUB_SEAT_DEPTH: f64 = 0.40;  // From the water line inwards
+pub const HOT_TUB_JETS: &[f64] = &[0.08, 0.12, 0.28, 0.32, 0.48, 0.52, 0.68, 0.72, 0.88, 0.92]; // Two behind each seat
+pub const HOT_TUB_STEPS: u32 = 2;          // Steps up to the rim, 0 for none
+pub const HOT_TUB_STEP_AT: f64 = 0.25;     // East side
+pub const HOT_TUB_STEP_WIDTH: f64 = 0.90;
+pub const HOT_TUB_STEP_TREAD: f64 = 0.30;
+pub const HOT_TUB_COVER_OVERHANG: f64 = 0.05; // Cover skirt past the shell
+pub const OCCUPANT_MASS: f64 = 80.0;       // kg per occupant
+pub const HOT_TUB_SLAB: Option<(f64, f64, f64, f64)> = None; // Concrete pad (x1, y1, x2, y2)
 
 // Elevation dimensions
 pub const BUILDING_HEIGHT: f64 = 2.50;     // Floor to ceiling height
@@ -39,7 +71,10 @@ pub const ROOF_THICKNESS: f64 = 0.20;      // Flat roof slab thickness
 pub const ROOF_OVERHANG: f64 = 0.30;       // Roof overhang
 
 // Fixture dimensions
-pub const HEATER_DIAMETER: f64 = 0.50;     // Sauna heater diameter
+pub const HEATER_MODEL: &str = "E-ROUND-6"; // Sauna heater from the catalogue (templates/heaters.txt)
+pub const CHIMNEY_ABOVE_ROOF: f64 = 0.91;  // Flue top above the roof it passes through (3 ft)
+pub const FLUE_COMBUSTIBLE_CLEARANCE: f64 = 0.05; // Insulated chimney to combustibles (2")
+pub const HEATER_CONTROL_HEIGHT: f64 = 1.50; // Electric heater control panel, outside the sauna
 pub const BENCH_DEPTH: f64 = 0.60;         // Sauna bench depth
 pub const BENCH_HEIGHT_LOWER: f64 = 0.45;  // Lower bench height
 pub const BENCH_HEIGHT_UPPER: f64 = 0.90;  // Upper bench height
@@ -48,9 +83,312 @@ pub const BENCH_HEIGHT_UPPER: f64 = 0.90;  // Upper bench height
 pub const POST_SIZE: f64 = 0.15;           // Support post dimensions (150mm)
 pub const POST_INSET: f64 = 0.30;          // Post inset from edges
 
+// Wall framing (timber studs on a bottom plate under double top plates)
+pub const STUD_WIDTH: f64 = 0.045;         // 45mm studs and plates
+pub const STUD_DEPTH: f64 = 0.095;         // 95mm studs (rest of the wall is lining)
+pub const STUD_SPACING: f64 = 0.60;        // Stud centres
+pub const TOP_PLATES: u32 = 2;             // Top plates over the studs
+pub const HEADER_PLIES: u32 = 2;           // Plies in each door/window header
+
+// Thermal envelope: assemblies are listed outside to inside. A layer without a
+// conductivity is a ventilated cavity: it and everything outside it are ignored
+// and the outside surface resistance becomes the inside one (ISO 6946).
+pub struct AssemblyLayer {
+    pub name: &'static str,
+    pub thickness: f64,            // m
+    pub conductivity: Option<f64>, // W/mK, None for a ventilated cavity
+    pub timber_fraction: f64,      // Share of studs, rafters or joists bridging the layer
+}
+
+pub struct Assembly {
+    pub name: &'static str,
+    pub layers: &'static [AssemblyLayer],
+    pub inside_resistance: f64,  // m²K/W
+    pub outside_resistance: f64, // m²K/W
+}
+
+pub const TIMBER_CONDUCTIVITY: f64 = 0.13;     // W/mK, softwood
+pub const INSULATION_CONDUCTIVITY: f64 = 0.037; // W/mK, mineral wool
+
+pub const WALL_ASSEMBLY: Assembly = Assembly {
+    name: "WALL",
+    layers: &[
+        AssemblyLayer { name: "CLADDING", thickness: 0.019, conductivity: Some(TIMBER_CONDUCTIVITY), timber_fraction: 0.0 },
+        AssemblyLayer { name: "BATTENS", thickness: 0.022, conductivity: None, timber_fraction: 0.0 },
+        AssemblyLayer { name: "MEMBRANE", thickness: 0.001, conductivity: Some(0.17), timber_fraction: 0.0 },
+        AssemblyLayer { name: "STUD+INSULATION", thickness: STUD_DEPTH, conductivity: Some(INSULATION_CONDUCTIVITY), timber_fraction: STUD_WIDTH / STUD_SPACING },
+        AssemblyLayer { name: "VAPOUR FOIL", thickness: 0.001, conductivity: Some(0.17), timber_fraction: 0.0 },
+        AssemblyLayer { name: "PANELLING", thickness: 0.012, conductivity: Some(TIMBER_CONDUCTIVITY), timber_fraction: 0.0 },
+    ],
+    inside_resistance: 0.13,
+    outside_resistance: 0.04,
+};
+
+pub const ROOF_ASSEMBLY: Assembly = Assembly {
+    name: "ROOF",
+    layers: &[
+        AssemblyLayer { name: "EPDM", thickness: 0.002, conductivity: Some(0.25), timber_fraction: 0.0 },
+        AssemblyLayer { name: "OSB DECK", thickness: 0.018, conductivity: Some(TIMBER_CONDUCTIVITY), timber_fraction: 0.0 },
+        AssemblyLayer { name: "VENTILATED GAP", thickness: 0.020, conductivity: None, timber_fraction: 0.0 },
+        AssemblyLayer { name: "RAFTER+INSULATION", thickness: 0.145, conductivity: Some(INSULATION_CONDUCTIVITY), timber_fraction: STUD_WIDTH / STUD_SPACING },
+        AssemblyLayer { name: "VAPOUR FOIL", thickness: 0.001, conductivity: Some(0.17), timber_fraction: 0.0 },
+        AssemblyLayer { name: "PANELLING", thickness: 0.014, conductivity: Some(TIMBER_CONDUCTIVITY), timber_fraction: 0.0 },
+    ],
+    inside_resistance: 0.10,
+    outside_resistance: 0.04,
+};
+
+pub const FLOOR_ASSEMBLY: Assembly = Assembly {
+    name: "FLOOR",
+    layers: &[
+        AssemblyLayer { name: "WINDPROOF BOARD", thickness: 0.009, conductivity: Some(TIMBER_CONDUCTIVITY), timber_fraction: 0.0 },
+        AssemblyLayer { name: "JOIST+INSULATION", thickness: 0.145, conductivity: Some(INSULATION_CONDUCTIVITY), timber_fraction: JOIST_WIDTH / JOIST_SPACING },
+        AssemblyLayer { name: "OSB", thickness: 0.018, conductivity: Some(TIMBER_CONDUCTIVITY), timber_fraction: 0.0 },
+        AssemblyLayer { name: "FLOORBOARDS", thickness: 0.028, conductivity: Some(TIMBER_CONDUCTIVITY), timber_fraction: 0.0 },
+    ],
+    inside_resistance: 0.17,
+    outside_resistance: 0.04,
+};
+
+pub const WINDOW_U_VALUE: f64 = 1.4;           // W/m²K, double glazed
+pub const DOOR_U_VALUE: f64 = 5.0;             // W/m²K, glass sauna doors
+pub const SAUNA_TEMPERATURE: f64 = 80.0;       // °C when in use
+pub const ADJACENT_ROOM_TEMPERATURE: f64 = 20.0; // °C in the wash room
+pub const OUTDOOR_DESIGN_TEMPERATURE: f64 = -10.0; // °C
+pub const GLASS_EQUIVALENT_VOLUME: f64 = 1.2;  // m³ added per m² of glass or uninsulated surface
+// Electric heater sizing as the makers' catalogues list it: the largest room
+// volume (m³, glass added) each output (kW) heats
+pub const HEATER_SIZES: &[(f64, f64)] = &[
+    (6.0, 4.5),
+    (9.0, 6.0),
+    (13.0, 8.0),
+    (15.0, 9.0),
+    (18.0, 10.5),
+    (21.0, 12.0),
+    (26.0, 15.0),
+    (32.0, 18.0),
+];
+pub const HEAT_UP_HOURS: f64 = 1.0;            // Heater at full power before a session
+pub const SESSION_HOURS: f64 = 2.0;            // Sauna kept at temperature
+
+// Sauna ventilation
+pub const VENT_SIZE: f64 = 0.20;           // Square grille side
+pub const INTAKE_HEIGHT: f64 = 0.30;       // Intake centre above floor
+pub const EXHAUST_HEIGHT: f64 = 2.20;      // Exhaust centre above floor
+pub const SAUNA_AIR_CHANGES: f64 = 6.0;    // Required air changes per hour
+pub const VENT_AIR_SPEED: f64 = 1.0;       // m/s through a grille, natural draught
+pub const INTAKE_MAX_HEIGHT: f64 = 0.60;   // Intake centre no higher than this
+pub const INTAKE_MAX_HEATER_DISTANCE: f64 = 1.00; // Intake within this of the heater centre in plan
+pub const EXHAUST_MIN_HEIGHT: f64 = 1.80;  // Exhaust centre no lower than this
+pub const VENT_MIN_SEPARATION: f64 = 1.50; // Exhaust at least this far from every intake in plan
+
+// Electrical: 240 V split-phase sub-panel; loads are taken as continuous
+pub const HOT_TUB_POWER: f64 = 9600.0;     // W, heater and pumps
+pub const OUTLET_LOAD: f64 = 180.0;        // VA per outlet
+pub const CONTINUOUS_LOAD_FACTOR: f64 = 1.25;
+pub const PANEL_RATING: f64 = 100.0;       // A, sub-panel main breaker
+pub const BREAKER_SIZES: &[f64] = &[15.0, 20.0, 25.0, 30.0, 35.0, 40.0, 45.0, 50.0, 60.0, 70.0, 80.0, 90.0, 100.0];
+pub const DISCONNECT_MIN_DISTANCE: f64 = 1.52; // Hot tub disconnect from the tub edge (5 ft)
+pub const OUTLET_MIN_DISTANCE: f64 = 1.83;     // Outlets from the tub edge (6 ft)
+
+pub struct Circuit {
+    pub number: u32,
+    pub name: &'static str,
+    pub voltage: f64,
+    pub gfci: bool,
+}
+
+pub const CIRCUITS: &[Circuit] = &[
+    Circuit { number: 1, name: "SAUNA HEATER", voltage: 240.0, gfci: false },
+    Circuit { number: 2, name: "HOT TUB", voltage: 240.0, gfci: true },
+    Circuit { number: 3, name: "LIGHTING (12 V LED DRIVERS)", voltage: 120.0, gfci: false },
+    Circuit { number: 4, name: "OUTLETS", voltage: 120.0, gfci: true },
+];
+
+// Ceilings and light fittings
+pub const CEILING_BOARD_WIDTH: f64 = 0.095;       // T&G panelling, boards along the long side of each room
+pub const SAUNA_CEILING_TEMPERATURE: f64 = 125.0; // °C, at the sauna ceiling with the heater on
+pub const HEATER_LIGHT_CLEARANCE: f64 = 0.50;     // No light fitting within this of the heater in plan
+
+pub struct LightFixture {
+    pub mark: &'static str,
+    pub description: &'static str,
+    pub watts: f64,
+    pub max_temperature: f64, // °C ambient the fitting is rated for
+    pub ip_rating: &'static str,
+}
+
+pub const LIGHT_FIXTURES: &[LightFixture] = &[
+    LightFixture { mark: "L1", description: "SAUNA LED SPOT 12 V, SILICONE SEALED", watts: 10.0, max_temperature: 130.0, ip_rating: "IP67" },
+    LightFixture { mark: "L2", description: "LED DOWNLIGHT 12 V, WET LOCATION", watts: 8.0, max_temperature: 45.0, ip_rating: "IP65" },
+    LightFixture { mark: "L3", description: "LED SOFFIT LIGHT 12 V, OUTDOOR", watts: 12.0, max_temperature: 45.0, ip_rating: "IP65" },
+];
+
+// Plumbing: nominal pipe sizes in inches, heights above finished floor
+pub const SERVICE_PIPE_SIZE: f64 = 0.75;    // in, water service and hot tub fill
+pub const SHOWER_SUPPLY_SIZE: f64 = 0.5;    // in
+pub const SHOWER_DRAIN_SIZE: f64 = 2.0;     // in
+pub const SHOWER_VENT_SIZE: f64 = 1.5;      // in
+pub const HOT_TUB_DRAIN_SIZE: f64 = 1.5;    // in
+pub const SHOWER_HEAD_HEIGHT: f64 = 2.00;   // Shower head on the north wall of the wash room
+pub const SHOWER_VALVE_HEIGHT: f64 = 1.10;  // Anti-scald mixing valve
+pub const SUPPLY_DEPTH: f64 = 0.10;         // Supply runs below finished floor, in the joist zone
+pub const DRAIN_MIN_SLOPE: f64 = 0.02;      // Fall per metre of horizontal drain (1/4" per foot)
+pub const TRAP_ARM_MAX: f64 = 2.44;         // Trap to vent along a 2" drain (8 ft)
+pub const VENT_ROOF_PROJECTION: f64 = 0.30; // Vent stack above the roof
+
+// Deck framing (timber joists on beams on pad footings)
+pub const TIMBER_GRADE: &str = "C24";      // Strength class of joists and beams
+pub const JOIST_WIDTH: f64 = 0.047;        // 47mm joists
+pub const JOIST_DEPTH: f64 = 0.200;        // 200mm joists
+pub const JOIST_SPACING: f64 = 0.40;       // Joist centres
+pub const BEAM_PLIES: u32 = 2;             // Joist-section plies per beam
+pub const BEAM_DEPTH: f64 = 0.200;         // Beam ply depth
+pub const JOISTS_FLUSH: bool = true;       // Joists hung between the beams, tops flush, to keep the beams off grade
+pub const BEAM_ROWS: u32 = 3;              // Beam lines across the deck depth
+pub const FOOTING_SPACING: f64 = 1.80;     // Maximum footing centres along a beam
+pub const FOOTING_INSET: f64 = 0.30;       // End footing inset from the deck edge
+pub const FOOTING_SIZE: f64 = 0.40;        // Square pad footing
+pub const PIER_DIAMETER: f64 = 0.20;       // Pier on each footing
+pub const DECK_DESIGN_LOAD: f64 = 2.0;     // kN/m², imposed plus dead load
+
+// Foundation under the deck beams; depths are below grade
+#[allow(dead_code)] // Only the variant chosen in FOUNDATION is constructed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FoundationType {
+    Slab,         // Reinforced slab at grade with thickened edges down to frost depth
+    Piers,        // Pad footings at FOOTING_SPACING with a pier up to each beam
+    ScrewPiles,   // Steel screw piles at the footing positions, no concrete
+    StripFooting, // Continuous footing and stem wall under every beam line
+}
+
+pub const FOUNDATION: FoundationType = FoundationType::Piers;
+pub const FROST_DEPTH: f64 = 1.20;         // Underside of footings below grade
+pub const BEAM_MIN_GRADE_CLEARANCE: f64 = 0.15; // Air gap under the timber beams
+pub const FOOTING_THICKNESS: f64 = 0.25;   // Pad and strip footings
+pub const STRIP_WIDTH: f64 = 0.40;         // Strip footing and slab edge beam
+pub const STEM_WALL_WIDTH: f64 = 0.20;     // Wall on a strip footing up to the beams
+pub const SLAB_THICKNESS: f64 = 0.10;      // Reinforced slab
+pub const SCREW_PILE_DIAMETER: f64 = 0.076; // Shaft
+pub const SCREW_PILE_HELIX: f64 = 0.25;    // Helix plate diameter
+pub const SCREW_PILE_LENGTH: f64 = 1.60;   // Below grade
+pub const CONCRETE_GRADE: &str = "C25/30";
+
+// Deck access stairs: each stair hangs off one edge of the deck, starting
+// `offset` along the edge from its west or south end. Risers and treads are
+// worked out from the deck height within the limits below.
+#[allow(dead_code)] // Only the edges used in DECK_STAIRS are constructed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DeckEdge {
+    North,
+    East,
+    South,
+    West,
+}
+
+pub struct DeckStair {
+    pub id: &'static str,
+    pub edge: DeckEdge,
+    pub offset: f64, // Along the edge to the stair side
+    pub width: f64,
+}
+
+pub const DECK_STAIRS: &[DeckStair] = &[
+    DeckStair { id: "ST1", edge: DeckEdge::South, offset: 2.11, width: 1.50 }, // Centred on the wash room door
+];
+pub const STAIR_MAX_RISER: f64 = 0.18;     // Code maximum riser height
+pub const STAIR_MIN_RISER: f64 = 0.10;     // Shallower risers are a trip hazard
+pub const STAIR_MIN_TREAD: f64 = 0.30;     // Code minimum going
+pub const STAIR_STEP_LENGTH: (f64, f64) = (0.60, 0.65); // Comfortable 2 x riser + tread
+pub const STAIR_TREAD_THICKNESS: f64 = 0.028; // Tread boards
+pub const STAIR_STRINGER_DEPTH: f64 = 0.225; // Stringer board, JOIST_WIDTH thick
+pub const STAIR_STRINGER_SPACING: f64 = 0.40; // Maximum stringer centres
+pub const STAIR_MIN_THROAT: f64 = 0.09;    // Stringer left below the notches
+
+// Deck railings along the open stretches of the chosen deck edges, broken at
+// walls, stairs and the hot tub surround
+pub const DECK_RAILINGS: &[DeckEdge] = &[DeckEdge::North, DeckEdge::East, DeckEdge::South, DeckEdge::West];
+pub const RAILING_HEIGHT: f64 = 1.06;      // Top of the top rail above the deck
+pub const RAILING_MIN_HEIGHT: f64 = 1.06;  // Code minimum guard height (42")
+pub const GUARD_REQUIRED_ABOVE: f64 = 0.76; // Deck height above grade needing a guard on every open edge (30")
+pub const RAILING_POST_SIZE: f64 = 0.09;   // Square posts, bolted to the rim
+pub const RAILING_POST_SPACING: f64 = 1.20; // Maximum post centres
+pub const RAILING_MIN_LENGTH: f64 = 0.60;  // Shorter open stretches are left open and reported
+pub const RAIL_SIZE: (f64, f64) = (0.045, 0.095); // Top and bottom rails laid flat, thickness x width
+pub const RAILING_BOTTOM_GAP: f64 = 0.08;  // Deck to the underside of the bottom rail
+pub const BALUSTER_SIZE: f64 = 0.038;      // Square balusters
+pub const BALUSTER_MAX_GAP: f64 = 0.10;    // Code maximum clear gap (4")
+
+// Sauna benches: tiers step up towards the back (north) wall of the sauna and
+// run along it alone (Straight), turn down the west wall, away from the door
+// in the east partition (L), or down both side walls (U). BENCH_TIERS goes
+// from the lowest tier to the top one.
+#[allow(dead_code)] // Only the variant chosen in BENCH_LAYOUT is constructed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BenchLayout {
+    Straight, // Back wall only
+    L,        // Back and west walls
+    U,        // Back, east and west walls
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BenchTier {
+    pub height: f64, // Seat top above the floor
+    pub depth: f64,  // Front to back
+}
+
+pub const BENCH_LAYOUT: BenchLayout = BenchLayout::L;
+pub const BENCH_TIERS: &[BenchTier] = &[
+    BenchTier { height: BENCH_HEIGHT_LOWER, depth: BENCH_DEPTH },
+    BenchTier { height: BENCH_HEIGHT_UPPER, depth: BENCH_DEPTH },
+];
+pub const BENCH_SIDE_LENGTH: f64 = 1.80;   // Side runs of L and U layouts, from the back wall; every tier fits from 1.80 m
+pub const BENCH_MIN_SEAT_LENGTH: f64 = 0.60; // Shorter side run tiers are left out
+pub const BENCH_STEP: Option<BenchTier> = None; // Foot step in front of the lowest tier
+pub const BENCH_BACKREST: Option<f64> = Some(0.45); // Backrest boards above the top seat
+pub const BENCH_BACKREST_STANDOFF: f64 = 0.04; // Air gap between the backrest and the wall
+pub const BENCH_SLAT_WIDTH: f64 = 0.090;   // Seat and backrest boards
+pub const BENCH_SLAT_GAP: f64 = 0.010;     // Least gap between boards, the rest spread evenly
+pub const BENCH_SLAT_THICKNESS: f64 = 0.028;
+pub const BENCH_FRAME: (f64, f64) = (0.045, 0.070); // Bearers and legs, thickness x depth
+pub const BENCH_MIN_HEADROOM: f64 = 1.05;  // Top seat to the ceiling
+pub const BENCH_DOOR_CLEARANCE: f64 = 0.10; // Bench to a door opening in plan
+
+// Site: lot corners in site metres (x east, y north), anticlockwise. Each lot
+// line runs from its corner to the next one and carries its own setback.
+pub struct LotLine {
+    pub name: &'static str,
+    pub x: f64,
+    pub y: f64,
+    pub setback: f64, // Minimum distance from the line to the building, deck or hot tub
+}
+
+pub const LOT_LINES: [LotLine; 4] = [
+    LotLine { name: "FRONT", x: 0.0, y: 0.0, setback: 6.0 },
+    LotLine { name: "EAST SIDE", x: 24.0, y: 0.0, setback: 3.0 },
+    LotLine { name: "REAR", x: 24.0, y: 28.0, setback: 7.5 },
+    LotLine { name: "WEST SIDE", x: 0.0, y: 31.0, setback: 3.0 },
+];
+pub const SITE_ORIGIN: (f64, f64) = (7.0, 12.0); // Site position of the model origin
+pub const SITE_ROTATION: f64 = 12.0;       // Degrees the building is turned anticlockwise from true north
+
+// Sun and shadow study: site location and local clock times on one date
+pub const SITE_LATITUDE: f64 = 47.61;      // Degrees, north positive
+pub const SITE_LONGITUDE: f64 = -122.33;   // Degrees, east positive
+pub const SITE_UTC_OFFSET: f64 = -7.0;     // Hours from UTC of the local clock on the study date
+pub const SUN_STUDY_DATE: (i32, u32, u32) = (2026, 6, 21); // Year, month, day
+pub const SUN_STUDY_TIMES: &[f64] = &[14.0, 16.0, 18.0, 20.0]; // Local clock hours
+pub const SHADOW_MIN_ALTITUDE: f64 = 3.0;  // Degrees; no shadow drawn for a lower sun
+
+// Survey underlay (--survey <file.dxf>): layers copied from the surveyor's
+// drawing, all of them if empty, and the survey coordinates of the site origin
+pub const SURVEY_LAYERS: &[&str] = &["BOUNDARY", "CONTOURS", "SPOT-LEVELS", "TREES", "SERVICES"];
+pub const SURVEY_BASE: (f64, f64) = (512_340.0, 6_781_250.0); // Easting, northing of the first lot corner
+
 // Deck pattern
 pub const DECK_BOARD_WIDTH: f64 = 0.15;    // 150mm deck boards
 pub const DECK_BOARD_GAP: f64 = 0.01;      // 10mm gap between boards
+pub const DECK_BOARD_THICKNESS: f64 = 0.028; // 28mm deck boards on the joists
 
 // Drawing annotation settings
 pub const DIM_TEXT_HEIGHT: f64 = 0.10;     // Dimension text height
@@ -59,9 +397,67 @@ pub const TITLE_TEXT_HEIGHT: f64 = 0.25;   // Title text height
 pub const DIM_OFFSET: f64 = 0.30;          // Offset for dimension lines
 pub const DIM_TICK_SIZE: f64 = 0.05;       // Dimension tick mark size
 
-// Scale
+// Presentation view (axonometric axis angles above horizontal, 30/30 = isometric)
+pub const AXO_ANGLE_X: f64 = 30.0;
+pub const AXO_ANGLE_Y: f64 = 30.0;
+
+// Revision clouds
+pub const CLOUD_MARGIN: f64 = 0.15;        // Clearance between a change and its cloud
+pub const CLOUD_ARC_LENGTH: f64 = 0.25;    // Chord length of each cloud scallop
+pub const CLOUD_MERGE_RATIO: f64 = 4.0;    // Largest area ratio of two clouds that merge
+
+// Drawing scale
 pub const DRAWING_SCALE: &str = "1:50";
 
+// Project metadata (title block)
+pub const PROJECT_NAME: &str = "SAUNA BUILDING";
+pub const PROJECT_NUMBER: &str = "2026-001";
+pub const PROJECT_CLIENT: &str = "PRIVATE CLIENT";
+pub const PROJECT_ADDRESS: &str = "TO BE CONFIRMED";
+pub const SHEET_TITLE: &str = "FLOOR PLAN & ELEVATIONS";
+pub const SHEET_NUMBER: &str = "A-101";
+pub const SHEET_COUNT: u32 = 1;
+pub const DRAWN_BY: &str = "BM";
+pub const CHECKED_BY: &str = "-";
+
+// Revision history, oldest first; the title block shows the latest revision
+pub struct Revision {
+    pub number: &'static str,
+    pub date: &'static str,
+    pub description: &'static str,
+    pub author: &'static str,
+}
+
+pub const REVISIONS: &[Revision] = &[
+    Revision { number: "A", date: "2026-01-03", description: "ISSUED FOR REVIEW", author: "BM" },
+];
+
+// Sheet views a revision cloud can be drawn around
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SheetView {
+    FloorPlan,
+    FrontElevation,
+    SideElevation,
+    Section,
+    Iso,
+}
+
+// Sheet views clouded, CLOUD_MARGIN outside their extents, and tagged with
+// the revision that changed them, e.g.
+// RevisionCloud { revision: "B", view: SheetView::Iso }
+#[allow(dead_code)] // Constructed once an issued revision is clouded
+pub struct RevisionCloud {
+    pub revision: &'static str,
+    pub view: SheetView,
+}
+
+pub const REVISION_CLOUDS: &[RevisionCloud] = &[];
+
+// Current revision (calculated)
+pub fn current_revision() -> &'static Revision {
+    REVISIONS.last().expect("at least one revision")
+}
+
 // Room areas (calculated)
 pub fn sauna_area() -> f64 {
     let inner_width = SAUNA_WIDTH - 2.0 * WALL_THICKNESS;
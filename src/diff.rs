@@ -0,0 +1,834 @@
+use std::collections::{BTreeMap, HashMap};
+use std::f64::consts::PI;
+
+use dxf::entities::*;
+use dxf::enums::AcadVersion;
+use dxf::objects::{Object, ObjectType, XRecordObject};
+use dxf::{CodePair, Drawing};
+
+use crate::constants::*;
+use crate::helpers::revision_cloud;
+use crate::layers::*;
+use crate::model::{BuildingModel, Footprint};
+use crate::sheet::build_drawing;
+use crate::title_block::TitleBlockTemplate;
+use crate::verify::entity_kind;
+
+// ============================================================================
+// DRAWING DIFF
+// Every generated drawing carries a record of the model's walls, openings,
+// rooms, furnishings, devices and fixtures in an XRECORD object. Two drawings
+// that both have one are compared element by element, by kind and id, so a
+// moved heater or an enlarged room is reported as just that element. Bare
+// DXFs without the record fall back to pairing up entities on the same layer
+// and of the same type. Either way the overlay shows the sheet entities that
+// changed, with revision clouds around the changed elements.
+// ============================================================================
+
+const TOLERANCE: f64 = 1e-6;
+
+/// First string of the XRECORD holding the model elements
+const MODEL_RECORD: &str = "SAUNA MODEL";
+
+/// Elements are recorded to six decimals
+const RECORD_TOLERANCE: f64 = 1e-5;
+
+/// Model element as recorded in a drawing: a box in plan coordinates with z
+/// above the finished floor, and its area
+#[derive(Clone, Debug, PartialEq)]
+pub struct Element {
+    pub kind: String, // WALL, OPENING, ROOM, FURNISHING, DEVICE or FIXTURE
+    pub id: String,
+    pub min: (f64, f64, f64),
+    pub max: (f64, f64, f64),
+    pub area: f64, // Floor area of rooms, footprint of furnishings, face of openings
+}
+
+impl Element {
+    fn size(&self) -> (f64, f64, f64) {
+        (self.max.0 - self.min.0, self.max.1 - self.min.1, self.max.2 - self.min.2)
+    }
+
+    /// Record line: kind, box and area, then the id, which may hold spaces
+    fn line(&self) -> String {
+        let (a, b) = (self.min, self.max);
+        format!(
+            "{} {:.6} {:.6} {:.6} {:.6} {:.6} {:.6} {:.6} {}",
+            self.kind, a.0, a.1, a.2, b.0, b.1, b.2, self.area, self.id
+        )
+    }
+
+    fn parse(line: &str) -> Option<Element> {
+        let mut fields = line.splitn(9, ' ');
+        let kind = fields.next()?.to_string();
+        let mut numbers = [0.0; 7];
+        for number in &mut numbers {
+            *number = fields.next()?.parse().ok()?;
+        }
+        let id = fields.next()?.to_string();
+        let [x1, y1, z1, x2, y2, z2, area] = numbers;
+        Some(Element { kind, id, min: (x1, y1, z1), max: (x2, y2, z2), area })
+    }
+}
+
+/// The elements of a model the diff compares
+pub fn model_elements(model: &BuildingModel) -> Vec<Element> {
+    let element = |kind: &str, id: &str, min, max, area| Element { kind: kind.to_string(), id: id.to_string(), min, max, area };
+    let mut elements = Vec::new();
+    for wall in &model.walls {
+        elements.push(element("WALL", wall.id, (wall.x1, wall.y1, 0.0), (wall.x2, wall.y2, BUILDING_HEIGHT), 0.0));
+        for opening in &wall.openings {
+            let (start, end) = (opening.offset, opening.offset + opening.width);
+            let (min, max) = if wall.is_horizontal() {
+                ((wall.x1 + start, wall.y1), (wall.x1 + end, wall.y2))
+            } else {
+                ((wall.x1, wall.y1 + start), (wall.x2, wall.y1 + end))
+            };
+            let (sill, head) = (opening.sill, opening.sill + opening.height);
+            elements.push(element("OPENING", opening.id, (min.0, min.1, sill), (max.0, max.1, head), opening.width * opening.height));
+        }
+    }
+    for room in &model.rooms {
+        let ceiling = room.ceiling.unwrap_or(0.0);
+        elements.push(element("ROOM", room.name, (room.x1, room.y1, 0.0), (room.x2, room.y2, ceiling), room.area()));
+    }
+    for furnishing in &model.furnishings {
+        let (min, max, area) = match furnishing.footprint {
+            Footprint::Rect { x1, y1, x2, y2 } => ((x1, y1), (x2, y2), (x2 - x1) * (y2 - y1)),
+            Footprint::Circle { x, y, radius } => ((x - radius, y - radius), (x + radius, y + radius), PI * radius * radius),
+        };
+        elements.push(element("FURNISHING", &furnishing.id, (min.0, min.1, 0.0), (max.0, max.1, furnishing.height), area));
+    }
+    for device in &model.devices {
+        let at = (device.x, device.y, device.height);
+        elements.push(element("DEVICE", device.id, at, at, 0.0));
+    }
+    for fixture in &model.fixtures {
+        let at = (fixture.x, fixture.y, fixture.z);
+        elements.push(element("FIXTURE", fixture.id, at, at, 0.0));
+    }
+    elements
+}
+
+/// Write the model elements into the drawing for a later diff
+pub fn add_model_record(drawing: &mut Drawing, model: &BuildingModel) {
+    let mut record = XRecordObject::default();
+    record.data_pairs.push(CodePair::new_str(1, MODEL_RECORD));
+    for element in model_elements(model) {
+        record.data_pairs.push(CodePair::new_string(1, &element.line()));
+    }
+    drawing.add_object(Object::new(ObjectType::XRecordObject(record)));
+}
+
+/// The model elements recorded in a drawing, None for a bare DXF, or the
+/// first line that does not read as an element
+fn model_record(drawing: &Drawing) -> Result<Option<Vec<Element>>, String> {
+    let record = drawing.objects().find_map(|object| match &object.specific {
+        ObjectType::XRecordObject(record) => {
+            let lines: Vec<String> = record
+                .data_pairs
+                .iter()
+                .filter(|pair| pair.code == 1)
+                .map(|pair| pair.value.to_string())
+                .collect();
+            if lines.first().map(String::as_str) == Some(MODEL_RECORD) { Some(lines) } else { None }
+        }
+        _ => None,
+    });
+    let Some(lines) = record else {
+        return Ok(None);
+    };
+    lines[1..]
+        .iter()
+        .map(|line| Element::parse(line).ok_or_else(|| format!("unreadable element '{}'", line)))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
+/// The model record of a loaded drawing; a missing record is reported and
+/// leaves the diff comparing entities, a corrupt one stops it
+fn read_model_record(drawing: &Drawing, path: &str) -> Result<Option<Vec<Element>>, ()> {
+    match model_record(drawing) {
+        Ok(Some(elements)) => Ok(Some(elements)),
+        Ok(None) => {
+            println!("NOTE: {} has no model record; comparing its entities instead", path);
+            println!();
+            Ok(None)
+        }
+        Err(e) => {
+            eprintln!("Error reading the model record of {}: {}", path, e);
+            Err(())
+        }
+    }
+}
+
+/// An entity reduced to what the comparison needs
+struct Shape {
+    entity: Entity,
+    layer: String,
+    kind: &'static str,
+    points: Vec<(f64, f64, f64)>,
+    values: Vec<f64>, // Radius, angles, text height
+    text: String,
+    closed: bool,
+}
+
+impl Shape {
+    fn new(entity: &Entity) -> Shape {
+        let mut points = Vec::new();
+        let mut values = Vec::new();
+        let mut text = String::new();
+        let mut closed = false;
+        match &entity.specific {
+            EntityType::Line(line) => {
+                points.push((line.p1.x, line.p1.y, line.p1.z));
+                points.push((line.p2.x, line.p2.y, line.p2.z));
+            }
+            EntityType::LwPolyline(polyline) => {
+                points.extend(polyline.vertices.iter().map(|v| (v.x, v.y, 0.0)));
+                values.extend(polyline.vertices.iter().map(|v| v.bulge));
+                closed = polyline.is_closed();
+            }
+            EntityType::Polyline(polyline) => {
+                points.extend(polyline.vertices().map(|v| (v.location.x, v.location.y, v.location.z)));
+                closed = polyline.is_closed();
+            }
+            EntityType::Circle(circle) => {
+                points.push((circle.center.x, circle.center.y, circle.center.z));
+                values.push(circle.radius);
+                closed = true;
+            }
+            EntityType::Arc(arc) => {
+                points.push((arc.center.x, arc.center.y, arc.center.z));
+                values.extend([arc.radius, arc.start_angle, arc.end_angle]);
+            }
+            EntityType::Text(t) => {
+                points.push((t.location.x, t.location.y, t.location.z));
+                values.extend([t.text_height, t.rotation]);
+                text = t.value.clone();
+            }
+            EntityType::MText(t) => {
+                points.push((t.insertion_point.x, t.insertion_point.y, t.insertion_point.z));
+                values.push(t.initial_text_height);
+                text = t.text.clone();
+            }
+            EntityType::Face3D(face) => {
+                for p in [&face.first_corner, &face.second_corner, &face.third_corner, &face.fourth_corner] {
+                    points.push((p.x, p.y, p.z));
+                }
+            }
+            EntityType::Insert(insert) => {
+                points.push((insert.location.x, insert.location.y, insert.location.z));
+                text = insert.name.clone();
+            }
+            _ => {}
+        }
+        Shape {
+            entity: entity.clone(),
+            layer: entity.common.layer.clone(),
+            kind: entity_kind(&entity.specific),
+            points,
+            values,
+            text,
+            closed,
+        }
+    }
+
+    /// Rounded description of the geometry, equal for identical entities
+    fn signature(&self) -> String {
+        let round = |v: f64| {
+            let text = format!("{:.6}", v);
+            if text == "-0.000000" { "0.000000".to_string() } else { text }
+        };
+        let points: Vec<String> = self
+            .points
+            .iter()
+            .map(|p| format!("{},{},{}", round(p.0), round(p.1), round(p.2)))
+            .collect();
+        let values: Vec<String> = self.values.iter().map(|v| round(*v)).collect();
+        format!("{}|{}|{}|{}|{}", self.kind, points.join(";"), values.join(";"), self.text, self.closed)
+    }
+
+    /// Plan extents (x1, y1, x2, y2)
+    fn extents(&self) -> Option<(f64, f64, f64, f64)> {
+        let first = self.points.first()?;
+        let mut ext = (first.0, first.1, first.0, first.1);
+        for p in &self.points {
+            ext = (ext.0.min(p.0), ext.1.min(p.1), ext.2.max(p.0), ext.3.max(p.1));
+        }
+        match self.kind {
+            // Circles and arcs: the full circle is close enough for a cloud
+            "CIRCLE" | "ARC" => {
+                let r = self.values[0];
+                ext = (ext.0 - r, ext.1 - r, ext.2 + r, ext.3 + r);
+            }
+            // Text: approximate width from the character count
+            "TEXT" | "MTEXT" => {
+                let height = self.values[0];
+                let width = self.text.lines().map(|line| line.chars().count()).max().unwrap_or(0) as f64;
+                ext = (ext.0, ext.1, ext.2 + width * height * 0.7, ext.3 + height);
+            }
+            _ => {}
+        }
+        Some(ext)
+    }
+
+    fn size(&self) -> (f64, f64) {
+        self.extents().map_or((0.0, 0.0), |e| (e.2 - e.0, e.3 - e.1))
+    }
+
+    /// Enclosed area of closed outlines
+    fn area(&self) -> Option<f64> {
+        match self.kind {
+            "CIRCLE" => Some(PI * self.values[0] * self.values[0]),
+            "LWPOLYLINE" | "POLYLINE" if self.closed && self.points.len() > 2 => {
+                let n = self.points.len();
+                let twice: f64 = (0..n)
+                    .map(|i| {
+                        let (a, b) = (self.points[i], self.points[(i + 1) % n]);
+                        a.0 * b.1 - b.0 * a.1
+                    })
+                    .sum();
+                Some(twice.abs() / 2.0)
+            }
+            _ => None,
+        }
+    }
+
+    /// Offset if `other` is this entity shifted without any other change
+    fn offset_to(&self, other: &Shape) -> Option<(f64, f64, f64)> {
+        if self.points.len() != other.points.len()
+            || self.points.is_empty()
+            || self.text != other.text
+            || self.closed != other.closed
+            || self.values.len() != other.values.len()
+            || self.values.iter().zip(&other.values).any(|(a, b)| (a - b).abs() > TOLERANCE)
+        {
+            return None;
+        }
+        let (a, b) = (self.points[0], other.points[0]);
+        let offset = (b.0 - a.0, b.1 - a.1, b.2 - a.2);
+        let uniform = self.points.iter().zip(&other.points).all(|(a, b)| {
+            (b.0 - a.0 - offset.0).abs() < TOLERANCE
+                && (b.1 - a.1 - offset.1).abs() < TOLERANCE
+                && (b.2 - a.2 - offset.2).abs() < TOLERANCE
+        });
+        uniform.then_some(offset)
+    }
+}
+
+/// Entity indices in the old and new drawing for one layer and entity type
+type Group = (Vec<usize>, Vec<usize>);
+
+enum ChangeKind {
+    Added,
+    Removed,
+    Moved(f64, f64, f64),
+    Resized,
+    Changed,
+}
+
+/// One difference between the drawings; `old`/`new` index into the shape lists
+struct Change {
+    kind: ChangeKind,
+    old: Option<usize>,
+    new: Option<usize>,
+}
+
+/// Plan extents (x1, y1, x2, y2) of an entity, text and arcs approximated
+pub fn entity_extents(entity: &Entity) -> Option<(f64, f64, f64, f64)> {
+    Shape::new(entity).extents()
+}
+
+/// Pair up entities on the same layer and of the same type: identical
+/// entities first, then the remaining ones in drawing order
+fn match_shapes(old: &[Shape], new: &[Shape]) -> (Vec<Change>, usize) {
+    let mut groups: BTreeMap<(String, &str), Group> = BTreeMap::new();
+    for (i, shape) in old.iter().enumerate() {
+        groups.entry((shape.layer.clone(), shape.kind)).or_default().0.push(i);
+    }
+    for (i, shape) in new.iter().enumerate() {
+        groups.entry((shape.layer.clone(), shape.kind)).or_default().1.push(i);
+    }
+
+    let mut changes = Vec::new();
+    let mut unchanged = 0;
+    for (old_group, new_group) in groups.into_values() {
+        // Identical entities, wherever they sit in the drawing order
+        let mut by_signature: HashMap<String, Vec<usize>> = HashMap::new();
+        for &i in old_group.iter().rev() {
+            by_signature.entry(old[i].signature()).or_default().push(i);
+        }
+        let mut old_left = vec![true; old.len()];
+        let mut new_left = Vec::new();
+        for &j in &new_group {
+            match by_signature.get_mut(&new[j].signature()).and_then(Vec::pop) {
+                Some(i) => {
+                    old_left[i] = false;
+                    unchanged += 1;
+                }
+                None => new_left.push(j),
+            }
+        }
+        let old_left: Vec<usize> = old_group.into_iter().filter(|&i| old_left[i]).collect();
+
+        // The rest pair up in order; leftovers were added or removed
+        for (k, &j) in new_left.iter().enumerate() {
+            let change = match old_left.get(k) {
+                Some(&i) => {
+                    let kind = if let Some((dx, dy, dz)) = old[i].offset_to(&new[j]) {
+                        ChangeKind::Moved(dx, dy, dz)
+                    } else {
+                        let (a, b) = (old[i].size(), new[j].size());
+                        if (a.0 - b.0).abs() > TOLERANCE || (a.1 - b.1).abs() > TOLERANCE {
+                            ChangeKind::Resized
+                        } else {
+                            ChangeKind::Changed
+                        }
+                    };
+                    Change { kind, old: Some(i), new: Some(j) }
+                }
+                None => Change { kind: ChangeKind::Added, old: None, new: Some(j) },
+            };
+            changes.push(change);
+        }
+        for &i in old_left.iter().skip(new_left.len()) {
+            changes.push(Change { kind: ChangeKind::Removed, old: Some(i), new: None });
+        }
+    }
+    (changes, unchanged)
+}
+
+/// Pair up model elements by kind and id
+fn match_elements(old: &[Element], new: &[Element]) -> (Vec<Change>, usize) {
+    let close = |a: f64, b: f64| (a - b).abs() <= RECORD_TOLERANCE;
+    let close_3 = |a: (f64, f64, f64), b: (f64, f64, f64)| close(a.0, b.0) && close(a.1, b.1) && close(a.2, b.2);
+
+    let mut by_id: HashMap<(&str, &str), Vec<usize>> = HashMap::new();
+    for (i, element) in old.iter().enumerate().rev() {
+        by_id.entry((&element.kind, &element.id)).or_default().push(i);
+    }
+    let mut changes = Vec::new();
+    let mut unchanged = 0;
+    for (j, element) in new.iter().enumerate() {
+        let Some(i) = by_id.get_mut(&(&element.kind, &element.id)).and_then(Vec::pop) else {
+            changes.push(Change { kind: ChangeKind::Added, old: None, new: Some(j) });
+            continue;
+        };
+        let (a, b) = (&old[i], element);
+        let same_area = close(a.area, b.area);
+        let kind = if !close_3(a.size(), b.size()) {
+            ChangeKind::Resized
+        } else if !close_3(a.min, b.min) && same_area {
+            ChangeKind::Moved(b.min.0 - a.min.0, b.min.1 - a.min.1, b.min.2 - a.min.2)
+        } else if !close_3(a.min, b.min) || !same_area {
+            ChangeKind::Changed
+        } else {
+            unchanged += 1;
+            continue;
+        };
+        changes.push(Change { kind, old: Some(i), new: Some(j) });
+    }
+    let mut removed: Vec<usize> = by_id.into_values().flatten().collect();
+    removed.sort();
+    for i in removed {
+        changes.push(Change { kind: ChangeKind::Removed, old: Some(i), new: None });
+    }
+    (changes, unchanged)
+}
+
+fn format_size(size: (f64, f64)) -> String {
+    format!("{:.2} x {:.2}", size.0, size.1)
+}
+
+fn describe(change: &Change, old: &[Shape], new: &[Shape]) -> String {
+    let shape = change.new.map(|j| &new[j]).or(change.old.map(|i| &old[i])).unwrap();
+    let name = if shape.text.is_empty() {
+        format!("{} {}", shape.layer, shape.kind)
+    } else {
+        format!("{} {} '{}'", shape.layer, shape.kind, shape.text.replace('\n', " "))
+    };
+    let at = |shape: &Shape| {
+        shape
+            .extents()
+            .map_or(String::new(), |e| format!(" at ({:.2}, {:.2})", e.0, e.1))
+    };
+    let area = |i: usize, j: usize| match (old[i].area(), new[j].area()) {
+        (Some(a), Some(b)) if (a - b).abs() > TOLERANCE => format!(", area {:.2} -> {:.2} m²", a, b),
+        _ => String::new(),
+    };
+    match (&change.kind, change.old, change.new) {
+        (ChangeKind::Added, _, Some(j)) => format!("+ {} added{}", name, at(&new[j])),
+        (ChangeKind::Removed, Some(i), _) => format!("- {} removed{}", name, at(&old[i])),
+        (ChangeKind::Moved(dx, dy, dz), _, _) if dz.abs() > TOLERANCE => {
+            format!("~ {} moved by ({:.3}, {:.3}, {:.3})", name, dx, dy, dz)
+        }
+        (ChangeKind::Moved(dx, dy, _), _, _) => format!("~ {} moved by ({:.3}, {:.3})", name, dx, dy),
+        (ChangeKind::Resized, Some(i), Some(j)) => format!(
+            "~ {} resized {} -> {}{}",
+            name,
+            format_size(old[i].size()),
+            format_size(new[j].size()),
+            area(i, j)
+        ),
+        (_, Some(i), Some(j)) => {
+            let was = if old[i].text != new[j].text && !old[i].text.is_empty() {
+                format!(" (was '{}')", old[i].text.replace('\n', " "))
+            } else {
+                String::new()
+            };
+            format!("~ {} changed{}{}{}", name, at(&new[j]), was, area(i, j))
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn format_element_size(size: (f64, f64, f64)) -> String {
+    format!("{} x {:.2}", format_size((size.0, size.1)), size.2)
+}
+
+fn describe_element(change: &Change, old: &[Element], new: &[Element]) -> String {
+    let element = change.new.map(|j| &new[j]).or(change.old.map(|i| &old[i])).unwrap();
+    let name = format!("{} {}", element.kind, element.id);
+    let at = |element: &Element| format!(" at ({:.2}, {:.2})", element.min.0, element.min.1);
+    let area = |i: usize, j: usize| {
+        let (a, b) = (old[i].area, new[j].area);
+        if (a - b).abs() > RECORD_TOLERANCE {
+            format!(", area {:.2} -> {:.2} m²", a, b)
+        } else {
+            String::new()
+        }
+    };
+    match (&change.kind, change.old, change.new) {
+        (ChangeKind::Added, _, Some(j)) => format!("+ {} added{}", name, at(&new[j])),
+        (ChangeKind::Removed, Some(i), _) => format!("- {} removed{}", name, at(&old[i])),
+        (ChangeKind::Moved(dx, dy, dz), _, _) if dz.abs() > RECORD_TOLERANCE => {
+            format!("~ {} moved by ({:.3}, {:.3}, {:.3})", name, dx, dy, dz)
+        }
+        (ChangeKind::Moved(dx, dy, _), _, _) => format!("~ {} moved by ({:.3}, {:.3})", name, dx, dy),
+        (ChangeKind::Resized, Some(i), Some(j)) => format!(
+            "~ {} resized {} -> {}{}",
+            name,
+            format_element_size(old[i].size()),
+            format_element_size(new[j].size()),
+            area(i, j)
+        ),
+        (_, Some(i), Some(j)) => format!("~ {} changed{}{}", name, at(&new[j]), area(i, j)),
+        _ => unreachable!(),
+    }
+}
+
+/// Merge overlapping rectangles of similar size so nearby changes share one
+/// cloud; a large change (such as the sheet border) does not swallow the
+/// small ones inside it
+fn merge_regions(mut regions: Vec<(f64, f64, f64, f64)>) -> Vec<(f64, f64, f64, f64)> {
+    let mut merged = true;
+    while merged {
+        merged = false;
+        'outer: for i in 0..regions.len() {
+            for j in i + 1..regions.len() {
+                let (a, b) = (regions[i], regions[j]);
+                let overlap = a.0 <= b.2 && b.0 <= a.2 && a.1 <= b.3 && b.1 <= a.3;
+                let (area_a, area_b) = ((a.2 - a.0) * (a.3 - a.1), (b.2 - b.0) * (b.3 - b.1));
+                if overlap && area_a.max(area_b) <= area_a.min(area_b) * CLOUD_MERGE_RATIO {
+                    regions[i] = (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3));
+                    regions.remove(j);
+                    merged = true;
+                    break 'outer;
+                }
+            }
+        }
+    }
+    regions
+}
+
+fn add_copy(drawing: &mut Drawing, shape: &Shape, layer: &str) {
+    let mut entity = shape.entity.clone();
+    entity.common.layer = layer.to_string();
+    drawing.add_entity(entity);
+}
+
+/// Overlay of both revisions: unchanged elements in gray, the old state of
+/// changed elements in red, the new state in green, with clouds around the
+/// given regions
+fn overlay_drawing(
+    old: &[Shape],
+    new: &[Shape],
+    changes: &[Change],
+    regions: Vec<(f64, f64, f64, f64)>,
+) -> (Drawing, usize) {
+    let mut drawing = Drawing::new();
+    drawing.header.version = AcadVersion::R2013;
+    drawing.header.default_drawing_units = dxf::enums::Units::Meters;
+    setup_diff_layers(&mut drawing);
+
+    let mut changed_new = vec![false; new.len()];
+    for change in changes {
+        if let Some(i) = change.old {
+            add_copy(&mut drawing, &old[i], LAYER_DIFF_REMOVED);
+        }
+        if let Some(j) = change.new {
+            add_copy(&mut drawing, &new[j], LAYER_DIFF_NEW);
+            changed_new[j] = true;
+        }
+    }
+    for (j, shape) in new.iter().enumerate() {
+        if !changed_new[j] {
+            add_copy(&mut drawing, shape, LAYER_DIFF_EXISTING);
+        }
+    }
+
+    // Revision clouds
+    let padded = regions
+        .into_iter()
+        .map(|e| (e.0 - CLOUD_MARGIN, e.1 - CLOUD_MARGIN, e.2 + CLOUD_MARGIN, e.3 + CLOUD_MARGIN))
+        .collect();
+    let clouds = merge_regions(padded);
+    for &(x1, y1, x2, y2) in &clouds {
+        let mut entity = Entity::new(EntityType::LwPolyline(revision_cloud(x1, y1, x2, y2)));
+        entity.common.layer = LAYER_REV_CLOUD.to_string();
+        drawing.add_entity(entity);
+    }
+    (drawing, clouds.len())
+}
+
+/// Entity changes counted by layer
+fn print_sheet_summary(changes: &[Change], unchanged: usize, old: &[Shape], new: &[Shape]) {
+    if changes.is_empty() {
+        println!("No changes to the sheet ({} entities identical).", unchanged);
+        println!();
+        return;
+    }
+    let mut layers: BTreeMap<&str, [usize; 5]> = BTreeMap::new();
+    for change in changes {
+        let shape = change.new.map(|j| &new[j]).or(change.old.map(|i| &old[i])).unwrap();
+        let column = match change.kind {
+            ChangeKind::Added => 0,
+            ChangeKind::Removed => 1,
+            ChangeKind::Moved(..) => 2,
+            ChangeKind::Resized => 3,
+            ChangeKind::Changed => 4,
+        };
+        layers.entry(shape.layer.as_str()).or_default()[column] += 1;
+    }
+    println!("CHANGES BY LAYER:        added removed   moved resized changed");
+    for (layer, counts) in &layers {
+        println!(
+            "  {:<20} {:>7} {:>7} {:>7} {:>7} {:>7}",
+            layer, counts[0], counts[1], counts[2], counts[3], counts[4]
+        );
+    }
+    println!("  ({} entities unchanged)", unchanged);
+    println!();
+}
+
+fn load(path: &str) -> Option<Drawing> {
+    match Drawing::load_file(path) {
+        Ok(drawing) => Some(drawing),
+        Err(e) => {
+            eprintln!("Error loading {}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Compare two drawings (or a drawing with the sheet the model and title
+/// block produce when `new_path` is None), print the changes and write the
+/// overlay to `overlay_path`. The recorded model elements are compared when
+/// both drawings carry them, the entities otherwise. Returns false if a drawing cannot be loaded or
+/// the overlay cannot be saved.
+pub fn diff_files(
+    old_path: &str,
+    new_path: Option<&str>,
+    overlay_path: &str,
+    model: &BuildingModel,
+    title_block: &TitleBlockTemplate,
+) -> bool {
+    let Some(old_drawing) = load(old_path) else {
+        return false;
+    };
+    let new_drawing = match new_path {
+        Some(path) => match load(path) {
+            Some(drawing) => drawing,
+            None => return false,
+        },
+        None => {
+            let model_3d = old_drawing.entities().any(|entity| entity.common.layer == LAYER_MODEL_WALLS);
+            build_drawing(model, model_3d, title_block)
+        }
+    };
+    let old: Vec<Shape> = old_drawing.entities().map(Shape::new).collect();
+    let new: Vec<Shape> = new_drawing.entities().map(Shape::new).collect();
+    let (changes, unchanged) = match_shapes(&old, &new);
+
+    println!("========================================");
+    println!("  DIFF: {} -> {}", old_path, new_path.unwrap_or("current model"));
+    println!("========================================");
+    println!();
+
+    let Ok(old_elements) = read_model_record(&old_drawing, old_path) else {
+        return false;
+    };
+    let new_elements = match new_path {
+        Some(path) => match read_model_record(&new_drawing, path) {
+            Ok(elements) => elements,
+            Err(()) => return false,
+        },
+        None => Some(model_elements(model)),
+    };
+
+    let regions = match (old_elements, new_elements) {
+        (Some(old_elements), Some(new_elements)) => {
+            // Both drawings record their model: compare the elements
+            let (element_changes, elements_unchanged) = match_elements(&old_elements, &new_elements);
+            if element_changes.is_empty() {
+                println!("No changes to the model ({} elements identical).", elements_unchanged);
+            } else {
+                println!("MODEL CHANGES:");
+                for change in &element_changes {
+                    println!("  {}", describe_element(change, &old_elements, &new_elements));
+                }
+                println!("  ({} elements unchanged)", elements_unchanged);
+            }
+            println!();
+            print_sheet_summary(&changes, unchanged, &old, &new);
+
+            let element_box = |element: &Element| (element.min.0, element.min.1, element.max.0, element.max.1);
+            element_changes
+                .iter()
+                .flat_map(|change| {
+                    let old_box = change.old.map(|i| element_box(&old_elements[i]));
+                    let new_box = change.new.map(|j| element_box(&new_elements[j]));
+                    old_box.into_iter().chain(new_box)
+                })
+                .collect()
+        }
+        _ => {
+            // Bare DXF: compare the entities themselves
+            print_sheet_summary(&changes, unchanged, &old, &new);
+            if !changes.is_empty() {
+                println!("CHANGES:");
+                for change in &changes {
+                    println!("  {}", describe(change, &old, &new));
+                }
+                println!();
+            }
+            changes
+                .iter()
+                .flat_map(|change| {
+                    let old_extents = change.old.and_then(|i| old[i].extents());
+                    let new_extents = change.new.and_then(|j| new[j].extents());
+                    old_extents.into_iter().chain(new_extents)
+                })
+                .collect()
+        }
+    };
+
+    let (overlay, clouds) = overlay_drawing(&old, &new, &changes, regions);
+    match overlay.save_file(overlay_path) {
+        Ok(_) => {
+            println!("Overlay saved: {} ({} revision clouds)", overlay_path, clouds);
+            println!();
+            true
+        }
+        Err(e) => {
+            eprintln!("Error saving overlay: {}", e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heaters::HeaterCatalogue;
+    use crate::model::building_model;
+
+    fn model() -> BuildingModel {
+        building_model(&HeaterCatalogue::default().select(HEATER_MODEL).unwrap())
+    }
+
+    /// Model changes between the two models, as the diff prints them
+    fn report(old: &BuildingModel, new: &BuildingModel) -> Vec<String> {
+        let (old, new) = (model_elements(old), model_elements(new));
+        let (changes, unchanged) = match_elements(&old, &new);
+        assert_eq!(unchanged + changes.len(), new.len());
+        changes.iter().map(|change| describe_element(change, &old, &new)).collect()
+    }
+
+    #[test]
+    fn recorded_model_reads_back_unchanged() {
+        let model = model();
+        let mut drawing = Drawing::new();
+        add_model_record(&mut drawing, &model);
+        let recorded = model_record(&drawing).unwrap().expect("no model record");
+        let elements = model_elements(&model);
+        assert_eq!(recorded.len(), elements.len());
+        let (changes, unchanged) = match_elements(&recorded, &elements);
+        assert!(changes.is_empty());
+        assert_eq!(unchanged, elements.len());
+        assert_eq!(model_record(&Drawing::new()), Ok(None));
+    }
+
+    #[test]
+    fn corrupt_record_names_the_bad_line() {
+        let mut record = XRecordObject::default();
+        record.data_pairs.push(CodePair::new_str(1, MODEL_RECORD));
+        record.data_pairs.push(CodePair::new_str(1, "WALL 0 0 0 x 1 2.4 0 wall-south"));
+        let mut drawing = Drawing::new();
+        drawing.add_object(Object::new(ObjectType::XRecordObject(record)));
+        assert_eq!(model_record(&drawing), Err("unreadable element 'WALL 0 0 0 x 1 2.4 0 wall-south'".to_string()));
+    }
+
+    #[test]
+    fn moved_heater_is_the_only_change() {
+        let old = model();
+        let mut new = model();
+        let heater = new.furnishings.iter_mut().find(|f| f.id == "heater").unwrap();
+        match &mut heater.footprint {
+            Footprint::Rect { x1, x2, .. } => {
+                *x1 += 0.10;
+                *x2 += 0.10;
+            }
+            Footprint::Circle { x, .. } => *x += 0.10,
+        }
+        assert_eq!(report(&old, &new), ["~ FURNISHING heater moved by (0.100, 0.000)"]);
+    }
+
+    #[test]
+    fn lengthened_wall_is_the_only_change() {
+        let old = model();
+        let mut new = model();
+        let wall = new.walls.iter_mut().find(|wall| wall.id == "wall-south").unwrap();
+        let (x1, y1, x2, y2) = (wall.x1, wall.y1, wall.x2, wall.y2);
+        wall.x2 += 0.30;
+        let expected = format!(
+            "~ WALL wall-south resized {:.2} x {:.2} x {:.2} -> {:.2} x {:.2} x {:.2}",
+            x2 - x1,
+            y2 - y1,
+            BUILDING_HEIGHT,
+            x2 + 0.30 - x1,
+            y2 - y1,
+            BUILDING_HEIGHT
+        );
+        assert_eq!(report(&old, &new), [expected]);
+    }
+
+    #[test]
+    fn enlarged_room_reports_its_new_area() {
+        let old = model();
+        let mut new = model();
+        let room = new.rooms.iter_mut().find(|room| room.name == "SAUNA").unwrap();
+        let area = room.area();
+        room.x2 += 0.20;
+        let enlarged = room.area();
+        let changes = report(&old, &new);
+        assert_eq!(changes.len(), 1, "{:?}", changes);
+        assert!(changes[0].starts_with("~ ROOM SAUNA resized"), "{}", changes[0]);
+        assert!(
+            changes[0].ends_with(&format!(", area {:.2} -> {:.2} m²", area, enlarged)),
+            "{}",
+            changes[0]
+        );
+    }
+}
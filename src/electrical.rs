@@ -0,0 +1,286 @@
+use dxf::Drawing;
+
+use crate::constants::*;
+use crate::helpers::{add_circle, add_line, add_rectangle, add_text};
+use crate::layers::*;
+use crate::hot_tub::{draw_hot_tub_outline, hot_tub_shell};
+use crate::model::{BuildingModel, Device, DeviceKind};
+
+// ============================================================================
+// ELECTRICAL
+// Devices in the model are fed from the CIRCUITS of a 240 V sub-panel. Every
+// load is taken as continuous, so breakers are sized at CONTINUOUS_LOAD_FACTOR
+// times the circuit current and the panel is checked the same way. The
+// electrical plan repeats the walls, deck and hot tub as background for the
+// device symbols on the E- layers.
+// ============================================================================
+
+/// Size of the device symbols in plan
+const SYMBOL_SIZE: f64 = 0.18;
+
+/// Load and breaker of one circuit in the panel schedule
+pub struct CircuitLoad {
+    pub circuit: &'static Circuit,
+    pub devices: usize,
+    pub load: f64,            // W
+    pub current: f64,         // A
+    pub breaker: Option<f64>, // A, None if no standard size is large enough
+}
+
+impl CircuitLoad {
+    pub fn poles(&self) -> u32 {
+        if self.circuit.voltage > 120.0 { 2 } else { 1 }
+    }
+}
+
+pub struct PanelSchedule {
+    pub circuits: Vec<CircuitLoad>,
+    pub problems: Vec<String>,
+}
+
+/// Smallest standard breaker for a continuous current
+pub fn breaker_size(current: f64) -> Option<f64> {
+    let design = current * CONTINUOUS_LOAD_FACTOR;
+    BREAKER_SIZES.iter().copied().find(|&size| size >= design)
+}
+
+impl DeviceKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            DeviceKind::Panel => "PANEL",
+            DeviceKind::HeaterFeed => "HEATER FEED",
+            DeviceKind::HeaterControl => "HEATER CONTROL",
+            DeviceKind::Disconnect => "DISCONNECT",
+            DeviceKind::Light(_) => "LIGHT",
+            DeviceKind::Switch => "SWITCH",
+            DeviceKind::Outlet => "OUTLET",
+        }
+    }
+}
+
+/// Plan distance from a device to the rim of the hot tub
+fn hot_tub_clearance(device: &Device) -> f64 {
+    hot_tub_shell().distance_to(device.x, device.y)
+}
+
+/// Panel schedule for the model's devices, with the placement checks
+pub fn panel_schedule(model: &BuildingModel) -> PanelSchedule {
+    let mut problems = Vec::new();
+
+    let circuits: Vec<CircuitLoad> = CIRCUITS
+        .iter()
+        .map(|circuit| {
+            let fed: Vec<&Device> = model.devices.iter().filter(|d| d.circuit == circuit.number).collect();
+            let load = fed.iter().fold(0.0, |sum, d| sum + d.load);
+            let current = load / circuit.voltage;
+            CircuitLoad { circuit, devices: fed.len(), load, current, breaker: breaker_size(current) }
+        })
+        .collect();
+
+    for load in &circuits {
+        if load.breaker.is_none() {
+            problems.push(format!(
+                "CIRCUIT {}: {:.1} A IS OVER THE LARGEST BREAKER",
+                load.circuit.number, load.current
+            ));
+        }
+    }
+
+    let panels = model.devices.iter().filter(|d| d.kind == DeviceKind::Panel).count();
+    if panels != 1 {
+        problems.push(format!("{} PANELS IN THE MODEL, EXPECTED 1", panels));
+    }
+    let panel_current = panel_current(&circuits);
+    if panel_current > PANEL_RATING {
+        problems.push(format!(
+            "PANEL LOAD {:.1} A OVER THE {:.0} A RATING",
+            panel_current, PANEL_RATING
+        ));
+    }
+
+    let sauna = model.sauna();
+    for device in &model.devices {
+        let name = device.id.to_uppercase();
+        if device.kind != DeviceKind::Panel && !CIRCUITS.iter().any(|c| c.number == device.circuit) {
+            problems.push(format!("{}: NO CIRCUIT {}", name, device.circuit));
+        }
+        // Only the heater feed and the light belong in the hot room
+        if sauna.contains(device.x, device.y) && matches!(device.kind, DeviceKind::Switch | DeviceKind::Outlet | DeviceKind::HeaterControl) {
+            problems.push(format!("{}: {} INSIDE THE SAUNA", name, device.kind.name()));
+        }
+        let minimum = match device.kind {
+            DeviceKind::Disconnect => DISCONNECT_MIN_DISTANCE,
+            DeviceKind::Outlet => OUTLET_MIN_DISTANCE,
+            _ => continue,
+        };
+        let clearance = hot_tub_clearance(device);
+        if clearance < minimum {
+            problems.push(format!(
+                "{}: {:.2} m FROM THE HOT TUB (MIN {:.2} m)",
+                name, clearance, minimum
+            ));
+        }
+        if device.kind == DeviceKind::Outlet && !CIRCUITS.iter().any(|c| c.number == device.circuit && c.gfci) {
+            problems.push(format!("{}: OUTLET NOT ON A GFCI CIRCUIT", name));
+        }
+    }
+
+    PanelSchedule { circuits, problems }
+}
+
+/// Continuous panel current with every load shared across both legs
+fn panel_current(circuits: &[CircuitLoad]) -> f64 {
+    let load: f64 = circuits.iter().map(|c| c.load).sum();
+    load / 240.0 * CONTINUOUS_LOAD_FACTOR
+}
+
+impl PanelSchedule {
+    pub fn total_load(&self) -> f64 {
+        self.circuits.iter().map(|c| c.load).sum()
+    }
+
+    pub fn panel_current(&self) -> f64 {
+        panel_current(&self.circuits)
+    }
+
+    /// Report lines for the sheet and the console
+    pub fn lines(&self, model: &BuildingModel) -> Vec<String> {
+        let mut lines = vec![format!(
+            "{:<3} {:<28} {:>4} {:>2} {:>3} {:>7} {:>6} {:>8} {}",
+            "CCT", "DESCRIPTION", "V", "P", "DEV", "LOAD W", "AMPS", "BREAKER", "GFCI"
+        )];
+        for load in &self.circuits {
+            let breaker = match load.breaker {
+                Some(size) => format!("{:.0} A", size),
+                None => "OVER".to_string(),
+            };
+            lines.push(format!(
+                "{:<3} {:<28} {:>4.0} {:>2} {:>3} {:>7.0} {:>6.1} {:>8} {}",
+                load.circuit.number,
+                load.circuit.name,
+                load.circuit.voltage,
+                load.poles(),
+                load.devices,
+                load.load,
+                load.current,
+                breaker,
+                if load.circuit.gfci { "YES" } else { "" }
+            )
+            .trim_end()
+            .to_string());
+        }
+        lines.push(format!(
+            "TOTAL {:.1} kW, {:.1} A CONTINUOUS AT 240 V ON A {:.0} A PANEL",
+            self.total_load() / 1000.0,
+            self.panel_current(),
+            PANEL_RATING
+        ));
+        // Device rows, the kind column as wide as the longest kind name
+        let kind_width = model.devices.iter().map(|device| device.kind.name().len()).max().unwrap_or(0);
+        for device in &model.devices {
+            let circuit = if device.circuit > 0 { format!("CCT {}", device.circuit) } else { "MAIN".to_string() };
+            lines.push(format!(
+                "{:<kind_width$} {:<21} {:<6} AT {:.2} m",
+                device.kind.name(),
+                device.id.to_uppercase(),
+                circuit,
+                device.height
+            ));
+        }
+        if self.problems.is_empty() {
+            lines.push("DEVICES MEET THE PLACEMENT RULES AND THE PANEL RATING".to_string());
+        }
+        lines.extend(self.problems.iter().map(|problem| format!("WARNING: {}", problem)));
+        lines
+    }
+}
+
+/// Plan symbol for one device centred at (x, y)
+pub fn draw_device_symbol(drawing: &mut Drawing, kind: DeviceKind, x: f64, y: f64) {
+    let s = SYMBOL_SIZE / 2.0;
+    let letter = |drawing: &mut Drawing, layer: &str, value: &str| {
+        add_text(drawing, layer, x - s * 0.6, y - s * 0.6, s * 1.2, value.to_string());
+    };
+    match kind {
+        // Panel: rectangle half filled with a diagonal
+        DeviceKind::Panel => {
+            add_rectangle(drawing, LAYER_ELEC_POWER, x - s * 1.5, y - s, x + s * 1.5, y + s);
+            add_line(drawing, LAYER_ELEC_POWER, x - s * 1.5, y - s, x + s * 1.5, y + s);
+        }
+        DeviceKind::HeaterFeed => {
+            add_circle(drawing, LAYER_ELEC_POWER, x, y, s);
+            letter(drawing, LAYER_ELEC_POWER, "J");
+        }
+        DeviceKind::HeaterControl => {
+            add_rectangle(drawing, LAYER_ELEC_POWER, x - s, y - s, x + s, y + s);
+            letter(drawing, LAYER_ELEC_POWER, "C");
+        }
+        DeviceKind::Disconnect => {
+            add_rectangle(drawing, LAYER_ELEC_POWER, x - s, y - s, x + s, y + s);
+            add_text(drawing, LAYER_ELEC_POWER, x - s * 0.8, y - s * 0.4, s * 0.8, "DS".to_string());
+        }
+        // Ceiling light: circle with a cross
+        DeviceKind::Light(_) => {
+            add_circle(drawing, LAYER_ELEC_LIGHTING, x, y, s);
+            let d = s * std::f64::consts::FRAC_1_SQRT_2;
+            add_line(drawing, LAYER_ELEC_LIGHTING, x - d, y - d, x + d, y + d);
+            add_line(drawing, LAYER_ELEC_LIGHTING, x - d, y + d, x + d, y - d);
+        }
+        DeviceKind::Switch => letter(drawing, LAYER_ELEC_LIGHTING, "S"),
+        // Duplex outlet: circle with two parallel strokes
+        DeviceKind::Outlet => {
+            add_circle(drawing, LAYER_ELEC_POWER, x, y, s);
+            add_line(drawing, LAYER_ELEC_POWER, x - s * 0.3, y - s * 1.5, x - s * 0.3, y + s * 1.5);
+            add_line(drawing, LAYER_ELEC_POWER, x + s * 0.3, y - s * 1.5, x + s * 0.3, y + s * 1.5);
+        }
+    }
+}
+
+/// Electrical plan: walls, deck and hot tub as background, devices with their
+/// circuit numbers, and switch legs to the lights they work. The model origin
+/// is drawn at (x, y).
+pub fn create_electrical_plan(drawing: &mut Drawing, model: &BuildingModel, x: f64, y: f64) {
+    add_rectangle(drawing, LAYER_DECK, x, y, x + TOTAL_WIDTH, y + DECK_DEPTH + BUILDING_DEPTH);
+    for wall in &model.walls {
+        let layer = if wall.exterior { LAYER_WALLS } else { LAYER_WALLS_INNER };
+        add_rectangle(drawing, layer, x + wall.x1, y + wall.y1, x + wall.x2, y + wall.y2);
+    }
+    draw_hot_tub_outline(drawing, |tub_x, tub_y| (x + tub_x, y + tub_y));
+
+    for device in &model.devices {
+        // Switch legs: straight runs to each light the switch works
+        for light in device.controls.iter().filter_map(|id| model.device(id)) {
+            add_line(drawing, LAYER_ELEC_LIGHTING, x + device.x, y + device.y, x + light.x, y + light.y);
+        }
+    }
+    for device in &model.devices {
+        draw_device_symbol(drawing, device.kind, x + device.x, y + device.y);
+        if device.circuit > 0 && device.kind != DeviceKind::Switch {
+            add_text(
+                drawing,
+                LAYER_ELEC_ANNO,
+                x + device.x + SYMBOL_SIZE * 0.7,
+                y + device.y + SYMBOL_SIZE * 0.3,
+                DIM_TEXT_HEIGHT,
+                device.circuit.to_string(),
+            );
+        }
+    }
+
+    add_text(
+        drawing,
+        LAYER_TEXT,
+        x,
+        y + DECK_DEPTH + BUILDING_DEPTH + 0.40,
+        LABEL_TEXT_HEIGHT,
+        "ELECTRICAL PLAN".to_string(),
+    );
+    add_text(
+        drawing,
+        LAYER_ELEC_ANNO,
+        x,
+        y + DECK_DEPTH + BUILDING_DEPTH + 0.15,
+        DIM_TEXT_HEIGHT,
+        "NUMBERS ARE PANEL CIRCUITS; J HEATER FEED, C HEATER CONTROL, DS HOT TUB DISCONNECT, S SWITCH".to_string(),
+    );
+}
@@ -0,0 +1,348 @@
+use std::f64::consts::PI;
+
+use dxf::Drawing;
+
+use crate::constants::*;
+use crate::framing::DeckFraming;
+use crate::helpers::{add_circle, add_line, add_rectangle, add_text, draw_dim_horizontal, draw_dim_vertical};
+use crate::layers::*;
+use crate::model::BuildingModel;
+
+// ============================================================================
+// FOUNDATION
+// What the deck beams bear on: pad footings with piers or screw piles at the
+// footing positions, a strip footing with a stem wall under every beam line,
+// or a slab at grade with thickened edges. Footings go down to frost depth,
+// and piers and stem walls come up to the beams, which must stay clear of
+// grade. Depths and heights are measured from grade.
+// ============================================================================
+
+/// One foundation element in plan coordinates
+pub enum FoundationElement {
+    Pad { x: f64, y: f64 },
+    Pile { x: f64, y: f64 },
+    Strip { x1: f64, x2: f64, y: f64 },
+    Slab { x1: f64, y1: f64, x2: f64, y2: f64 },
+}
+
+pub struct Foundation {
+    pub kind: FoundationType,
+    pub elements: Vec<FoundationElement>,
+    pub columns: Vec<f64>, // Dimensioned centre lines along x
+    pub rows: Vec<f64>,    // Dimensioned centre lines along y
+    pub problems: Vec<String>,
+}
+
+/// Height of the beam underside above grade, below the deck boards and the
+/// joists on top of the beams, or the deeper of the two when they are flush
+pub fn beam_underside() -> f64 {
+    let framing = if JOISTS_FLUSH { JOIST_DEPTH.max(BEAM_DEPTH) } else { JOIST_DEPTH + BEAM_DEPTH };
+    DECK_ELEV_HEIGHT - DECK_BOARD_THICKNESS - framing
+}
+
+/// Pier or stem wall height, from the top of the footing to the beams
+pub fn pier_height() -> f64 {
+    beam_underside() + FROST_DEPTH - FOOTING_THICKNESS
+}
+
+/// Slab edge thickening below the slab, down to frost depth
+pub fn edge_beam_depth() -> f64 {
+    FROST_DEPTH - SLAB_THICKNESS
+}
+
+/// Concrete in one pad footing, in m³
+pub fn pad_volume() -> f64 {
+    FOOTING_SIZE * FOOTING_SIZE * FOOTING_THICKNESS
+}
+
+/// Concrete in one pier, in m³
+pub fn pier_volume() -> f64 {
+    PI * (PIER_DIAMETER / 2.0).powi(2) * pier_height()
+}
+
+/// Centre-line length of the thickened slab edge
+pub fn edge_beam_length(width: f64, depth: f64) -> f64 {
+    2.0 * (width + depth) - 4.0 * STRIP_WIDTH
+}
+
+impl FoundationType {
+    pub fn name(self) -> &'static str {
+        match self {
+            FoundationType::Slab => "SLAB ON GRADE",
+            FoundationType::Piers => "PAD FOOTINGS WITH PIERS",
+            FoundationType::ScrewPiles => "SCREW PILES",
+            FoundationType::StripFooting => "STRIP FOOTINGS",
+        }
+    }
+}
+
+impl FoundationElement {
+    /// Concrete volume in m³
+    pub fn concrete(&self) -> f64 {
+        match *self {
+            FoundationElement::Pad { .. } => pad_volume() + pier_volume(),
+            FoundationElement::Pile { .. } => 0.0,
+            FoundationElement::Strip { x1, x2, .. } => {
+                (x2 - x1) * (STRIP_WIDTH * FOOTING_THICKNESS + STEM_WALL_WIDTH * pier_height())
+            }
+            FoundationElement::Slab { x1, y1, x2, y2 } => {
+                let edge = edge_beam_length(x2 - x1, y2 - y1);
+                (x2 - x1) * (y2 - y1) * SLAB_THICKNESS + edge * STRIP_WIDTH * edge_beam_depth()
+            }
+        }
+    }
+}
+
+/// Lay out the foundation of the chosen type under the deck framing
+pub fn foundation(framing: &DeckFraming) -> Foundation {
+    let kind = FOUNDATION;
+    let (elements, columns, rows) = match kind {
+        FoundationType::Piers | FoundationType::ScrewPiles => {
+            let elements = framing
+                .footings
+                .iter()
+                .map(|&(x, y)| match kind {
+                    FoundationType::Piers => FoundationElement::Pad { x, y },
+                    _ => FoundationElement::Pile { x, y },
+                })
+                .collect();
+            (elements, framing.columns.clone(), framing.beams.clone())
+        }
+        FoundationType::StripFooting => {
+            let elements = framing
+                .beams
+                .iter()
+                .map(|&y| FoundationElement::Strip { x1: framing.x1, x2: framing.x2, y })
+                .collect();
+            (elements, vec![framing.x1, framing.x2], framing.beams.clone())
+        }
+        FoundationType::Slab => {
+            let (x1, y1, x2, y2) = (framing.x1, framing.y1, framing.x2, framing.y2);
+            (vec![FoundationElement::Slab { x1, y1, x2, y2 }], vec![x1, x2], vec![y1, y2])
+        }
+    };
+
+    let mut problems = Vec::new();
+    if beam_underside() < 0.0 {
+        problems.push(format!("BEAMS {:.2} m BELOW GRADE", -beam_underside()));
+    } else if beam_underside() < BEAM_MIN_GRADE_CLEARANCE {
+        problems.push(format!(
+            "BEAMS {:.2} m ABOVE GRADE, LESS THAN THE {:.2} m CLEARANCE",
+            beam_underside(),
+            BEAM_MIN_GRADE_CLEARANCE
+        ));
+    }
+    match kind {
+        FoundationType::Piers if PIER_DIAMETER > FOOTING_SIZE => problems.push(format!(
+            "PIER DIA {:.0} WIDER THAN THE {:.0} PAD",
+            PIER_DIAMETER * 1000.0,
+            FOOTING_SIZE * 1000.0
+        )),
+        FoundationType::ScrewPiles if SCREW_PILE_LENGTH < FROST_DEPTH => problems.push(format!(
+            "SCREW PILES {:.2} m LONG, SHORT OF THE {:.2} m FROST DEPTH",
+            SCREW_PILE_LENGTH, FROST_DEPTH
+        )),
+        FoundationType::StripFooting if STEM_WALL_WIDTH > STRIP_WIDTH => problems.push(format!(
+            "STEM WALL {:.0} WIDER THAN THE {:.0} FOOTING",
+            STEM_WALL_WIDTH * 1000.0,
+            STRIP_WIDTH * 1000.0
+        )),
+        _ => {}
+    }
+
+    Foundation { kind, elements, columns, rows, problems }
+}
+
+impl Foundation {
+    /// Depth of the foundation's lowest point below grade
+    pub fn underside(&self) -> f64 {
+        match self.kind {
+            FoundationType::ScrewPiles => SCREW_PILE_LENGTH,
+            _ => FROST_DEPTH,
+        }
+    }
+
+    /// Total concrete volume in m³
+    pub fn concrete(&self) -> f64 {
+        self.elements.iter().map(FoundationElement::concrete).sum()
+    }
+
+    /// Short description for the framing plan note
+    pub fn note(&self) -> String {
+        match self.kind {
+            FoundationType::Slab => format!("{:.0} SLAB", SLAB_THICKNESS * 1000.0),
+            FoundationType::Piers => format!(
+                "{:.0}x{:.0} PADS @ {:.2} m MAX",
+                FOOTING_SIZE * 1000.0,
+                FOOTING_SIZE * 1000.0,
+                FOOTING_SPACING
+            ),
+            FoundationType::ScrewPiles => {
+                format!("{:.0} SCREW PILES @ {:.2} m MAX", SCREW_PILE_DIAMETER * 1000.0, FOOTING_SPACING)
+            }
+            FoundationType::StripFooting => format!("{:.0} STRIP FOOTINGS", STRIP_WIDTH * 1000.0),
+        }
+    }
+
+    /// Report lines for the sheet and the console
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("{} ({})", self.kind.name(), self.elements.len())];
+        lines.push(match self.kind {
+            FoundationType::Slab => format!(
+                "{:.0} SLAB, {:.0} WIDE EDGE THICKENED {:.0} BELOW",
+                SLAB_THICKNESS * 1000.0,
+                STRIP_WIDTH * 1000.0,
+                edge_beam_depth() * 1000.0
+            ),
+            FoundationType::Piers => format!(
+                "{:.0}x{:.0}x{:.0} PADS, DIA {:.0} PIERS {:.2} m HIGH",
+                FOOTING_SIZE * 1000.0,
+                FOOTING_SIZE * 1000.0,
+                FOOTING_THICKNESS * 1000.0,
+                PIER_DIAMETER * 1000.0,
+                pier_height()
+            ),
+            FoundationType::ScrewPiles => format!(
+                "DIA {:.0} SHAFT, {:.0} HELIX, {:.2} m BELOW GRADE",
+                SCREW_PILE_DIAMETER * 1000.0,
+                SCREW_PILE_HELIX * 1000.0,
+                SCREW_PILE_LENGTH
+            ),
+            FoundationType::StripFooting => format!(
+                "{:.0}x{:.0} FOOTINGS, {:.0} STEM WALLS {:.2} m HIGH",
+                STRIP_WIDTH * 1000.0,
+                FOOTING_THICKNESS * 1000.0,
+                STEM_WALL_WIDTH * 1000.0,
+                pier_height()
+            ),
+        });
+        lines.push(format!("FROST DEPTH {:.2} m, UNDERSIDE OF BEAMS {:+.2} m TO GRADE", FROST_DEPTH, beam_underside()));
+        if self.kind != FoundationType::ScrewPiles {
+            lines.push(format!("CONCRETE {} {:.2} m³", CONCRETE_GRADE, self.concrete()));
+        }
+        if self.problems.is_empty() {
+            lines.push("FOUNDATION BELOW FROST DEPTH, BEAMS CLEAR OF GRADE".to_string());
+        }
+        lines.extend(self.problems.iter().map(|problem| format!("WARNING: {}", problem)));
+        lines
+    }
+}
+
+/// Draw the foundation elements in plan with the deck origin at (ox, oy)
+pub fn draw_foundation_elements(drawing: &mut Drawing, foundation: &Foundation, ox: f64, oy: f64) {
+    for element in &foundation.elements {
+        match *element {
+            FoundationElement::Pad { x, y } => {
+                let half = FOOTING_SIZE / 2.0;
+                add_rectangle(drawing, LAYER_FOOTINGS, ox + x - half, oy + y - half, ox + x + half, oy + y + half);
+                add_circle(drawing, LAYER_FOOTINGS, ox + x, oy + y, PIER_DIAMETER / 2.0);
+            }
+            FoundationElement::Pile { x, y } => {
+                add_circle(drawing, LAYER_FOOTINGS, ox + x, oy + y, SCREW_PILE_HELIX / 2.0);
+                add_circle(drawing, LAYER_FOOTINGS, ox + x, oy + y, SCREW_PILE_DIAMETER / 2.0);
+            }
+            FoundationElement::Strip { x1, x2, y } => {
+                let (half, stem) = (STRIP_WIDTH / 2.0, STEM_WALL_WIDTH / 2.0);
+                add_rectangle(drawing, LAYER_FOOTINGS, ox + x1, oy + y - half, ox + x2, oy + y + half);
+                add_line(drawing, LAYER_FOOTINGS, ox + x1, oy + y - stem, ox + x2, oy + y - stem);
+                add_line(drawing, LAYER_FOOTINGS, ox + x1, oy + y + stem, ox + x2, oy + y + stem);
+            }
+            FoundationElement::Slab { x1, y1, x2, y2 } => {
+                add_rectangle(drawing, LAYER_FOOTINGS, ox + x1, oy + y1, ox + x2, oy + y2);
+                add_rectangle(
+                    drawing,
+                    LAYER_FOOTINGS,
+                    ox + x1 + STRIP_WIDTH,
+                    oy + y1 + STRIP_WIDTH,
+                    ox + x2 - STRIP_WIDTH,
+                    oy + y2 - STRIP_WIDTH,
+                );
+            }
+        }
+    }
+}
+
+/// Foundation plan with the deck origin at (x, y): the deck edge, the
+/// elements and chained dimensions between element centres
+pub fn create_foundation_plan(drawing: &mut Drawing, model: &BuildingModel, foundation: &Foundation, x: f64, y: f64) {
+    let deck = &model.deck;
+    add_rectangle(drawing, LAYER_DECK, x + deck.min.x, y + deck.min.y, x + deck.max.x, y + deck.max.y);
+    draw_foundation_elements(drawing, foundation, x, y);
+
+    // Chained dimensions below and to the left of the deck
+    for pair in foundation.columns.windows(2) {
+        draw_dim_horizontal(drawing, x + pair[0], x + pair[1], y + deck.min.y, -0.60, "");
+    }
+    for pair in foundation.rows.windows(2) {
+        draw_dim_vertical(drawing, x + deck.min.x, y + pair[0], y + pair[1], -0.60, "");
+    }
+
+    add_text(drawing, LAYER_TEXT, x, y + deck.max.y + 0.60, LABEL_TEXT_HEIGHT, "FOUNDATION PLAN".to_string());
+    add_text(
+        drawing,
+        LAYER_ANNO,
+        x,
+        y + deck.max.y + 0.30,
+        DIM_TEXT_HEIGHT,
+        format!(
+            "{}, UNDERSIDE {:.2} m BELOW GRADE, DIMENSIONS TO CENTRES",
+            foundation.kind.name(),
+            foundation.underside()
+        ),
+    );
+}
+
+/// Foundation below grade in section A-A, which looks south from the middle
+/// of the sauna: the elements on the beam line nearest the cut, with grade
+/// at offset_y
+pub fn draw_foundation_section(drawing: &mut Drawing, model: &BuildingModel, foundation: &Foundation, offset_x: f64, offset_y: f64) {
+    let Some(sauna) = model.room("SAUNA") else { return };
+    let cut_y = (sauna.y1 + sauna.y2) / 2.0;
+    let Some(row) = foundation.rows.iter().copied().min_by(|a, b| (a - cut_y).abs().total_cmp(&(b - cut_y).abs())) else {
+        return;
+    };
+    let section_x = |x: f64| offset_x + SAUNA_WIDTH - x;
+    let (start, end) = (-0.50, SAUNA_WIDTH + 0.50);
+    let (bottom, beams) = (offset_y - FROST_DEPTH, offset_y + beam_underside());
+
+    for element in &foundation.elements {
+        match *element {
+            FoundationElement::Pad { x, y } if (y - row).abs() < 1e-6 && (start..=end).contains(&x) => {
+                let (half, pier) = (FOOTING_SIZE / 2.0, PIER_DIAMETER / 2.0);
+                let x = section_x(x);
+                add_rectangle(drawing, LAYER_FOOTINGS, x - half, bottom, x + half, bottom + FOOTING_THICKNESS);
+                add_rectangle(drawing, LAYER_FOOTINGS, x - pier, bottom + FOOTING_THICKNESS, x + pier, beams);
+            }
+            FoundationElement::Pile { x, y } if (y - row).abs() < 1e-6 && (start..=end).contains(&x) => {
+                let (half, helix) = (SCREW_PILE_DIAMETER / 2.0, SCREW_PILE_HELIX / 2.0);
+                let (x, toe) = (section_x(x), offset_y - SCREW_PILE_LENGTH);
+                add_rectangle(drawing, LAYER_FOOTINGS, x - half, toe, x + half, beams);
+                add_line(drawing, LAYER_FOOTINGS, x - helix, toe + 0.10, x + helix, toe + 0.15);
+            }
+            FoundationElement::Strip { x1, x2, y } if (y - row).abs() < 1e-6 => {
+                // Seen beyond the cut, running the full width of the section
+                let (left, right) = (section_x(x2.min(end)), section_x(x1.max(start)));
+                add_rectangle(drawing, LAYER_FOOTINGS, left, bottom, right, bottom + FOOTING_THICKNESS);
+                add_rectangle(drawing, LAYER_FOOTINGS, left, bottom + FOOTING_THICKNESS, right, beams);
+            }
+            FoundationElement::Slab { x1, x2, .. } => {
+                let (left, right) = (section_x(x2.min(end)), section_x(x1.max(start)));
+                add_rectangle(drawing, LAYER_FOOTINGS, left, offset_y - SLAB_THICKNESS, right, offset_y);
+                if (start..=end).contains(&x1) {
+                    let edge = section_x(x1);
+                    add_rectangle(drawing, LAYER_FOOTINGS, edge - STRIP_WIDTH, bottom, edge, offset_y - SLAB_THICKNESS);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    add_text(
+        drawing,
+        LAYER_ANNO,
+        offset_x + SAUNA_WIDTH + 0.55,
+        bottom,
+        DIM_TEXT_HEIGHT,
+        format!("FROST DEPTH -{:.2}", FROST_DEPTH),
+    );
+}
@@ -0,0 +1,242 @@
+use dxf::Drawing;
+
+use crate::constants::*;
+use crate::foundation::{draw_foundation_elements, foundation};
+use crate::helpers::{add_rectangle, add_text};
+use crate::layers::*;
+use crate::hot_tub::{draw_hot_tub_outline, hot_tub_shell};
+use crate::loads::hot_tub_area_load;
+use crate::model::BuildingModel;
+
+// ============================================================================
+// DECK FRAMING
+// Joists run across the deck boards (along y) on beam lines running along x,
+// and each beam sits on evenly spaced footings. Spans are checked against
+// a timber span table for the chosen strength class.
+// ============================================================================
+
+/// Member centres the span table is drawn up for
+const SPAN_TABLE_SPACING: f64 = 0.40;
+
+/// Maximum simple span in metres of one timber section at 400 mm centres
+/// under DECK_DESIGN_LOAD
+struct SpanEntry {
+    grade: &'static str,
+    width: f64,
+    depth: f64,
+    span: f64,
+}
+
+const SPAN_TABLE: [SpanEntry; 8] = [
+    SpanEntry { grade: "C16", width: 0.047, depth: 0.150, span: 2.79 },
+    SpanEntry { grade: "C16", width: 0.047, depth: 0.175, span: 3.29 },
+    SpanEntry { grade: "C16", width: 0.047, depth: 0.200, span: 3.75 },
+    SpanEntry { grade: "C16", width: 0.047, depth: 0.225, span: 4.17 },
+    SpanEntry { grade: "C24", width: 0.047, depth: 0.150, span: 3.11 },
+    SpanEntry { grade: "C24", width: 0.047, depth: 0.175, span: 3.58 },
+    SpanEntry { grade: "C24", width: 0.047, depth: 0.200, span: 4.04 },
+    SpanEntry { grade: "C24", width: 0.047, depth: 0.225, span: 4.48 },
+];
+
+/// Framing layout in plan coordinates
+pub struct DeckFraming {
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+    pub joists: Vec<f64>,           // Joist centre lines (x)
+    pub beams: Vec<f64>,            // Beam centre lines (y)
+    pub columns: Vec<f64>,          // Footing centre lines along every beam (x)
+    pub footings: Vec<(f64, f64)>,  // Footing centres
+    pub bay: f64,                   // Joist span between beam centres
+    pub footing_span: f64,          // Beam span between footing centres
+}
+
+/// One member checked against the span table
+pub struct SpanCheck {
+    pub member: &'static str,
+    pub section: String,
+    pub load: f64,              // Area load in kN/m²
+    pub span: f64,
+    pub allowable: Option<f64>, // None if the section is not in the table
+}
+
+impl SpanCheck {
+    pub fn passes(&self) -> bool {
+        self.allowable.is_some_and(|allowable| self.span <= allowable)
+    }
+}
+
+/// Evenly spaced positions from `start` to `end`, no further apart than `max_gap`
+fn spaced(start: f64, end: f64, max_gap: f64) -> Vec<f64> {
+    let count = ((end - start) / max_gap).ceil().max(1.0) as usize;
+    (0..=count)
+        .map(|i| start + (end - start) * i as f64 / count as f64)
+        .collect()
+}
+
+fn beam_width() -> f64 {
+    BEAM_PLIES as f64 * JOIST_WIDTH
+}
+
+/// Lay out joists, beams and footings under the model's deck platform
+pub fn deck_framing(model: &BuildingModel) -> DeckFraming {
+    let (x1, y1) = (model.deck.min.x, model.deck.min.y);
+    let (x2, y2) = (model.deck.max.x, model.deck.max.y);
+
+    // Joists at the given centres, with one on each edge
+    let mut joists = Vec::new();
+    let mut x = x1 + JOIST_WIDTH / 2.0;
+    while x < x2 - JOIST_WIDTH / 2.0 - 1e-9 {
+        joists.push(x);
+        x += JOIST_SPACING;
+    }
+    joists.push(x2 - JOIST_WIDTH / 2.0);
+
+    // Beam lines flush with the front and back edges, evenly spaced between
+    let rows = BEAM_ROWS.max(2) as usize;
+    let (first, last) = (y1 + beam_width() / 2.0, y2 - beam_width() / 2.0);
+    let beams: Vec<f64> = (0..rows)
+        .map(|i| first + (last - first) * i as f64 / (rows - 1) as f64)
+        .collect();
+    let bay = (last - first) / (rows - 1) as f64;
+
+    // Footings along every beam
+    let columns = spaced(x1 + FOOTING_INSET, x2 - FOOTING_INSET, FOOTING_SPACING);
+    let footing_span = columns[1] - columns[0];
+    let footings = beams
+        .iter()
+        .flat_map(|&y| columns.iter().map(move |&x| (x, y)))
+        .collect();
+
+    DeckFraming { x1, y1, x2, y2, joists, beams, columns, footings, bay, footing_span }
+}
+
+/// Allowable span of one member carrying `line_load` kN/m, scaled from the
+/// table span by bending (√) or deflection (∛), whichever is shorter
+fn allowable_span(depth: f64, line_load: f64) -> Option<f64> {
+    let entry = SPAN_TABLE
+        .iter()
+        .find(|e| e.grade == TIMBER_GRADE && (e.width - JOIST_WIDTH).abs() < 1e-6 && (e.depth - depth).abs() < 1e-6)?;
+    let ratio = DECK_DESIGN_LOAD * SPAN_TABLE_SPACING / line_load;
+    Some(entry.span * ratio.sqrt().min(ratio.cbrt()))
+}
+
+fn section(plies: u32, depth: f64) -> String {
+    let size = format!("{:.0}x{:.0} {}", JOIST_WIDTH * 1000.0, depth * 1000.0, TIMBER_GRADE);
+    if plies > 1 { format!("{}/{}", plies, size) } else { size }
+}
+
+/// Span checks for the joists and beams, including those under the hot tub
+pub fn span_checks(framing: &DeckFraming) -> Vec<SpanCheck> {
+    let plies = BEAM_PLIES as f64;
+    let joist = |member, load: f64| SpanCheck {
+        member,
+        section: format!("{} @ {:.0}", section(1, JOIST_DEPTH), JOIST_SPACING * 1000.0),
+        load,
+        span: framing.bay,
+        allowable: allowable_span(JOIST_DEPTH, load * JOIST_SPACING),
+    };
+    // Beams carry half a bay each side, edge beams half a bay
+    let beam = |member, load: f64, tributary: f64| SpanCheck {
+        member,
+        section: section(BEAM_PLIES, BEAM_DEPTH),
+        load,
+        span: framing.footing_span,
+        allowable: allowable_span(BEAM_DEPTH, load * tributary / plies),
+    };
+
+    let interior = if framing.beams.len() > 2 { framing.bay } else { framing.bay / 2.0 };
+    let mut checks = vec![
+        joist("JOISTS", DECK_DESIGN_LOAD),
+        beam("BEAMS", DECK_DESIGN_LOAD, interior),
+    ];
+
+    // Members under the part of the tub that sits on the deck
+    let (_, tub_y1, _, tub_y2) = hot_tub_shell().bounds();
+    if tub_y2 > framing.y1 && tub_y1 < framing.y2 {
+        let load = hot_tub_area_load();
+        checks.push(joist("JOISTS UNDER HOT TUB", load));
+        checks.push(beam("BEAM UNDER HOT TUB", load, framing.bay / 2.0));
+    }
+    checks
+}
+
+/// Draw the deck framing plan with the deck origin at (offset_x, offset_y),
+/// with member notes and the span check table below it
+pub fn create_framing_plan(drawing: &mut Drawing, framing: &DeckFraming, offset_x: f64, offset_y: f64) {
+    let (ox, oy) = (offset_x, offset_y);
+
+    // Deck edge
+    add_rectangle(drawing, LAYER_DECK, ox + framing.x1, oy + framing.y1, ox + framing.x2, oy + framing.y2);
+
+    // Footings, piles or slab under the beams
+    let foundation = foundation(framing);
+    draw_foundation_elements(drawing, &foundation, ox, oy);
+
+    // Beams
+    let half_beam = beam_width() / 2.0;
+    for &y in &framing.beams {
+        add_rectangle(drawing, LAYER_BEAMS, ox + framing.x1, oy + y - half_beam, ox + framing.x2, oy + y + half_beam);
+    }
+
+    // Joists
+    let half_joist = JOIST_WIDTH / 2.0;
+    for &x in &framing.joists {
+        add_rectangle(drawing, LAYER_JOISTS, ox + x - half_joist, oy + framing.y1, ox + x + half_joist, oy + framing.y2);
+    }
+
+    // Hot tub footprint for reference
+    draw_hot_tub_outline(drawing, |x, y| (ox + x, oy + y));
+
+    // Title and member notes
+    add_text(drawing, LAYER_TEXT, ox + framing.x1, oy + framing.y2 + 0.60, LABEL_TEXT_HEIGHT, "DECK FRAMING PLAN".to_string());
+    add_text(
+        drawing,
+        LAYER_ANNO,
+        ox + framing.x1,
+        oy + framing.y2 + 0.30,
+        DIM_TEXT_HEIGHT,
+        format!(
+            "JOISTS {} @ {:.0} CRS {} {} BEAMS, {}",
+            section(1, JOIST_DEPTH),
+            JOIST_SPACING * 1000.0,
+            if JOISTS_FLUSH { "HUNG FLUSH BETWEEN" } else { "ON" },
+            section(BEAM_PLIES, BEAM_DEPTH),
+            foundation.note()
+        ),
+    );
+
+    // Span check table below the hot tub
+    let (_, tub_y1, _, _) = hot_tub_shell().bounds();
+    let mut y = oy + tub_y1 - 0.40;
+    add_text(
+        drawing,
+        LAYER_ANNO,
+        ox + framing.x1,
+        y,
+        DIM_TEXT_HEIGHT,
+        format!("SPAN CHECKS ({}, DESIGN LOAD {:.1} kN/m²)", TIMBER_GRADE, DECK_DESIGN_LOAD),
+    );
+    for check in span_checks(framing) {
+        y -= DIM_TEXT_HEIGHT * 2.0;
+        add_text(drawing, LAYER_ANNO, ox + framing.x1, y, DIM_TEXT_HEIGHT, span_check_line(&check));
+    }
+}
+
+/// One line of the span check table
+pub fn span_check_line(check: &SpanCheck) -> String {
+    let allowable = match check.allowable {
+        Some(span) => format!("{:.2} m", span),
+        None => "NOT IN TABLE".to_string(),
+    };
+    format!(
+        "{:<22} {:<18} {:>5.1} kN/m²  SPAN {:.2} m  ALLOWABLE {}  {}",
+        check.member,
+        check.section,
+        check.load,
+        check.span,
+        allowable,
+        if check.passes() { "OK" } else { "OVERSPAN" }
+    )
+}
@@ -0,0 +1,378 @@
+use std::fs;
+
+use dxf::Drawing;
+
+use crate::constants::*;
+use crate::helpers::{add_circle, add_line, add_rectangle, draw_dim_vertical};
+use crate::layers::*;
+use crate::model::{BuildingModel, DeviceKind, Footprint};
+use crate::thermal::sauna_heat_loss;
+
+// ============================================================================
+// SAUNA HEATERS
+// Heaters come from a catalogue listing each model with its type, footprint,
+// height, power, clearances and flue; the built-in one is
+// templates/heaters.txt, which also describes the format. HEATER_MODEL picks
+// the heater by name. It stands its wall clearance off the south-west corner
+// of the sauna. Wood and gas heaters get a flue straight up through the
+// ceiling and roof to CHIMNEY_ABOVE_ROOF, electric ones a feed and a control
+// panel outside the sauna. The heater must put out the power the heat loss
+// calculation asks for, and a flue must stand FLUE_COMBUSTIBLE_CLEARANCE off
+// the walls, the benches and the roof rafters, which are framed like the
+// wall studs at STUD_SPACING centres from the west end of the building.
+// ============================================================================
+
+const DEFAULT_CATALOGUE: &str = include_str!("../templates/heaters.txt");
+
+/// How a heater is fired
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HeaterKind {
+    Electric,
+    Wood,
+    Gas,
+}
+
+/// One catalogue entry
+#[derive(Clone, Debug)]
+pub struct Heater {
+    pub model: String,
+    pub kind: HeaterKind,
+    pub round: bool,
+    pub width: f64, // Along x, the diameter of a round heater
+    pub depth: f64, // Along y
+    pub height: f64,
+    pub power: f64, // W
+    pub wall_clearance: f64,
+    pub bench_clearance: f64,
+    pub ceiling_clearance: f64,  // Heater top to the ceiling
+    pub flue: Option<f64>,       // Flue pipe diameter
+}
+
+pub struct HeaterCatalogue {
+    pub heaters: Vec<Heater>,
+}
+
+pub struct HeaterCheck {
+    pub heater: Heater,
+    pub required_power: f64, // W, from the sauna heat loss
+    pub problems: Vec<String>,
+}
+
+impl HeaterKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            HeaterKind::Electric => "ELECTRIC",
+            HeaterKind::Wood => "WOOD",
+            HeaterKind::Gas => "GAS",
+        }
+    }
+}
+
+fn number(field: &str, line: usize) -> Result<f64, String> {
+    match field.parse::<f64>() {
+        Ok(value) if value >= 0.0 => Ok(value),
+        _ => Err(format!("line {}: '{}' is not a size", line, field)),
+    }
+}
+
+impl Heater {
+    /// Plan outline with the heater centred at (x, y)
+    pub fn footprint(&self, x: f64, y: f64) -> Footprint {
+        if self.round {
+            Footprint::Circle { x, y, radius: self.width / 2.0 }
+        } else {
+            Footprint::Rect {
+                x1: x - self.width / 2.0,
+                y1: y - self.depth / 2.0,
+                x2: x + self.width / 2.0,
+                y2: y + self.depth / 2.0,
+            }
+        }
+    }
+
+    /// Footprint as written in the catalogue
+    pub fn size(&self) -> String {
+        if self.round {
+            format!("Ø{:.2} m", self.width)
+        } else {
+            format!("{:.2} x {:.2} m", self.width, self.depth)
+        }
+    }
+}
+
+impl HeaterCatalogue {
+    /// Parse a catalogue; errors name the offending line
+    pub fn parse(text: &str) -> Result<HeaterCatalogue, String> {
+        let mut heaters: Vec<Heater> = Vec::new();
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            let raw = raw.trim();
+            if raw.is_empty() || raw.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = raw.split_whitespace().collect();
+            if fields.len() != 9 {
+                return Err(format!("line {}: expected 9 fields, found {}", line, fields.len()));
+            }
+            if heaters.iter().any(|heater| heater.model == fields[0]) {
+                return Err(format!("line {}: model {} listed twice", line, fields[0]));
+            }
+            let kind = match fields[1] {
+                "electric" => HeaterKind::Electric,
+                "wood" => HeaterKind::Wood,
+                "gas" => HeaterKind::Gas,
+                other => return Err(format!("line {}: unknown heater type '{}'", line, other)),
+            };
+            let (round, width, depth) = match fields[2].split_once('x') {
+                Some((width, depth)) => (false, number(width, line)?, number(depth, line)?),
+                None => {
+                    let diameter = number(fields[2], line)?;
+                    (true, diameter, diameter)
+                }
+            };
+            let flue = match fields[8] {
+                "-" => None,
+                field => Some(number(field, line)?),
+            };
+            match (kind, flue) {
+                (HeaterKind::Electric, Some(_)) => {
+                    return Err(format!("line {}: an electric heater has no flue", line));
+                }
+                (HeaterKind::Wood | HeaterKind::Gas, None) => {
+                    return Err(format!("line {}: a {} heater needs a flue", line, fields[1]));
+                }
+                _ => {}
+            }
+            heaters.push(Heater {
+                model: fields[0].to_string(),
+                kind,
+                round,
+                width,
+                depth,
+                height: number(fields[3], line)?,
+                power: number(fields[4], line)? * 1000.0,
+                wall_clearance: number(fields[5], line)?,
+                bench_clearance: number(fields[6], line)?,
+                ceiling_clearance: number(fields[7], line)?,
+                flue,
+            });
+        }
+        if heaters.is_empty() {
+            return Err("no heaters in the catalogue".to_string());
+        }
+        Ok(HeaterCatalogue { heaters })
+    }
+
+    /// Load a catalogue file
+    pub fn load(path: &str) -> Result<HeaterCatalogue, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        HeaterCatalogue::parse(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    /// The heater of a model, by name
+    pub fn select(&self, model: &str) -> Result<Heater, String> {
+        self.heaters
+            .iter()
+            .find(|heater| heater.model == model)
+            .cloned()
+            .ok_or_else(|| format!("heater {} is not in the catalogue", model))
+    }
+}
+
+impl Default for HeaterCatalogue {
+    fn default() -> Self {
+        HeaterCatalogue::parse(DEFAULT_CATALOGUE).expect("built-in heater catalogue is valid")
+    }
+}
+
+/// Plan box of the heater in the model, centred on the heater
+fn heater_box(model: &BuildingModel) -> Option<(f64, f64, f64, f64)> {
+    match model.furnishing("heater")?.footprint {
+        Footprint::Circle { x, y, radius } => Some((x - radius, y - radius, x + radius, y + radius)),
+        Footprint::Rect { x1, y1, x2, y2 } => Some((x1, y1, x2, y2)),
+    }
+}
+
+/// Top of the flue above the deck surface, for heaters with one
+fn chimney_top() -> f64 {
+    BUILDING_HEIGHT + ROOF_THICKNESS + CHIMNEY_ABOVE_ROOF
+}
+
+/// Flue clearances to the sauna walls, the benches and the roof rafters
+fn flue_problems(model: &BuildingModel, flue: f64, x: f64, y: f64) -> Vec<String> {
+    let room = model.sauna();
+    let r = flue / 2.0;
+    let mut problems = Vec::new();
+
+    let wall = (x - room.x1).min(y - room.y1).min(room.x2 - x).min(room.y2 - y) - r;
+    if wall < FLUE_COMBUSTIBLE_CLEARANCE - 1e-9 {
+        problems.push(format!("FLUE {:.2} m FROM A WALL (MIN {:.2} m)", wall.max(0.0), FLUE_COMBUSTIBLE_CLEARANCE));
+    }
+
+    for bench in model.furnishings.iter().filter(|furnishing| furnishing.id.starts_with("bench-")) {
+        let clearance = (bench.footprint.distance_to(x, y) - r).max(0.0);
+        if clearance < FLUE_COMBUSTIBLE_CLEARANCE - 1e-9 {
+            problems.push(format!(
+                "FLUE {:.2} m FROM {} (MIN {:.2} m)",
+                clearance,
+                bench.id.to_uppercase(),
+                FLUE_COMBUSTIBLE_CLEARANCE
+            ));
+        }
+    }
+
+    // Nearest rafter either side of the flue
+    let below = ((x - STUD_WIDTH) / STUD_SPACING).floor() * STUD_SPACING;
+    for rafter in [below, below + STUD_SPACING] {
+        let clearance = ((rafter - x).max(x - rafter - STUD_WIDTH) - r).max(0.0);
+        if clearance < FLUE_COMBUSTIBLE_CLEARANCE - 1e-9 {
+            problems.push(format!(
+                "FLUE {:.2} m FROM THE ROOF RAFTER AT {:.2} m (MIN {:.2} m)",
+                clearance, rafter, FLUE_COMBUSTIBLE_CLEARANCE
+            ));
+        }
+    }
+    problems
+}
+
+/// Output of the heater against the size of the sauna room, its clearances to
+/// the sauna walls and ceiling, and its control panel or flue
+pub fn heater_check(model: &BuildingModel) -> HeaterCheck {
+    let heater = model.heater.clone();
+    let room = model.sauna();
+    let required_power = sauna_heat_loss(model).heater_kw() * 1000.0;
+    let mut problems = Vec::new();
+
+    if heater.power < required_power - 1e-6 {
+        problems.push(format!(
+            "HEATER {} {:.1} kW IS BELOW THE {:.1} kW MINIMUM",
+            heater.model,
+            heater.power / 1000.0,
+            required_power / 1000.0
+        ));
+    }
+
+    if let Some((x1, y1, x2, y2)) = heater_box(model) {
+        let wall = (x1 - room.x1).min(y1 - room.y1).min(room.x2 - x2).min(room.y2 - y2);
+        if wall < heater.wall_clearance - 1e-9 {
+            problems.push(format!(
+                "HEATER {:.2} m FROM A WALL (MIN {:.2} m)",
+                wall, heater.wall_clearance
+            ));
+        }
+    } else {
+        problems.push("NO HEATER IN THE SAUNA".to_string());
+    }
+
+    let ceiling = room.ceiling.unwrap_or(BUILDING_HEIGHT);
+    if ceiling - heater.height < heater.ceiling_clearance {
+        problems.push(format!(
+            "HEATER TOP {:.2} m UNDER THE CEILING (MIN {:.2} m)",
+            ceiling - heater.height,
+            heater.ceiling_clearance
+        ));
+    }
+
+    if heater.kind == HeaterKind::Electric && !model.devices.iter().any(|d| d.kind == DeviceKind::HeaterControl) {
+        problems.push("NO CONTROL PANEL FOR THE ELECTRIC HEATER".to_string());
+    }
+
+    if let (Some(flue), Some(furnishing)) = (heater.flue, model.furnishing("heater")) {
+        let (x, y) = furnishing.footprint.centre();
+        problems.extend(flue_problems(model, flue, x, y));
+    }
+
+    HeaterCheck { heater, required_power, problems }
+}
+
+impl HeaterCheck {
+    /// Report lines for the sheet and the console
+    pub fn lines(&self) -> Vec<String> {
+        let heater = &self.heater;
+        let mut lines = vec![
+            format!(
+                "{} {}, {:.1} kW, {}, {:.2} m HIGH",
+                heater.model,
+                heater.kind.name(),
+                heater.power / 1000.0,
+                heater.size(),
+                heater.height
+            ),
+            format!("OUTPUT NEEDED {:.1} kW FOR THE SAUNA ROOM", self.required_power / 1000.0),
+            format!(
+                "CLEARANCES: WALLS {:.2} m, BENCHES {:.2} m, CEILING {:.2} m",
+                heater.wall_clearance, heater.bench_clearance, heater.ceiling_clearance
+            ),
+        ];
+        match heater.flue {
+            Some(flue) => lines.push(format!(
+                "FLUE Ø{:.0} mm TO {:.2} m ABOVE THE ROOF, {:.0} mm CLEAR OF COMBUSTIBLES",
+                flue * 1000.0,
+                CHIMNEY_ABOVE_ROOF,
+                FLUE_COMBUSTIBLE_CLEARANCE * 1000.0
+            )),
+            None => lines.push(format!("CONTROL PANEL OUTSIDE THE SAUNA AT {:.2} m", HEATER_CONTROL_HEIGHT)),
+        }
+        if self.problems.is_empty() {
+            lines.push("HEATER OUTPUT AND CLEARANCES OK".to_string());
+        }
+        lines.extend(self.problems.iter().map(|problem| format!("WARNING: {}", problem)));
+        lines
+    }
+}
+
+/// Flue pipe seen from the side at view x `x`, from the heater top up to the
+/// rain cap, with the clearance to combustibles through the ceiling and roof
+fn draw_flue_side(drawing: &mut Drawing, heater: &Heater, flue: f64, x: f64, floor: f64, show_heater_run: bool) {
+    let r = flue / 2.0;
+    let bottom = if show_heater_run { floor + heater.height } else { floor + BUILDING_HEIGHT + ROOF_THICKNESS };
+    let top = floor + chimney_top();
+    add_line(drawing, LAYER_FIXTURES, x - r, bottom, x - r, top);
+    add_line(drawing, LAYER_FIXTURES, x + r, bottom, x + r, top);
+    add_line(drawing, LAYER_FIXTURES, x - r * 2.0, top, x + r * 2.0, top);
+    if show_heater_run {
+        let clear = r + FLUE_COMBUSTIBLE_CLEARANCE;
+        add_rectangle(drawing, LAYER_FIXTURES, x - clear, floor + BUILDING_HEIGHT, x + clear, floor + BUILDING_HEIGHT + ROOF_THICKNESS);
+    }
+}
+
+/// Heater in the floor plan over its furnishing outline: the flue of a wood
+/// or gas heater
+pub fn draw_heater_plan(drawing: &mut Drawing, model: &BuildingModel) {
+    if let (Some(flue), Some((x1, y1, x2, y2))) = (model.heater.flue, heater_box(model)) {
+        add_circle(drawing, LAYER_FIXTURES, (x1 + x2) / 2.0, (y1 + y2) / 2.0, flue / 2.0);
+    }
+}
+
+/// Heater in section A-A, which looks south so model x runs from right to
+/// left: its outline seen beyond the cut, and the flue through the roof with
+/// the chimney height dimensioned
+pub fn draw_heater_section(drawing: &mut Drawing, model: &BuildingModel, offset_x: f64, offset_y: f64) {
+    let Some((x1, _, x2, _)) = heater_box(model) else { return };
+    let floor = offset_y + DECK_ELEV_HEIGHT;
+    let section_x = |x: f64| offset_x + SAUNA_WIDTH - x;
+    let heater = &model.heater;
+    add_rectangle(drawing, LAYER_FIXTURES, section_x(x2), floor, section_x(x1), floor + heater.height);
+
+    if let Some(flue) = heater.flue {
+        let x = section_x((x1 + x2) / 2.0);
+        draw_flue_side(drawing, heater, flue, x, floor, true);
+        let roof_top = floor + BUILDING_HEIGHT + ROOF_THICKNESS;
+        draw_dim_vertical(drawing, x + flue / 2.0, roof_top, floor + chimney_top(), 0.25, "");
+    }
+}
+
+/// Chimney above the roof in the front elevation, which looks north
+pub fn draw_heater_front_elevation(drawing: &mut Drawing, model: &BuildingModel, offset_x: f64, offset_y: f64) {
+    let (Some(flue), Some((x1, _, x2, _))) = (model.heater.flue, heater_box(model)) else { return };
+    draw_flue_side(drawing, &model.heater, flue, offset_x + (x1 + x2) / 2.0, offset_y + DECK_ELEV_HEIGHT, false);
+}
+
+/// Chimney in the roof plan, drawn with the enclosure's south-west corner at
+/// (offset_x, offset_y): the flue and its clearance to the roof deck
+pub fn draw_heater_roof_plan(drawing: &mut Drawing, model: &BuildingModel, offset_x: f64, offset_y: f64) {
+    let (Some(flue), Some((x1, y1, x2, y2))) = (model.heater.flue, heater_box(model)) else { return };
+    let (x, y) = (offset_x + (x1 + x2) / 2.0, offset_y + (y1 + y2) / 2.0 - DECK_DEPTH);
+    add_circle(drawing, LAYER_FIXTURES, x, y, flue / 2.0);
+    add_circle(drawing, LAYER_FIXTURES, x, y, flue / 2.0 + FLUE_COMBUSTIBLE_CLEARANCE);
+}
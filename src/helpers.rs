@@ -4,8 +4,17 @@ use dxf::{Drawing, LwPolylineVertex, Point};
 use crate::constants::*;
 use crate::layers::*;
 
-pub fn polyline_from_points(points: Vec<Point>) -> LwPolyline {
+/// Sheet extents (x1, y1, x2, y2)
+pub type Extents = (f64, f64, f64, f64);
+
+/// Build a polyline; a point list that returns to its start becomes a
+/// closed polyline without the repeated vertex
+pub fn polyline_from_points(mut points: Vec<Point>) -> LwPolyline {
     let mut polyline = LwPolyline::default();
+    if points.len() > 2 && points.first() == points.last() {
+        points.pop();
+        polyline.set_is_closed(true);
+    }
     for point in points {
         polyline.vertices.push(LwPolylineVertex {
             x: point.x,
@@ -19,6 +28,97 @@ pub fn polyline_from_points(points: Vec<Point>) -> LwPolyline {
     polyline
 }
 
+/// Closed scalloped outline around a rectangle (revision cloud)
+pub fn revision_cloud(x1: f64, y1: f64, x2: f64, y2: f64) -> LwPolyline {
+    let corners = [(x1, y1), (x2, y1), (x2, y2), (x1, y2)];
+    let mut polyline = LwPolyline::default();
+    polyline.set_is_closed(true);
+
+    // Counter-clockwise outline; a positive bulge bows each scallop outwards
+    for i in 0..4 {
+        let (ax, ay) = corners[i];
+        let (bx, by) = corners[(i + 1) % 4];
+        let length = ((bx - ax).powi(2) + (by - ay).powi(2)).sqrt();
+        let count = (length / CLOUD_ARC_LENGTH).ceil().max(1.0) as usize;
+        for step in 0..count {
+            let t = step as f64 / count as f64;
+            polyline.vertices.push(LwPolylineVertex {
+                x: ax + (bx - ax) * t,
+                y: ay + (by - ay) * t,
+                bulge: 0.5,
+                ..Default::default()
+            });
+        }
+    }
+    polyline
+}
+
+/// Add a line on a layer
+pub fn add_line(drawing: &mut Drawing, layer: &str, x1: f64, y1: f64, x2: f64, y2: f64) {
+    let line = Line {
+        p1: Point::new(x1, y1, 0.0),
+        p2: Point::new(x2, y2, 0.0),
+        ..Default::default()
+    };
+    let mut entity = Entity::new(EntityType::Line(line));
+    entity.common.layer = layer.to_string();
+    drawing.add_entity(entity);
+}
+
+/// Add a closed polygon through the given corners on a layer
+pub fn add_polygon(drawing: &mut Drawing, layer: &str, points: &[(f64, f64)]) {
+    let mut points: Vec<Point> = points.iter().map(|&(x, y)| Point::new(x, y, 0.0)).collect();
+    points.push(points[0].clone());
+    let mut entity = Entity::new(EntityType::LwPolyline(polyline_from_points(points)));
+    entity.common.layer = layer.to_string();
+    drawing.add_entity(entity);
+}
+
+/// Add an axis-aligned rectangle between two corners on a layer
+pub fn add_rectangle(drawing: &mut Drawing, layer: &str, x1: f64, y1: f64, x2: f64, y2: f64) {
+    add_polygon(drawing, layer, &[(x1, y1), (x2, y1), (x2, y2), (x1, y2)]);
+}
+
+/// Add a circle on a layer
+pub fn add_circle(drawing: &mut Drawing, layer: &str, x: f64, y: f64, radius: f64) {
+    let circle = Circle {
+        center: Point::new(x, y, 0.0),
+        radius,
+        ..Default::default()
+    };
+    let mut entity = Entity::new(EntityType::Circle(circle));
+    entity.common.layer = layer.to_string();
+    drawing.add_entity(entity);
+}
+
+/// Add text turned `rotation` degrees anticlockwise on a layer
+pub fn add_rotated_text(drawing: &mut Drawing, layer: &str, x: f64, y: f64, height: f64, rotation: f64, value: String) {
+    let text = Text {
+        location: Point::new(x, y, 0.0),
+        text_height: height,
+        rotation,
+        value,
+        ..Default::default()
+    };
+    let mut entity = Entity::new(EntityType::Text(text));
+    entity.common.layer = layer.to_string();
+    drawing.add_entity(entity);
+}
+
+/// Add horizontal text on a layer
+pub fn add_text(drawing: &mut Drawing, layer: &str, x: f64, y: f64, height: f64, value: String) {
+    add_rotated_text(drawing, layer, x, y, height, 0.0, value);
+}
+
+/// Draw a titled note block with its top-left at (x, y): the title on the
+/// text layer, then one line per row on `layer`
+pub fn draw_note_block(drawing: &mut Drawing, title: &str, lines: Vec<String>, layer: &str, x: f64, y: f64) {
+    add_text(drawing, LAYER_TEXT, x, y, LABEL_TEXT_HEIGHT, title.to_string());
+    for (i, line) in lines.into_iter().enumerate() {
+        add_text(drawing, layer, x, y - DIM_TEXT_HEIGHT * 2.0 * (i + 1) as f64, DIM_TEXT_HEIGHT, line);
+    }
+}
+
 /// Draw a horizontal dimension line
 pub fn draw_dim_horizontal(
     drawing: &mut Drawing,
@@ -174,6 +274,55 @@ pub fn draw_dim_vertical(
     drawing.add_entity(entity);
 }
 
+/// Draw a dimension along the line from (x1, y1) to (x2, y2), with the text
+/// beside it and turned to read from below or from the right
+pub fn draw_dim_aligned(drawing: &mut Drawing, x1: f64, y1: f64, x2: f64, y2: f64, prefix: &str) {
+    let length = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt();
+    if length < 1e-9 {
+        return;
+    }
+    let (ux, uy) = ((x2 - x1) / length, (y2 - y1) / length);
+
+    // Dimension line with a tick at each end, square to the line
+    let mut lines = vec![(x1, y1, x2, y2)];
+    for (x, y) in [(x1, y1), (x2, y2)] {
+        let (tx, ty) = ((ux - uy) * DIM_TICK_SIZE, (uy + ux) * DIM_TICK_SIZE);
+        lines.push((x - tx, y - ty, x + tx, y + ty));
+    }
+    for (ax, ay, bx, by) in lines {
+        let line = Line {
+            p1: Point::new(ax, ay, 0.0),
+            p2: Point::new(bx, by, 0.0),
+            ..Default::default()
+        };
+        let mut entity = Entity::new(EntityType::Line(line));
+        entity.common.layer = LAYER_DIMENSIONS.to_string();
+        drawing.add_entity(entity);
+    }
+
+    // Text on the left of the line as it reads
+    let (ux, uy) = if ux < -1e-9 || (ux.abs() <= 1e-9 && uy < 0.0) { (-ux, -uy) } else { (ux, uy) };
+    let text_value = if prefix.is_empty() {
+        format!("{:.2}m", length)
+    } else {
+        format!("{}{:.2}m", prefix, length)
+    };
+    let dim_text = Text {
+        location: Point::new(
+            (x1 + x2) / 2.0 - uy * DIM_TEXT_HEIGHT * 0.5,
+            (y1 + y2) / 2.0 + ux * DIM_TEXT_HEIGHT * 0.5,
+            0.0,
+        ),
+        text_height: DIM_TEXT_HEIGHT,
+        value: text_value,
+        rotation: uy.atan2(ux).to_degrees(),
+        ..Default::default()
+    };
+    let mut entity = Entity::new(EntityType::Text(dim_text));
+    entity.common.layer = LAYER_DIMENSIONS.to_string();
+    drawing.add_entity(entity);
+}
+
 /// Draw a door symbol (plan view) with swing arc
 /// hinge_x, hinge_y: position of door hinge
 /// direction: 0=right, 90=up, 180=left, 270=down (swing direction)
@@ -345,12 +494,32 @@ pub fn draw_room_label(
     drawing.add_entity(entity);
 }
 
-/// Draw north arrow
-pub fn draw_north_arrow(drawing: &mut Drawing, x: f64, y: f64, size: f64) {
+/// Draw an arrow from (x1, y1) to (x2, y2) with an open head of the given size
+pub fn draw_arrow(drawing: &mut Drawing, layer: &str, x1: f64, y1: f64, x2: f64, y2: f64, head: f64) {
+    let length = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt();
+    let (ux, uy) = ((x2 - x1) / length, (y2 - y1) / length);
+    let (bx, by) = (x2 - ux * head, y2 - uy * head);
+    for (px, py) in [(x1, y1), (bx - uy * head * 0.4, by + ux * head * 0.4), (bx + uy * head * 0.4, by - ux * head * 0.4)] {
+        let line = Line {
+            p1: Point::new(px, py, 0.0),
+            p2: Point::new(x2, y2, 0.0),
+            ..Default::default()
+        };
+        let mut entity = Entity::new(EntityType::Line(line));
+        entity.common.layer = layer.to_string();
+        drawing.add_entity(entity);
+    }
+}
+
+/// Draw north arrow pointing `rotation` degrees anticlockwise from straight up
+pub fn draw_north_arrow(drawing: &mut Drawing, x: f64, y: f64, size: f64, rotation: f64) {
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let at = |dx: f64, dy: f64| Point::new(x + dx * cos - dy * sin, y + dx * sin + dy * cos, 0.0);
+
     // Arrow shaft
     let shaft = Line {
-        p1: Point::new(x, y, 0.0),
-        p2: Point::new(x, y + size, 0.0),
+        p1: at(0.0, 0.0),
+        p2: at(0.0, size),
         ..Default::default()
     };
     let mut entity = Entity::new(EntityType::Line(shaft));
@@ -359,19 +528,20 @@ pub fn draw_north_arrow(drawing: &mut Drawing, x: f64, y: f64, size: f64) {
 
     // Arrow head (triangle)
     let head = polyline_from_points(vec![
-        Point::new(x, y + size, 0.0),
-        Point::new(x - size * 0.2, y + size * 0.7, 0.0),
-        Point::new(x, y + size * 0.8, 0.0),
-        Point::new(x + size * 0.2, y + size * 0.7, 0.0),
-        Point::new(x, y + size, 0.0),
+        at(0.0, size),
+        at(-size * 0.2, size * 0.7),
+        at(0.0, size * 0.8),
+        at(size * 0.2, size * 0.7),
+        at(0.0, size),
     ]);
     let mut entity = Entity::new(EntityType::LwPolyline(head));
     entity.common.layer = LAYER_ANNO.to_string();
     drawing.add_entity(entity);
 
-    // N label
+    // N label, kept upright beyond the tip
+    let tip = at(0.0, size * 1.35);
     let n_text = Text {
-        location: Point::new(x - size * 0.1, y + size + size * 0.2, 0.0),
+        location: Point::new(tip.x - size * 0.1, tip.y - size * 0.15, 0.0),
         text_height: size * 0.3,
         value: "N".to_string(),
         ..Default::default()
@@ -381,86 +551,158 @@ pub fn draw_north_arrow(drawing: &mut Drawing, x: f64, y: f64, size: f64) {
     drawing.add_entity(entity);
 }
 
-/// Draw title block
-pub fn draw_title_block(
-    drawing: &mut Drawing,
-    x: f64,
-    y: f64,
-    width: f64,
-    height: f64,
-) {
-    // Outer border
+/// Draw the revision table with its lower-right corner at (x, y): a header
+/// row at the bottom and one row per revision above it, latest on top
+pub fn draw_revision_table(drawing: &mut Drawing, x: f64, y: f64, width: f64) {
+    let row_height = DIM_TEXT_HEIGHT * 2.0;
+    let height = row_height * (REVISIONS.len() + 1) as f64;
+    let left = x - width;
+
+    // Column starts as fractions of the width: REV, DATE, DESCRIPTION, BY
+    let columns = [0.0, 0.10, 0.35, 0.88];
+
     let border = polyline_from_points(vec![
+        Point::new(left, y, 0.0),
         Point::new(x, y, 0.0),
-        Point::new(x + width, y, 0.0),
-        Point::new(x + width, y + height, 0.0),
         Point::new(x, y + height, 0.0),
-        Point::new(x, y, 0.0),
+        Point::new(left, y + height, 0.0),
+        Point::new(left, y, 0.0),
     ]);
     let mut entity = Entity::new(EntityType::LwPolyline(border));
     entity.common.layer = LAYER_TITLE.to_string();
     drawing.add_entity(entity);
 
-    // Horizontal dividers
-    let div1 = Line {
-        p1: Point::new(x, y + height * 0.5, 0.0),
-        p2: Point::new(x + width, y + height * 0.5, 0.0),
-        ..Default::default()
-    };
-    let mut entity = Entity::new(EntityType::Line(div1));
-    entity.common.layer = LAYER_TITLE.to_string();
-    drawing.add_entity(entity);
+    // Row and column dividers
+    for row in 1..=REVISIONS.len() {
+        let row_y = y + row_height * row as f64;
+        let line = Line {
+            p1: Point::new(left, row_y, 0.0),
+            p2: Point::new(x, row_y, 0.0),
+            ..Default::default()
+        };
+        let mut entity = Entity::new(EntityType::Line(line));
+        entity.common.layer = LAYER_TITLE.to_string();
+        drawing.add_entity(entity);
+    }
+    for column in &columns[1..] {
+        let line = Line {
+            p1: Point::new(left + width * column, y, 0.0),
+            p2: Point::new(left + width * column, y + height, 0.0),
+            ..Default::default()
+        };
+        let mut entity = Entity::new(EntityType::Line(line));
+        entity.common.layer = LAYER_TITLE.to_string();
+        drawing.add_entity(entity);
+    }
 
-    let div2 = Line {
-        p1: Point::new(x, y + height * 0.25, 0.0),
-        p2: Point::new(x + width, y + height * 0.25, 0.0),
-        ..Default::default()
-    };
-    let mut entity = Entity::new(EntityType::Line(div2));
-    entity.common.layer = LAYER_TITLE.to_string();
-    drawing.add_entity(entity);
+    // Header, then the revisions
+    let header = ["REV", "DATE", "DESCRIPTION", "BY"];
+    let rows = std::iter::once(header).chain(
+        REVISIONS
+            .iter()
+            .map(|rev| [rev.number, rev.date, rev.description, rev.author]),
+    );
+    for (row, cells) in rows.enumerate() {
+        let text_y = y + row_height * row as f64 + (row_height - DIM_TEXT_HEIGHT) / 2.0;
+        for (column, value) in columns.iter().zip(cells) {
+            let text = Text {
+                location: Point::new(left + width * column + 0.05, text_y, 0.0),
+                text_height: DIM_TEXT_HEIGHT,
+                value: value.to_string(),
+                ..Default::default()
+            };
+            let mut entity = Entity::new(EntityType::Text(text));
+            entity.common.layer = LAYER_TITLE.to_string();
+            drawing.add_entity(entity);
+        }
+    }
+}
 
-    // Project title
-    let title = Text {
-        location: Point::new(x + width * 0.05, y + height * 0.7, 0.0),
-        text_height: TITLE_TEXT_HEIGHT,
-        value: "SAUNA BUILDING".to_string(),
-        ..Default::default()
-    };
-    let mut entity = Entity::new(EntityType::Text(title));
-    entity.common.layer = LAYER_TITLE.to_string();
-    drawing.add_entity(entity);
+/// Revision clouds tagged with a revision that has no row in REVISIONS
+pub fn revision_cloud_problems(clouds: &[RevisionCloud]) -> Vec<String> {
+    clouds
+        .iter()
+        .filter(|cloud| !REVISIONS.iter().any(|revision| revision.number == cloud.revision))
+        .map(|cloud| format!("REVISION CLOUD {} HAS NO ROW IN THE REVISION TABLE", cloud.revision))
+        .collect()
+}
 
-    // Drawing title
-    let drawing_title = Text {
-        location: Point::new(x + width * 0.05, y + height * 0.35, 0.0),
-        text_height: LABEL_TEXT_HEIGHT,
-        value: "FLOOR PLAN & ELEVATIONS".to_string(),
-        ..Default::default()
-    };
-    let mut entity = Entity::new(EntityType::Text(drawing_title));
-    entity.common.layer = LAYER_TITLE.to_string();
-    drawing.add_entity(entity);
+/// Draw a revision cloud CLOUD_MARGIN around the extents of each tagged
+/// view, with a revision triangle carrying its number at the top-left corner;
+/// clouds of a revision missing from REVISIONS are left out
+pub fn draw_revision_clouds(drawing: &mut Drawing, clouds: &[RevisionCloud], views: &[(SheetView, Extents)]) {
+    for cloud in clouds {
+        if !REVISIONS.iter().any(|revision| revision.number == cloud.revision) {
+            continue;
+        }
+        let Some(&(_, (x1, y1, x2, y2))) = views.iter().find(|(view, _)| *view == cloud.view) else { continue };
+        let (x1, y1, x2, y2) = (x1 - CLOUD_MARGIN, y1 - CLOUD_MARGIN, x2 + CLOUD_MARGIN, y2 + CLOUD_MARGIN);
+        let outline = revision_cloud(x1, y1, x2, y2);
+        let mut entity = Entity::new(EntityType::LwPolyline(outline));
+        entity.common.layer = LAYER_REV_CLOUD.to_string();
+        drawing.add_entity(entity);
 
-    // Scale
-    let scale_text = Text {
-        location: Point::new(x + width * 0.05, y + height * 0.1, 0.0),
-        text_height: DIM_TEXT_HEIGHT,
-        value: format!("SCALE: {}", DRAWING_SCALE),
-        ..Default::default()
-    };
-    let mut entity = Entity::new(EntityType::Text(scale_text));
-    entity.common.layer = LAYER_TITLE.to_string();
-    drawing.add_entity(entity);
+        // Revision triangle
+        let size = LABEL_TEXT_HEIGHT * 2.5;
+        let (tx, ty) = (x1, y2 + CLOUD_ARC_LENGTH);
+        let triangle = polyline_from_points(vec![
+            Point::new(tx, ty, 0.0),
+            Point::new(tx + size, ty, 0.0),
+            Point::new(tx + size / 2.0, ty + size * 0.87, 0.0),
+            Point::new(tx, ty, 0.0),
+        ]);
+        let mut entity = Entity::new(EntityType::LwPolyline(triangle));
+        entity.common.layer = LAYER_REV_CLOUD.to_string();
+        drawing.add_entity(entity);
 
-    // Date
-    let date_text = Text {
-        location: Point::new(x + width * 0.5, y + height * 0.1, 0.0),
-        text_height: DIM_TEXT_HEIGHT,
-        value: "DATE: 2026-01-03".to_string(),
-        ..Default::default()
-    };
-    let mut entity = Entity::new(EntityType::Text(date_text));
-    entity.common.layer = LAYER_TITLE.to_string();
-    drawing.add_entity(entity);
+        let number = Text {
+            location: Point::new(tx + size / 2.0 - LABEL_TEXT_HEIGHT * 0.35, ty + size * 0.15, 0.0),
+            text_height: LABEL_TEXT_HEIGHT,
+            value: cloud.revision.to_string(),
+            ..Default::default()
+        };
+        let mut entity = Entity::new(EntityType::Text(number));
+        entity.common.layer = LAYER_REV_CLOUD.to_string();
+        drawing.add_entity(entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn revision_cloud_wraps_its_view() {
+        let mut drawing = Drawing::new();
+        let clouds = [
+            RevisionCloud { revision: "A", view: SheetView::Iso },
+            RevisionCloud { revision: "A", view: SheetView::Section },
+        ];
+        draw_revision_clouds(&mut drawing, &clouds, &[(SheetView::Iso, (10.0, 2.0, 14.0, 5.0))]);
+
+        // One cloud and its triangle and number; the section was not drawn
+        let outlines: Vec<&LwPolyline> = drawing
+            .entities()
+            .filter_map(|entity| match &entity.specific {
+                EntityType::LwPolyline(polyline) => Some(polyline),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(outlines.len(), 2);
+        let xs = outlines[0].vertices.iter().map(|v| v.x);
+        let ys = outlines[0].vertices.iter().map(|v| v.y);
+        let (x1, x2) = (xs.clone().fold(f64::MAX, f64::min), xs.fold(f64::MIN, f64::max));
+        let (y1, y2) = (ys.clone().fold(f64::MAX, f64::min), ys.fold(f64::MIN, f64::max));
+        assert!((x1 - (10.0 - CLOUD_MARGIN)).abs() < 1e-9 && (x2 - (14.0 + CLOUD_MARGIN)).abs() < 1e-9);
+        assert!((y1 - (2.0 - CLOUD_MARGIN)).abs() < 1e-9 && (y2 - (5.0 + CLOUD_MARGIN)).abs() < 1e-9);
+    }
+    #[test]
+    fn cloud_without_a_revision_row_is_reported_and_left_out() {
+        let mut drawing = Drawing::new();
+        let clouds = [RevisionCloud { revision: "Z", view: SheetView::Iso }];
+        draw_revision_clouds(&mut drawing, &clouds, &[(SheetView::Iso, (10.0, 2.0, 14.0, 5.0))]);
+        assert_eq!(drawing.entities().count(), 0);
+        assert_eq!(revision_cloud_problems(&clouds), ["REVISION CLOUD Z HAS NO ROW IN THE REVISION TABLE"]);
+        assert!(revision_cloud_problems(&[RevisionCloud { revision: "A", view: SheetView::Iso }]).is_empty());
+    }
 }
@@ -0,0 +1,347 @@
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_8, PI};
+
+use dxf::entities::*;
+use dxf::{Drawing, LwPolylineVertex};
+
+use crate::constants::*;
+use crate::helpers::{add_circle, add_polygon};
+use crate::layers::*;
+use crate::loads::hot_tub_centre;
+
+// ============================================================================
+// HOT TUB
+// The shell outline is a rectangle with rounded corners: a round tub is one
+// whose corner radius is half its size. The water line is the shell inset by
+// HOT_TUB_WALL, the cover the shell grown by HOT_TUB_COVER_OVERHANG. Seats sit
+// inside the water line, jets on it and steps outside the shell, each placed
+// by its fraction of the way round the outline.
+// ============================================================================
+
+/// Segments along the back of a seat, enough to follow a corner
+const SEAT_POINTS: usize = 6;
+
+/// Rounded rectangle in plan: centre, half length (x), half width (y) and
+/// corner radius
+#[derive(Clone, Copy, Debug)]
+pub struct TubOutline {
+    pub x: f64,
+    pub y: f64,
+    pub half_length: f64,
+    pub half_width: f64,
+    pub radius: f64,
+}
+
+impl TubOutline {
+    /// Same outline moved in by `inset` all round (out for a negative inset)
+    pub fn inset(&self, inset: f64) -> TubOutline {
+        TubOutline {
+            half_length: self.half_length - inset,
+            half_width: self.half_width - inset,
+            radius: (self.radius - inset).max(0.0),
+            ..*self
+        }
+    }
+
+    pub fn bounds(&self) -> (f64, f64, f64, f64) {
+        (self.x - self.half_length, self.y - self.half_width, self.x + self.half_length, self.y + self.half_width)
+    }
+
+    pub fn area(&self) -> f64 {
+        4.0 * self.half_length * self.half_width - (4.0 - PI) * self.radius * self.radius
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        4.0 * (self.half_length + self.half_width - 2.0 * self.radius) + 2.0 * PI * self.radius
+    }
+
+    /// Half the north-south extent of the outline at plan x, zero outside it
+    pub fn half_chord(&self, x: f64) -> f64 {
+        let straight = self.half_length - self.radius;
+        let dx = (x - self.x).abs() - straight;
+        if dx <= 0.0 {
+            self.half_width
+        } else if dx < self.radius {
+            self.half_width - self.radius + (self.radius * self.radius - dx * dx).sqrt()
+        } else {
+            0.0
+        }
+    }
+
+    /// Plan distance from a point to the outline, negative inside it
+    pub fn distance_to(&self, px: f64, py: f64) -> f64 {
+        let qx = (px - self.x).abs() - (self.half_length - self.radius);
+        let qy = (py - self.y).abs() - (self.half_width - self.radius);
+        qx.max(0.0).hypot(qy.max(0.0)) + qx.max(qy).min(0.0) - self.radius
+    }
+
+    /// Point of the outline farthest in the unit direction (dx, dy)
+    pub fn extreme(&self, dx: f64, dy: f64) -> (f64, f64) {
+        let (a, b) = (self.half_length - self.radius, self.half_width - self.radius);
+        (self.x + a * dx.signum() + self.radius * dx, self.y + b * dy.signum() + self.radius * dy)
+    }
+
+    /// Nearest point of the outline to a point outside it
+    pub fn nearest(&self, px: f64, py: f64) -> (f64, f64) {
+        let (a, b) = (self.half_length - self.radius, self.half_width - self.radius);
+        let (cx, cy) = (px.clamp(self.x - a, self.x + a), py.clamp(self.y - b, self.y + b));
+        let d = (px - cx).hypot(py - cy);
+        if d < 1e-9 {
+            return (cx, cy);
+        }
+        (cx + (px - cx) * self.radius / d, cy + (py - cy) * self.radius / d)
+    }
+
+    /// True if the rectangle (x1, y1, x2, y2) reaches inside the outline
+    pub fn overlaps(&self, x1: f64, y1: f64, x2: f64, y2: f64) -> bool {
+        let (px, py) = (self.x.clamp(x1, x2), self.y.clamp(y1, y2));
+        self.distance_to(px, py) < 0.0
+    }
+
+    /// Point on the outline `t` of the way round it, counterclockwise from
+    /// the middle of the south side, with the outward normal there
+    pub fn point_at(&self, t: f64) -> ((f64, f64), (f64, f64)) {
+        let (a, b, r) = (self.half_length - self.radius, self.half_width - self.radius, self.radius);
+        let mut s = t.rem_euclid(1.0) * self.perimeter();
+        let (mut start, mut dir) = ((0.0, -self.half_width), (1.0, 0.0));
+        // Each straight run followed by the corner at its end: (run, corner centre, start angle)
+        let runs = [(a, (a, -b), -FRAC_PI_2), (2.0 * b, (a, b), 0.0), (2.0 * a, (-a, b), FRAC_PI_2), (2.0 * b, (-a, -b), PI)];
+        for (run, (ox, oy), angle) in runs {
+            if s <= run {
+                return ((self.x + start.0 + dir.0 * s, self.y + start.1 + dir.1 * s), (dir.1, -dir.0));
+            }
+            s -= run;
+            if s < r * FRAC_PI_2 {
+                let (sin, cos) = (angle + s / r).sin_cos();
+                return ((self.x + ox + r * cos, self.y + oy + r * sin), (cos, sin));
+            }
+            s -= r * FRAC_PI_2;
+            let (sin, cos) = (angle + FRAC_PI_2).sin_cos();
+            start = (ox + r * cos, oy + r * sin);
+            dir = (-sin, cos);
+        }
+        let s = s.min(a);
+        ((self.x + start.0 + dir.0 * s, self.y + start.1 + dir.1 * s), (dir.1, -dir.0))
+    }
+
+    /// Polyline vertices (x, y, bulge) counterclockwise, corners as arcs
+    pub fn vertices(&self) -> Vec<(f64, f64, f64)> {
+        let (a, b, r) = (self.half_length - self.radius, self.half_width - self.radius, self.radius);
+        let bulge = if r > 0.0 { FRAC_PI_8.tan() } else { 0.0 };
+        let mut vertices: Vec<(f64, f64, f64)> = Vec::new();
+        for ((ox, oy), angle) in [((a, -b), -FRAC_PI_2), ((a, b), 0.0), ((-a, b), FRAC_PI_2), ((-a, -b), PI)] {
+            for (angle, bulge) in [(angle, bulge), (angle + FRAC_PI_2, 0.0)] {
+                let (sin, cos) = f64::sin_cos(angle);
+                let point = (self.x + ox + r * cos, self.y + oy + r * sin, bulge);
+                // A run of zero length leaves two vertices at one point
+                match vertices.last_mut() {
+                    Some(last) if (last.0 - point.0).hypot(last.1 - point.1) < 1e-9 => *last = point,
+                    _ => vertices.push(point),
+                }
+            }
+        }
+        let (first, last) = (vertices[0], vertices[vertices.len() - 1]);
+        if vertices.len() > 1 && (first.0 - last.0).hypot(first.1 - last.1) < 1e-9 {
+            vertices.pop();
+        }
+        vertices
+    }
+}
+
+/// Outside of the tub shell in plan
+pub fn hot_tub_shell() -> TubOutline {
+    let (x, y) = hot_tub_centre();
+    let (length, width) = HOT_TUB_SIZE;
+    let radius = match HOT_TUB_SHAPE {
+        HotTubShape::Round => HOT_TUB_DIAMETER / 2.0,
+        HotTubShape::Square | HotTubShape::Rectangular => HOT_TUB_CORNER_RADIUS,
+    };
+    TubOutline { x, y, half_length: length / 2.0, half_width: width / 2.0, radius }
+}
+
+/// Water line, HOT_TUB_WALL inside the shell
+pub fn hot_tub_water_line() -> TubOutline {
+    hot_tub_shell().inset(HOT_TUB_WALL)
+}
+
+/// Water to the fill depth, in m³. The seat ledges are not taken off: they
+/// are moulded into the shell and their volume is not listed anywhere, so the
+/// fill and the water load err on the heavy side.
+pub fn hot_tub_water_volume() -> f64 {
+    hot_tub_water_line().area() * HOT_TUB_WATER_DEPTH
+}
+
+/// Shape and size as printed on the sheet
+pub fn hot_tub_size() -> String {
+    let (length, width) = HOT_TUB_SIZE;
+    match HOT_TUB_SHAPE {
+        HotTubShape::Round => format!("ROUND Ø{:.2} m", length),
+        HotTubShape::Square => format!("SQUARE {:.2} x {:.2} m", length, width),
+        HotTubShape::Rectangular => format!("RECTANGULAR {:.2} x {:.2} m", length, width),
+    }
+}
+
+/// Corners and seats that do not fit the shell; empty if they do
+pub fn hot_tub_problems() -> Vec<String> {
+    let mut problems = Vec::new();
+    let (length, width) = HOT_TUB_SIZE;
+    if HOT_TUB_SHAPE != HotTubShape::Round && 2.0 * HOT_TUB_CORNER_RADIUS > length.min(width) {
+        problems.push(format!(
+            "HOT TUB CORNER RADIUS {:.2} m IS MORE THAN HALF THE {:.2} m SIDE",
+            HOT_TUB_CORNER_RADIUS,
+            length.min(width)
+        ));
+    }
+    let water = hot_tub_water_line();
+    let seating = HOT_TUB_SEATS as f64 * HOT_TUB_SEAT_WIDTH;
+    if seating > water.perimeter() {
+        problems.push(format!(
+            "{} HOT TUB SEATS NEED {:.2} m OF SHELL, THE WATER LINE IS {:.2} m",
+            HOT_TUB_SEATS,
+            seating,
+            water.perimeter()
+        ));
+    }
+    if HOT_TUB_SEAT_DEPTH >= water.half_length.min(water.half_width) {
+        problems.push(format!("HOT TUB SEATS {:.2} m DEEP MEET IN THE MIDDLE", HOT_TUB_SEAT_DEPTH));
+    }
+    problems
+}
+
+fn add_outline(drawing: &mut Drawing, layer: &str, vertices: Vec<(f64, f64, f64)>) {
+    let mut polyline = LwPolyline::default();
+    polyline.set_is_closed(true);
+    for (x, y, bulge) in vertices {
+        polyline.vertices.push(LwPolylineVertex { x, y, bulge, ..Default::default() });
+    }
+    let mut entity = Entity::new(EntityType::LwPolyline(polyline));
+    entity.common.layer = layer.to_string();
+    drawing.add_entity(entity);
+}
+
+/// Shell outline for the plans that show the tub for reference, each point
+/// mapped through `place` (a move, or a move and turn, which keeps the corner
+/// arcs)
+pub fn draw_hot_tub_outline(drawing: &mut Drawing, place: impl Fn(f64, f64) -> (f64, f64)) {
+    let vertices = hot_tub_shell()
+        .vertices()
+        .into_iter()
+        .map(|(x, y, bulge)| {
+            let (x, y) = place(x, y);
+            (x, y, bulge)
+        })
+        .collect();
+    add_outline(drawing, LAYER_HOT_TUB, vertices);
+}
+
+/// Hot tub in the floor plan: shell, water line, seats, jets, steps and the
+/// cover outline
+pub fn draw_hot_tub_plan(drawing: &mut Drawing) {
+    let shell = hot_tub_shell();
+    let water = hot_tub_water_line();
+    add_outline(drawing, LAYER_HOT_TUB, shell.vertices());
+    add_outline(drawing, LAYER_HOT_TUB, water.vertices());
+    add_outline(drawing, LAYER_HOT_TUB, shell.inset(-HOT_TUB_COVER_OVERHANG).vertices());
+
+    // Seat ledges inside the water line, spaced evenly round it: the back
+    // follows the water line, the front the line HOT_TUB_SEAT_DEPTH inside
+    // it, so a seat round a corner gets a mitred front
+    let front = water.inset(HOT_TUB_SEAT_DEPTH);
+    let half = HOT_TUB_SEAT_WIDTH / 2.0 / water.perimeter();
+    for i in 0..HOT_TUB_SEATS {
+        let t = (i as f64 + 0.5) / HOT_TUB_SEATS as f64;
+        let back: Vec<(f64, f64)> = (0..=SEAT_POINTS).map(|k| water.point_at(t - half + 2.0 * half * k as f64 / SEAT_POINTS as f64).0).collect();
+        let mut points = back.clone();
+        points.extend(back.iter().rev().map(|&(x, y)| front.nearest(x, y)));
+        points.dedup_by(|a, b| (a.0 - b.0).hypot(a.1 - b.1) < 1e-9);
+        add_polygon(drawing, LAYER_HOT_TUB, &points);
+    }
+
+    // Jets in the shell wall just below the water line
+    for &t in HOT_TUB_JETS {
+        let ((x, y), (nx, ny)) = water.point_at(t);
+        add_circle(drawing, LAYER_HOT_TUB, x - nx * 0.05, y - ny * 0.05, 0.03);
+    }
+
+    // Steps outside the shell, the top one against it
+    let ((x, y), (nx, ny)) = shell.point_at(HOT_TUB_STEP_AT);
+    let (tx, ty) = (-ny * HOT_TUB_STEP_WIDTH / 2.0, nx * HOT_TUB_STEP_WIDTH / 2.0);
+    for step in 0..HOT_TUB_STEPS {
+        let (near, far) = (step as f64 * HOT_TUB_STEP_TREAD, (step + 1) as f64 * HOT_TUB_STEP_TREAD);
+        let (ax, ay) = (x + nx * near, y + ny * near);
+        let (bx, by) = (x + nx * far, y + ny * far);
+        add_polygon(drawing, LAYER_HOT_TUB, &[(ax - tx, ay - ty), (ax + tx, ay + ty), (bx + tx, by + ty), (bx - tx, by - ty)]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outline(length: f64, width: f64, radius: f64) -> TubOutline {
+        TubOutline { x: 3.0, y: -1.0, half_length: length / 2.0, half_width: width / 2.0, radius }
+    }
+
+    /// Length and area of the closed polyline through the vertices, each arc
+    /// taken from its bulge
+    fn traced(vertices: &[(f64, f64, f64)]) -> (f64, f64) {
+        let (mut length, mut area) = (0.0, 0.0);
+        for (i, &(x1, y1, bulge)) in vertices.iter().enumerate() {
+            let (x2, y2, _) = vertices[(i + 1) % vertices.len()];
+            let chord = (x2 - x1).hypot(y2 - y1);
+            area += (x1 * y2 - x2 * y1) / 2.0;
+            if bulge == 0.0 {
+                length += chord;
+            } else {
+                let angle = 4.0 * bulge.atan();
+                let radius = chord / 2.0 / (angle / 2.0).sin();
+                length += radius * angle;
+                area += radius * radius * (angle - angle.sin()) / 2.0;
+            }
+        }
+        (length, area)
+    }
+
+    fn assert_consistent(tub: TubOutline) {
+        let vertices = tub.vertices();
+        let (first, last) = (vertices[0], vertices[vertices.len() - 1]);
+        assert!((first.0 - last.0).hypot(first.1 - last.1) > 1e-9, "closing vertex repeated");
+        let (length, area) = traced(&vertices);
+        assert!((length - tub.perimeter()).abs() < 1e-9, "traced {} against {}", length, tub.perimeter());
+        assert!((area - tub.area()).abs() < 1e-9, "traced {} against {}", area, tub.area());
+        assert!((tub.point_at(0.0).0.0 - tub.point_at(1.0).0.0).abs() < 1e-9);
+        assert!((tub.point_at(0.0).0.1 - tub.point_at(1.0).0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn round_outline() {
+        let tub = outline(2.0, 2.0, 1.0);
+        assert!((tub.area() - PI).abs() < 1e-9);
+        assert!((tub.perimeter() - 2.0 * PI).abs() < 1e-9);
+        assert_eq!(tub.vertices().len(), 4);
+        assert_consistent(tub);
+    }
+
+    #[test]
+    fn square_outline() {
+        let tub = outline(2.0, 2.0, 0.3);
+        assert!((tub.area() - (4.0 - (4.0 - PI) * 0.09)).abs() < 1e-9);
+        assert!((tub.perimeter() - (4.0 * (2.0 - 0.6) + 0.6 * PI)).abs() < 1e-9);
+        assert_eq!(tub.vertices().len(), 8);
+        assert_consistent(tub);
+    }
+
+    #[test]
+    fn rectangular_outline() {
+        let tub = outline(2.3, 2.0, 0.3);
+        assert!((tub.area() - (4.6 - (4.0 - PI) * 0.09)).abs() < 1e-9);
+        assert!((tub.perimeter() - (2.0 * (2.3 + 2.0) - 8.0 * 0.3 + 0.6 * PI)).abs() < 1e-9);
+        assert_consistent(tub);
+
+        // Sharp corners, and a water line inset past the corner radius
+        assert_consistent(outline(2.3, 2.0, 0.0));
+        let water = tub.inset(0.4);
+        assert_eq!(water.radius, 0.0);
+        assert!((water.area() - 1.5 * 1.2).abs() < 1e-9);
+        assert_consistent(water);
+    }
+}
@@ -0,0 +1,416 @@
+use std::fs;
+use std::io;
+
+use crate::constants::*;
+use crate::model::*;
+
+// ============================================================================
+// IFC4 EXPORT (STEP physical file, ISO 10303-21)
+// Every element is an extruded rectangle or circle placed relative to the
+// ground floor storey, which sits at the deck top (floor level).
+// ============================================================================
+
+const DOOR_PANEL_THICKNESS: f64 = 0.05;
+const WINDOW_PANEL_THICKNESS: f64 = 0.07;
+
+/// Format a REAL the way STEP expects it (always with a decimal point)
+fn real(value: f64) -> String {
+    let value = if value.abs() < 1e-9 { 0.0 } else { value };
+    let text = format!("{:.6}", value);
+    let text = text.trim_end_matches('0');
+    text.to_string()
+}
+
+/// Quote a STRING, doubling embedded apostrophes
+fn string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Deterministic 22 character IFC GlobalId derived from an element key
+fn global_id(key: &str) -> String {
+    const ALPHABET: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz_$";
+
+    // Two FNV-1a passes with different offsets give 128 bits
+    let fnv = |offset: u64| {
+        key.bytes()
+            .fold(offset, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+    };
+    let value = ((fnv(0xcbf29ce484222325) as u128) << 64) | fnv(0x84222325cbf29ce4) as u128;
+
+    // First character carries 2 bits, the remaining 21 carry 6 bits each
+    let mut id = String::with_capacity(22);
+    id.push(ALPHABET[(value >> 126) as usize] as char);
+    for i in (0..21).rev() {
+        id.push(ALPHABET[((value >> (i * 6)) & 0x3f) as usize] as char);
+    }
+    id
+}
+
+/// Accumulates numbered STEP entity instances
+struct IfcWriter {
+    lines: Vec<String>,
+    body_context: String,
+}
+
+impl IfcWriter {
+    /// Append an entity instance and return its reference (e.g. "#12")
+    fn add(&mut self, entity: String) -> String {
+        let reference = format!("#{}", self.lines.len() + 1);
+        self.lines.push(format!("{}={};", reference, entity));
+        reference
+    }
+
+    fn point(&mut self, x: f64, y: f64, z: f64) -> String {
+        self.add(format!("IFCCARTESIANPOINT(({},{},{}))", real(x), real(y), real(z)))
+    }
+
+    fn direction(&mut self, x: f64, y: f64, z: f64) -> String {
+        self.add(format!("IFCDIRECTION(({},{},{}))", real(x), real(y), real(z)))
+    }
+
+    /// Local placement at (x, y, z) relative to another placement
+    fn placement(&mut self, relative_to: Option<&str>, x: f64, y: f64, z: f64) -> String {
+        let location = self.point(x, y, z);
+        let axis = self.add(format!("IFCAXIS2PLACEMENT3D({},$,$)", location));
+        self.add(format!(
+            "IFCLOCALPLACEMENT({},{})",
+            relative_to.unwrap_or("$"),
+            axis
+        ))
+    }
+
+    /// Body representation extruded upwards from a 2D profile
+    fn extrusion(&mut self, profile: String, depth: f64) -> String {
+        let profile = self.add(profile);
+        let origin = self.point(0.0, 0.0, 0.0);
+        let position = self.add(format!("IFCAXIS2PLACEMENT3D({},$,$)", origin));
+        let up = self.direction(0.0, 0.0, 1.0);
+        let solid = self.add(format!(
+            "IFCEXTRUDEDAREASOLID({},{},{},{})",
+            profile,
+            position,
+            up,
+            real(depth)
+        ));
+        let representation = self.add(format!(
+            "IFCSHAPEREPRESENTATION({},'Body','SweptSolid',({}))",
+            self.body_context, solid
+        ));
+        self.add(format!("IFCPRODUCTDEFINITIONSHAPE($,$,({}))", representation))
+    }
+
+    /// Box of dx × dy × dz whose plan rectangle starts at (x, y)
+    fn box_shape(&mut self, x: f64, y: f64, dx: f64, dy: f64, dz: f64) -> String {
+        let center = self.add(format!("IFCCARTESIANPOINT(({},{}))", real(x + dx / 2.0), real(y + dy / 2.0)));
+        let position = self.add(format!("IFCAXIS2PLACEMENT2D({},$)", center));
+        self.extrusion(
+            format!("IFCRECTANGLEPROFILEDEF(.AREA.,$,{},{},{})", position, real(dx), real(dy)),
+            dz,
+        )
+    }
+
+    /// Cylinder of the given radius and height centred on (x, y)
+    fn cylinder_shape(&mut self, x: f64, y: f64, radius: f64, dz: f64) -> String {
+        let center = self.add(format!("IFCCARTESIANPOINT(({},{}))", real(x), real(y)));
+        let position = self.add(format!("IFCAXIS2PLACEMENT2D({},$)", center));
+        self.extrusion(
+            format!("IFCCIRCLEPROFILEDEF(.AREA.,$,{},{})", position, real(radius)),
+            dz,
+        )
+    }
+
+    fn aggregate(&mut self, key: &str, parent: &str, children: &[String]) {
+        if !children.is_empty() {
+            self.add(format!(
+                "IFCRELAGGREGATES({},$,$,$,{},({}))",
+                string(&global_id(key)),
+                parent,
+                children.join(",")
+            ));
+        }
+    }
+}
+
+/// Build the IFC4 STEP text for the building model
+pub fn ifc_text(model: &BuildingModel, file_name: &str) -> String {
+    let mut ifc = IfcWriter { lines: Vec::new(), body_context: String::new() };
+
+    // =========================================================================
+    // UNITS AND REPRESENTATION CONTEXT
+    // =========================================================================
+
+    let length = ifc.add("IFCSIUNIT(*,.LENGTHUNIT.,$,.METRE.)".to_string());
+    let area = ifc.add("IFCSIUNIT(*,.AREAUNIT.,$,.SQUARE_METRE.)".to_string());
+    let volume = ifc.add("IFCSIUNIT(*,.VOLUMEUNIT.,$,.CUBIC_METRE.)".to_string());
+    let angle = ifc.add("IFCSIUNIT(*,.PLANEANGLEUNIT.,$,.RADIAN.)".to_string());
+    let units = ifc.add(format!("IFCUNITASSIGNMENT(({},{},{},{}))", length, area, volume, angle));
+
+    let origin = ifc.point(0.0, 0.0, 0.0);
+    let world = ifc.add(format!("IFCAXIS2PLACEMENT3D({},$,$)", origin));
+    let context = ifc.add(format!(
+        "IFCGEOMETRICREPRESENTATIONCONTEXT($,'Model',3,1.E-05,{},$)",
+        world
+    ));
+    ifc.body_context = ifc.add(format!(
+        "IFCGEOMETRICREPRESENTATIONSUBCONTEXT('Body','Model',*,*,*,*,{},$,.MODEL_VIEW.,$)",
+        context
+    ));
+
+    // =========================================================================
+    // SPATIAL STRUCTURE (project > site > building > storey > spaces)
+    // =========================================================================
+
+    let project = ifc.add(format!(
+        "IFCPROJECT({},$,'Sauna Building',$,$,$,$,({}),{})",
+        string(&global_id("project")),
+        context,
+        units
+    ));
+
+    let site_placement = ifc.placement(None, 0.0, 0.0, 0.0);
+    let site = ifc.add(format!(
+        "IFCSITE({},$,'Site',$,$,{},$,$,.ELEMENT.,$,$,$,$,$)",
+        string(&global_id("site")),
+        site_placement
+    ));
+
+    let building_placement = ifc.placement(Some(&site_placement), 0.0, 0.0, 0.0);
+    let building = ifc.add(format!(
+        "IFCBUILDING({},$,'Sauna Building',$,$,{},$,$,.ELEMENT.,$,$,$)",
+        string(&global_id("building")),
+        building_placement
+    ));
+
+    let storey_placement = ifc.placement(Some(&building_placement), 0.0, 0.0, floor_level());
+    let storey = ifc.add(format!(
+        "IFCBUILDINGSTOREY({},$,'Ground Floor',$,$,{},$,$,.ELEMENT.,{})",
+        string(&global_id("storey")),
+        storey_placement,
+        real(floor_level())
+    ));
+
+    ifc.aggregate("project-site", &project, std::slice::from_ref(&site));
+    ifc.aggregate("site-building", &site, std::slice::from_ref(&building));
+    ifc.aggregate("building-storey", &building, std::slice::from_ref(&storey));
+
+    let mut spaces = Vec::new();
+    for room in &model.rooms {
+        let placement = ifc.placement(Some(&storey_placement), room.x1, room.y1, 0.0);
+        let shape = ifc.box_shape(0.0, 0.0, room.x2 - room.x1, room.y2 - room.y1, BUILDING_HEIGHT);
+        spaces.push(ifc.add(format!(
+            "IFCSPACE({},$,{},$,$,{},{},{},.ELEMENT.,{},$)",
+            string(&global_id(&format!("space-{}", room.name))),
+            string(room.name),
+            placement,
+            shape,
+            string(&format!("{} ({:.1} m2)", room.name, room.area())),
+            if room.interior { ".INTERNAL." } else { ".EXTERNAL." }
+        )));
+    }
+    ifc.aggregate("storey-spaces", &storey, &spaces);
+
+    // =========================================================================
+    // WALLS WITH OPENINGS, DOORS AND WINDOWS
+    // =========================================================================
+
+    let mut elements = Vec::new();
+    let wall_height = roof_level() - floor_level();
+
+    for wall in &model.walls {
+        let placement = ifc.placement(Some(&storey_placement), wall.x1, wall.y1, 0.0);
+        let shape = ifc.box_shape(0.0, 0.0, wall.x2 - wall.x1, wall.y2 - wall.y1, wall_height);
+        let ifc_wall = ifc.add(format!(
+            "IFCWALL({},$,{},{},$,{},{},$,.{}.)",
+            string(&global_id(wall.id)),
+            string(wall.id),
+            string(if wall.exterior { "Exterior wall" } else { "Interior partition" }),
+            placement,
+            shape,
+            if wall.exterior { "SOLIDWALL" } else { "PARTITIONING" }
+        ));
+        elements.push(ifc_wall.clone());
+
+        for opening in &wall.openings {
+            // Opening and filling boxes in wall coordinates
+            let thickness = wall.thickness();
+            let panel = match opening.kind {
+                OpeningKind::Door => DOOR_PANEL_THICKNESS,
+                OpeningKind::Window => WINDOW_PANEL_THICKNESS,
+            };
+            let (ox, oy, dx, dy, px, py, pdx, pdy) = if wall.is_horizontal() {
+                (
+                    opening.offset,
+                    0.0,
+                    opening.width,
+                    thickness,
+                    0.0,
+                    (thickness - panel) / 2.0,
+                    opening.width,
+                    panel,
+                )
+            } else {
+                (
+                    0.0,
+                    opening.offset,
+                    thickness,
+                    opening.width,
+                    (thickness - panel) / 2.0,
+                    0.0,
+                    panel,
+                    opening.width,
+                )
+            };
+
+            let opening_placement = ifc.placement(Some(&placement), ox, oy, opening.sill);
+            let opening_shape = ifc.box_shape(0.0, 0.0, dx, dy, opening.height);
+            let ifc_opening = ifc.add(format!(
+                "IFCOPENINGELEMENT({},$,{},$,$,{},{},$,.OPENING.)",
+                string(&global_id(&format!("opening-{}", opening.id))),
+                string(&format!("Opening {}", opening.id)),
+                opening_placement,
+                opening_shape
+            ));
+            ifc.add(format!(
+                "IFCRELVOIDSELEMENT({},$,$,$,{},{})",
+                string(&global_id(&format!("voids-{}", opening.id))),
+                ifc_wall,
+                ifc_opening
+            ));
+
+            let filling_placement = ifc.placement(Some(&opening_placement), 0.0, 0.0, 0.0);
+            let filling_shape = ifc.box_shape(px, py, pdx, pdy, opening.height);
+            let filling = match opening.kind {
+                OpeningKind::Door => ifc.add(format!(
+                    "IFCDOOR({},$,{},$,$,{},{},$,{},{},.DOOR.,.SINGLE_SWING_LEFT.,$)",
+                    string(&global_id(opening.id)),
+                    string(opening.id),
+                    filling_placement,
+                    filling_shape,
+                    real(opening.height),
+                    real(opening.width)
+                )),
+                OpeningKind::Window => ifc.add(format!(
+                    "IFCWINDOW({},$,{},$,$,{},{},$,{},{},.WINDOW.,.SINGLE_PANEL.,$)",
+                    string(&global_id(opening.id)),
+                    string(opening.id),
+                    filling_placement,
+                    filling_shape,
+                    real(opening.height),
+                    real(opening.width)
+                )),
+            };
+            ifc.add(format!(
+                "IFCRELFILLSELEMENT({},$,$,$,{},{})",
+                string(&global_id(&format!("fills-{}", opening.id))),
+                ifc_opening,
+                filling
+            ));
+            elements.push(filling);
+        }
+    }
+
+    // =========================================================================
+    // POSTS AND SLABS
+    // =========================================================================
+
+    for (i, post) in model.posts.iter().enumerate() {
+        let id = format!("post-{}", i + 1);
+        let placement = ifc.placement(Some(&storey_placement), post.min.x, post.min.y, 0.0);
+        let shape = ifc.box_shape(
+            0.0,
+            0.0,
+            post.max.x - post.min.x,
+            post.max.y - post.min.y,
+            post.max.z - post.min.z,
+        );
+        elements.push(ifc.add(format!(
+            "IFCCOLUMN({},$,{},$,$,{},{},$,.COLUMN.)",
+            string(&global_id(&id)),
+            string(&id),
+            placement,
+            shape
+        )));
+    }
+
+    for (id, slab, predefined) in [("deck", &model.deck, "FLOOR"), ("roof", &model.roof, "ROOF")] {
+        let placement = ifc.placement(
+            Some(&storey_placement),
+            slab.min.x,
+            slab.min.y,
+            slab.min.z - floor_level(),
+        );
+        let shape = ifc.box_shape(
+            0.0,
+            0.0,
+            slab.max.x - slab.min.x,
+            slab.max.y - slab.min.y,
+            slab.max.z - slab.min.z,
+        );
+        elements.push(ifc.add(format!(
+            "IFCSLAB({},$,{},$,$,{},{},$,.{}.)",
+            string(&global_id(id)),
+            string(id),
+            placement,
+            shape,
+            predefined
+        )));
+    }
+
+    // =========================================================================
+    // FURNISHINGS (benches and heater)
+    // =========================================================================
+
+    for furnishing in &model.furnishings {
+        let shape = match furnishing.footprint {
+            Footprint::Rect { x1, y1, x2, y2 } => {
+                ifc.box_shape(x1, y1, x2 - x1, y2 - y1, furnishing.height)
+            }
+            Footprint::Circle { x, y, radius } => ifc.cylinder_shape(x, y, radius, furnishing.height),
+        };
+        let placement = ifc.placement(Some(&storey_placement), 0.0, 0.0, 0.0);
+        elements.push(ifc.add(format!(
+            "IFCFURNISHINGELEMENT({},$,{},$,$,{},{},$)",
+            string(&global_id(&furnishing.id)),
+            string(&furnishing.id),
+            placement,
+            shape
+        )));
+    }
+
+    ifc.add(format!(
+        "IFCRELCONTAINEDINSPATIALSTRUCTURE({},$,$,$,({}),{})",
+        string(&global_id("storey-contents")),
+        elements.join(","),
+        storey
+    ));
+
+    // =========================================================================
+    // STEP FILE
+    // =========================================================================
+
+    let mut text = String::new();
+    text.push_str("ISO-10303-21;\n");
+    text.push_str("HEADER;\n");
+    text.push_str("FILE_DESCRIPTION(('ViewDefinition [DesignTransferView]'),'2;1');\n");
+    text.push_str(&format!(
+        "FILE_NAME({},{},(''),(''),'sauna','sauna {}','');\n",
+        string(file_name),
+        string(&format!("{}T00:00:00", current_revision().date)),
+        env!("CARGO_PKG_VERSION")
+    ));
+    text.push_str("FILE_SCHEMA(('IFC4'));\n");
+    text.push_str("ENDSEC;\n");
+    text.push_str("DATA;\n");
+    for line in &ifc.lines {
+        text.push_str(line);
+        text.push('\n');
+    }
+    text.push_str("ENDSEC;\n");
+    text.push_str("END-ISO-10303-21;\n");
+    text
+}
+
+/// Write the building model as an IFC4 file
+pub fn save_ifc(model: &BuildingModel, path: &str) -> io::Result<()> {
+    fs::write(path, ifc_text(model, path))
+}
@@ -1,6 +1,9 @@
 use dxf::tables::Layer;
 use dxf::{Color, Drawing};
 
+use crate::constants::SUN_STUDY_TIMES;
+use crate::sun::clock;
+
 // Layer names following AIA naming convention
 pub const LAYER_WALLS: &str = "A-WALL";
 pub const LAYER_WALLS_INNER: &str = "A-WALL-INTR";
@@ -17,6 +20,58 @@ pub const LAYER_TITLE: &str = "A-ANNO-TTLB";
 pub const LAYER_ELEV: &str = "A-ELEV";
 pub const LAYER_SECTION: &str = "A-SECT";
 pub const LAYER_ROOF: &str = "A-ROOF";
+pub const LAYER_ISO: &str = "A-ELEV-ISOM";
+pub const LAYER_WALL_LAYERS: &str = "A-WALL-PATT";
+pub const LAYER_ROOF_LAYERS: &str = "A-ROOF-PATT";
+pub const LAYER_FLOOR_LAYERS: &str = "A-FLOR-PATT";
+pub const LAYER_CEILING: &str = "A-CLNG";
+pub const LAYER_CEILING_PATTERN: &str = "A-CLNG-PATT";
+pub const LAYER_STAIRS: &str = "A-FLOR-STRS";
+pub const LAYER_RAILINGS: &str = "A-FLOR-HRAL";
+
+// Mechanical layers (sauna ventilation)
+pub const LAYER_VENTS: &str = "M-HVAC-VENT";
+
+// Electrical layers (electrical plan)
+pub const LAYER_ELEC_POWER: &str = "E-POWR";
+pub const LAYER_ELEC_LIGHTING: &str = "E-LITE";
+pub const LAYER_ELEC_ANNO: &str = "E-ANNO";
+
+// Plumbing layers (plumbing plan)
+pub const LAYER_PLUMB_SUPPLY: &str = "P-DOMW";
+pub const LAYER_PLUMB_DRAIN: &str = "P-SANR";
+pub const LAYER_PLUMB_VENT: &str = "P-VENT";
+pub const LAYER_PLUMB_FIXTURES: &str = "P-FIXT";
+pub const LAYER_PLUMB_ANNO: &str = "P-ANNO";
+
+// Civil layers (site plan)
+pub const LAYER_PROPERTY: &str = "C-PROP";
+pub const LAYER_SETBACK: &str = "C-PROP-SETB";
+
+// Shadow study: one layer per study time, A-SHAD-HHMM
+pub fn layer_shadow(time: f64) -> String {
+    format!("A-SHAD-{}", clock(time).replace(':', ""))
+}
+
+// Survey underlay layers: V- and the surveyor's layer name
+pub const LAYER_SURVEY_PREFIX: &str = "V-";
+
+// Structural layers (deck framing plan)
+pub const LAYER_JOISTS: &str = "S-JOIS";
+pub const LAYER_BEAMS: &str = "S-BEAM";
+pub const LAYER_FOOTINGS: &str = "S-FNDN";
+pub const LAYER_WALL_FRAMING: &str = "S-WALL-FRMG";
+
+// 3D model layers (3D DXF mode only)
+pub const LAYER_MODEL_WALLS: &str = "A-MODL-WALL";
+pub const LAYER_MODEL_DECK: &str = "A-MODL-DECK";
+pub const LAYER_MODEL_ROOF: &str = "A-MODL-ROOF";
+
+// Revision overlay layers (diff output and revision clouds)
+pub const LAYER_REV_CLOUD: &str = "A-ANNO-REVS";
+pub const LAYER_DIFF_EXISTING: &str = "A-DIFF-EXST";
+pub const LAYER_DIFF_NEW: &str = "A-DIFF-NEWW";
+pub const LAYER_DIFF_REMOVED: &str = "A-DIFF-DEMO";
 
 fn create_layer(name: &str, color_index: u8) -> Layer {
     Layer {
@@ -71,4 +126,111 @@ pub fn setup_layers(drawing: &mut Drawing) {
 
     // Roof (Red)
     drawing.add_layer(create_layer(LAYER_ROOF, 1));
+
+    // Isometric presentation view (White)
+    drawing.add_layer(create_layer(LAYER_ISO, 7));
+
+    // Assembly build-up lines in plan and section (Gray)
+    drawing.add_layer(create_layer(LAYER_WALL_LAYERS, 8));
+    drawing.add_layer(create_layer(LAYER_ROOF_LAYERS, 8));
+    drawing.add_layer(create_layer(LAYER_FLOOR_LAYERS, 8));
+
+    // Reflected ceiling plan: roof edge (White), panelling joints (Gray)
+    drawing.add_layer(create_layer(LAYER_CEILING, 7));
+    drawing.add_layer(create_layer(LAYER_CEILING_PATTERN, 8));
+
+    // Deck stairs and stringers (Brown)
+    drawing.add_layer(create_layer(LAYER_STAIRS, 30));
+
+    // Deck railings (Orange)
+    drawing.add_layer(create_layer(LAYER_RAILINGS, 40));
+
+    // Ventilation grilles and airflow (Blue)
+    drawing.add_layer(create_layer(LAYER_VENTS, 150));
+
+    // Electrical power devices (Red), lighting and switching (Yellow), tags (White)
+    drawing.add_layer(create_layer(LAYER_ELEC_POWER, 1));
+    drawing.add_layer(create_layer(LAYER_ELEC_LIGHTING, 2));
+    drawing.add_layer(create_layer(LAYER_ELEC_ANNO, 7));
+
+    // Plumbing supply (Blue), drains (Brown), vents (Green), fixtures (Cyan), tags (White)
+    drawing.add_layer(create_layer(LAYER_PLUMB_SUPPLY, 5));
+    drawing.add_layer(create_layer(LAYER_PLUMB_DRAIN, 30));
+    drawing.add_layer(create_layer(LAYER_PLUMB_VENT, 3));
+    drawing.add_layer(create_layer(LAYER_PLUMB_FIXTURES, 4));
+    drawing.add_layer(create_layer(LAYER_PLUMB_ANNO, 7));
+
+    // Revision clouds (Magenta)
+    drawing.add_layer(create_layer(LAYER_REV_CLOUD, 6));
+
+    // Shadows, one colour per study time
+    for (i, &time) in SUN_STUDY_TIMES.iter().enumerate() {
+        drawing.add_layer(create_layer(&layer_shadow(time), [30, 40, 50, 140, 150, 170][i % 6]));
+    }
+
+    // Lot lines (Magenta) and setback lines (Red)
+    drawing.add_layer(create_layer(LAYER_PROPERTY, 6));
+    drawing.add_layer(create_layer(LAYER_SETBACK, 1));
+
+    // Deck joists (Brown)
+    drawing.add_layer(create_layer(LAYER_JOISTS, 30));
+
+    // Deck beams (Blue)
+    drawing.add_layer(create_layer(LAYER_BEAMS, 5));
+
+    // Footings and piers (Gray)
+    drawing.add_layer(create_layer(LAYER_FOOTINGS, 8));
+
+    // Wall framing elevations (Orange)
+    drawing.add_layer(create_layer(LAYER_WALL_FRAMING, 40));
+}
+
+/// Underlay layer for one of the surveyor's layers (Gray, so it reads as background)
+pub fn setup_survey_layer(drawing: &mut Drawing, name: &str) {
+    drawing.add_layer(create_layer(name, 8));
+}
+
+pub fn setup_model_layers(drawing: &mut Drawing) {
+    // 3D walls and posts (White)
+    drawing.add_layer(create_layer(LAYER_MODEL_WALLS, 7));
+
+    // 3D deck platform (Brown)
+    drawing.add_layer(create_layer(LAYER_MODEL_DECK, 30));
+
+    // 3D roof slab (Red)
+    drawing.add_layer(create_layer(LAYER_MODEL_ROOF, 1));
+}
+
+pub fn setup_diff_layers(drawing: &mut Drawing) {
+    // Unchanged elements, for context (Gray)
+    drawing.add_layer(create_layer(LAYER_DIFF_EXISTING, 8));
+
+    // Added elements and the new state of changed ones (Green)
+    drawing.add_layer(create_layer(LAYER_DIFF_NEW, 3));
+
+    // Removed elements and the old state of changed ones (Red)
+    drawing.add_layer(create_layer(LAYER_DIFF_REMOVED, 1));
+
+    // Assembly build-up lines in plan and section (Gray)
+    drawing.add_layer(create_layer(LAYER_WALL_LAYERS, 8));
+    drawing.add_layer(create_layer(LAYER_ROOF_LAYERS, 8));
+    drawing.add_layer(create_layer(LAYER_FLOOR_LAYERS, 8));
+
+    // Ventilation grilles and airflow (Blue)
+    drawing.add_layer(create_layer(LAYER_VENTS, 150));
+
+    // Electrical power devices (Red), lighting and switching (Yellow), tags (White)
+    drawing.add_layer(create_layer(LAYER_ELEC_POWER, 1));
+    drawing.add_layer(create_layer(LAYER_ELEC_LIGHTING, 2));
+    drawing.add_layer(create_layer(LAYER_ELEC_ANNO, 7));
+
+    // Plumbing supply (Blue), drains (Brown), vents (Green), fixtures (Cyan), tags (White)
+    drawing.add_layer(create_layer(LAYER_PLUMB_SUPPLY, 5));
+    drawing.add_layer(create_layer(LAYER_PLUMB_DRAIN, 30));
+    drawing.add_layer(create_layer(LAYER_PLUMB_VENT, 3));
+    drawing.add_layer(create_layer(LAYER_PLUMB_FIXTURES, 4));
+    drawing.add_layer(create_layer(LAYER_PLUMB_ANNO, 7));
+
+    // Revision clouds (Magenta)
+    drawing.add_layer(create_layer(LAYER_REV_CLOUD, 6));
 }
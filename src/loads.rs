@@ -0,0 +1,150 @@
+use crate::constants::*;
+use crate::hot_tub::{hot_tub_problems, hot_tub_shell, hot_tub_size, hot_tub_water_volume};
+use crate::model::BuildingModel;
+
+// ============================================================================
+// HOT TUB LOAD CHECK
+// Filled weight of the tub, its area load, and how much of its footprint
+// bears on the deck, on a concrete pad or on nothing at all.
+// ============================================================================
+
+const GRAVITY: f64 = 9.81;
+const WATER_DENSITY: f64 = 1000.0; // kg/m³
+
+/// Strips used to integrate the footprint over the supports
+const SUPPORT_STRIPS: usize = 2000;
+
+pub struct HotTubLoad {
+    pub water_volume: f64,  // m³
+    pub water_mass: f64,    // kg
+    pub occupant_mass: f64, // kg
+    pub total_mass: f64,    // kg
+    pub footprint: f64,     // m²
+    pub area_load: f64,     // kN/m²
+    pub on_deck: f64,       // Fraction of the footprint
+    pub on_slab: f64,       // Fraction of the footprint
+    pub unsupported: f64,   // Fraction of the footprint
+}
+
+/// Hot tub centre in plan
+pub fn hot_tub_centre() -> (f64, f64) {
+    (TOTAL_WIDTH - HOT_TUB_OFFSET_X, -HOT_TUB_SIZE.1 / 2.0 + HOT_TUB_OFFSET_Y)
+}
+
+/// Filled mass in kg: water to the fill depth, shell and occupants
+pub fn hot_tub_filled_mass() -> f64 {
+    hot_tub_water_volume() * WATER_DENSITY + HOT_TUB_SHELL_MASS + HOT_TUB_SEATS as f64 * OCCUPANT_MASS
+}
+
+/// Filled tub spread over its footprint, in kN/m²
+pub fn hot_tub_area_load() -> f64 {
+    hot_tub_filled_mass() * GRAVITY / 1000.0 / hot_tub_shell().area()
+}
+
+/// Area of the tub footprint covered by any of the rectangles (x1, y1, x2, y2)
+fn covered_area(rects: &[(f64, f64, f64, f64)]) -> f64 {
+    let shell = hot_tub_shell();
+    let dx = 2.0 * shell.half_length / SUPPORT_STRIPS as f64;
+    let mut area = 0.0;
+    for i in 0..SUPPORT_STRIPS {
+        let x = shell.x - shell.half_length + dx * (i as f64 + 0.5);
+        let half = shell.half_chord(x);
+        let (low, high) = (shell.y - half, shell.y + half);
+
+        // Union of the rectangles crossing this strip, clipped to the chord
+        let mut spans: Vec<(f64, f64)> = rects
+            .iter()
+            .filter(|rect| rect.0 <= x && x <= rect.2)
+            .map(|rect| (rect.1.max(low), rect.3.min(high)))
+            .filter(|span| span.1 > span.0)
+            .collect();
+        spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut covered = 0.0;
+        let mut reach = f64::NEG_INFINITY;
+        for (start, end) in spans {
+            let start = start.max(reach);
+            if end > start {
+                covered += end - start;
+            }
+            reach = reach.max(end);
+        }
+        area += covered * dx;
+    }
+    area
+}
+
+pub fn hot_tub_load(model: &BuildingModel) -> HotTubLoad {
+    let water_volume = hot_tub_water_volume();
+    let footprint = hot_tub_shell().area();
+
+    // A pad under the tub carries it even where the deck also runs over it
+    let deck = (model.deck.min.x, model.deck.min.y, model.deck.max.x, model.deck.max.y);
+    let slab: Vec<(f64, f64, f64, f64)> = HOT_TUB_SLAB.into_iter().collect();
+    let on_slab = covered_area(&slab) / footprint;
+    let on_either = covered_area(&[slab, vec![deck]].concat()) / footprint;
+
+    HotTubLoad {
+        water_volume,
+        water_mass: water_volume * WATER_DENSITY,
+        occupant_mass: HOT_TUB_SEATS as f64 * OCCUPANT_MASS,
+        total_mass: hot_tub_filled_mass(),
+        footprint,
+        area_load: hot_tub_area_load(),
+        on_deck: (on_either - on_slab).max(0.0),
+        on_slab,
+        unsupported: (1.0 - on_either).max(0.0),
+    }
+}
+
+impl HotTubLoad {
+    /// Problems with the tub layout and how it is supported; empty if it is fine
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = hot_tub_problems();
+        if self.on_deck > 0.001 && self.area_load > DECK_DESIGN_LOAD {
+            problems.push(format!(
+                "HOT TUB LOAD {:.1} kN/m² EXCEEDS DECK DESIGN LOAD {:.1} kN/m²",
+                self.area_load, DECK_DESIGN_LOAD
+            ));
+        }
+        if self.unsupported > 0.001 {
+            problems.push(format!(
+                "HOT TUB NOT FULLY ON A SLAB OR FOOTING: {:.0}% OF ITS FOOTPRINT IS UNSUPPORTED",
+                self.unsupported * 100.0
+            ));
+        }
+        problems
+    }
+
+    /// Report lines for the sheet and the console
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!(
+                "{}, {} SEATS, {} JETS, {} STEPS",
+                hot_tub_size(),
+                HOT_TUB_SEATS,
+                HOT_TUB_JETS.len(),
+                HOT_TUB_STEPS
+            ),
+            format!(
+                "WATER {:.2} m³ = {:.0} kg, SHELL {:.0} kg, {} OCCUPANTS {:.0} kg",
+                self.water_volume, self.water_mass, HOT_TUB_SHELL_MASS, HOT_TUB_SEATS, self.occupant_mass
+            ),
+            format!(
+                "FILLED {:.0} kg OVER {:.2} m² = {:.1} kN/m² (DECK DESIGN LOAD {:.1} kN/m²)",
+                self.total_mass, self.footprint, self.area_load, DECK_DESIGN_LOAD
+            ),
+            format!(
+                "FOOTPRINT ON SLAB {:.0}%, ON DECK {:.0}%, UNSUPPORTED {:.0}%",
+                self.on_slab * 100.0,
+                self.on_deck * 100.0,
+                self.unsupported * 100.0
+            ),
+        ];
+        let problems = self.problems();
+        if problems.is_empty() {
+            lines.push("TUB FULLY SUPPORTED WITHIN DESIGN LOAD".to_string());
+        }
+        lines.extend(problems.into_iter().map(|problem| format!("WARNING: {}", problem)));
+        lines
+    }
+}
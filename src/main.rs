@@ -1,38 +1,172 @@
+mod benches;
+mod bom;
+mod ceiling;
 mod constants;
+mod diff;
+mod electrical;
+mod foundation;
+mod framing;
+mod heaters;
 mod helpers;
+mod hot_tub;
+mod ifc;
 mod layers;
+mod loads;
+mod model;
+mod model3d;
 mod plan;
+mod plumbing;
+mod railings;
+mod sheet;
+mod site;
+mod stairs;
+mod sun;
+mod survey;
+mod thermal;
+mod title_block;
+mod ventilation;
+mod verify;
 mod views;
+mod wall_framing;
 
-use dxf::enums::AcadVersion;
-use dxf::Drawing;
-
+use benches::bench_check;
+use bom::{bill_of_materials, bom_line, concrete_total, save_bom, section_totals};
+use ceiling::lighting_schedule;
 use constants::*;
-use layers::setup_layers;
-use plan::draw_floor_plan;
-use views::*;
+use diff::diff_files;
+use electrical::panel_schedule;
+use foundation::foundation;
+use framing::{deck_framing, span_check_line, span_checks};
+use heaters::{heater_check, HeaterCatalogue};
+use helpers::revision_cloud_problems;
+use ifc::save_ifc;
+use loads::hot_tub_load;
+use model::building_model;
+use plumbing::plumbing_check;
+use railings::railing_check;
+use sheet::{build_drawing, site_plan_origin};
+use site::site_check;
+use stairs::stair_schedule;
+use sun::{clock, sun_study};
+use survey::{draw_survey_underlay, load_survey};
+use thermal::sauna_heat_loss;
+use title_block::TitleBlockTemplate;
+use ventilation::sauna_ventilation;
+use verify::verify_file;
+use wall_framing::model_wall_framing;
+
+/// Options followed by a file name
+const OPTIONS_WITH_VALUES: [&str; 3] = ["--title-block", "--heaters", "--survey"];
 
 fn main() {
-    let mut drawing = Drawing::new();
-    drawing.header.version = AcadVersion::R2013;
+    let args: Vec<String> = std::env::args().skip(1).collect();
 
-    // Set up drawing units (meters)
-    drawing.header.default_drawing_units = dxf::enums::Units::Meters;
+    // Title block layout: built-in template unless --title-block <file> is given
+    let title_block = match args.iter().position(|arg| arg == "--title-block") {
+        Some(index) => {
+            let Some(path) = args.get(index + 1) else {
+                eprintln!("Usage: sauna --title-block <template.txt>");
+                std::process::exit(2);
+            };
+            match TitleBlockTemplate::load(path) {
+                Ok(template) => template,
+                Err(e) => {
+                    eprintln!("Error loading title block template: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => TitleBlockTemplate::default(),
+    };
+
+    // Heater catalogue: built-in unless --heaters <file> is given
+    let heaters = match args.iter().position(|arg| arg == "--heaters") {
+        Some(index) => {
+            let Some(path) = args.get(index + 1) else {
+                eprintln!("Usage: sauna --heaters <catalogue.txt>");
+                std::process::exit(2);
+            };
+            match HeaterCatalogue::load(path) {
+                Ok(catalogue) => catalogue,
+                Err(e) => {
+                    eprintln!("Error loading heater catalogue: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => HeaterCatalogue::default(),
+    };
+    let heater = match heaters.select(HEATER_MODEL) {
+        Ok(heater) => heater,
+        Err(e) => {
+            eprintln!("Error selecting heater: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Arguments that are not options or option values: the subcommand and its files
+    let operands: Vec<&str> = args
+        .iter()
+        .enumerate()
+        .filter(|&(i, arg)| !arg.starts_with("--") && (i == 0 || !OPTIONS_WITH_VALUES.contains(&args[i - 1].as_str())))
+        .map(|(_, arg)| arg.as_str())
+        .collect();
+
+    // sauna verify <file.dxf>: re-read a generated drawing and check it
+    if operands.first() == Some(&"verify") {
+        let Some(path) = operands.get(1) else {
+            eprintln!("Usage: sauna verify <file.dxf> [--heaters <catalogue.txt>] [--title-block <template.txt>]");
+            std::process::exit(2);
+        };
+        if !verify_file(path, &building_model(&heater), &title_block) {
+            std::process::exit(1);
+        }
+        return;
+    }
 
-    // Set up all layers
-    setup_layers(&mut drawing);
+    // sauna diff <old.dxf> [new.dxf]: changes between two revisions, or
+    // between a drawing and the current model
+    if operands.first() == Some(&"diff") {
+        let Some(old_path) = operands.get(1) else {
+            eprintln!("Usage: sauna diff <old.dxf> [new.dxf] [--heaters <catalogue.txt>] [--title-block <template.txt>]");
+            std::process::exit(2);
+        };
+        if !diff_files(old_path, operands.get(2).copied(), "sauna_diff.dxf", &building_model(&heater), &title_block) {
+            std::process::exit(1);
+        }
+        return;
+    }
 
-    // Draw floor plan with all elements
-    draw_floor_plan(&mut drawing);
+    // Optional outputs: extruded 3D model in the DXF, IFC4 BIM export
+    let model_3d = args.iter().any(|arg| arg == "--3d");
+    let export_ifc = args.iter().any(|arg| arg == "--ifc");
+    let export_bom = args.iter().any(|arg| arg == "--bom");
 
-    // Create elevation views offset from plan view
-    let offset_x = TOTAL_WIDTH + 2.0;
-    let offset_y = DECK_DEPTH;
+    // Survey drawing to copy under the site plan, if --survey <file> is given
+    let survey = match args.iter().position(|arg| arg == "--survey") {
+        Some(index) => {
+            let Some(path) = args.get(index + 1) else {
+                eprintln!("Usage: sauna --survey <survey.dxf>");
+                std::process::exit(2);
+            };
+            match load_survey(path) {
+                Ok(survey) => Some(survey),
+                Err(e) => {
+                    eprintln!("Error loading survey {}: {}", path, e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => None,
+    };
 
-    create_front_elevation(&mut drawing, offset_x, offset_y);
-    create_top_view(&mut drawing, offset_x, offset_y - 5.0);
-    create_side_elevation(&mut drawing, offset_x + 5.0, offset_y);
-    create_section_cut(&mut drawing, offset_x + 8.0, offset_y);
+    // Building model shared by the plan and the 3D/IFC exports
+    let model = building_model(&heater);
+    let mut drawing = build_drawing(&model, model_3d, &title_block);
+    let underlay = survey.map(|survey| {
+        let (site_x, site_y) = site_plan_origin();
+        draw_survey_underlay(&mut drawing, &survey, site_x, site_y)
+    });
 
     // Save the drawing
     match drawing.save_file("sauna_design.dxf") {
@@ -43,6 +177,10 @@ fn main() {
             println!();
             println!("Drawing saved: sauna_design.dxf");
             println!("Scale: {}", DRAWING_SCALE);
+            println!("Revision: {} ({})", current_revision().number, current_revision().date);
+            for problem in revision_cloud_problems(REVISION_CLOUDS) {
+                println!("  WARNING: {}", problem);
+            }
             println!();
             println!("DIMENSIONS:");
             println!("  Total width:     {:.2} m", TOTAL_WIDTH);
@@ -59,6 +197,108 @@ fn main() {
             println!("  ─────────────────────────");
             println!("  TOTAL:           {:.1} m²", total_floor_area());
             println!();
+            println!("DECK FRAMING:");
+            let framing = deck_framing(&model);
+            println!(
+                "  {} joists, {} beam lines, {} footings",
+                framing.joists.len(),
+                framing.beams.len(),
+                framing.footings.len()
+            );
+            for check in span_checks(&framing) {
+                println!("  {}", span_check_line(&check));
+            }
+            println!();
+            println!("FOUNDATION:");
+            for line in foundation(&framing).lines() {
+                println!("  {}", line);
+            }
+            println!();
+            println!("STAIRS:");
+            for line in stair_schedule(&model).lines() {
+                println!("  {}", line);
+            }
+            println!();
+            println!("RAILINGS:");
+            for line in railing_check(&model).lines() {
+                println!("  {}", line);
+            }
+            println!();
+            println!("SAUNA BENCHES:");
+            for line in bench_check(&model).lines() {
+                println!("  {}", line);
+            }
+            println!();
+            println!("HEATER:");
+            for line in heater_check(&model).lines() {
+                println!("  {}", line);
+            }
+            println!();
+            println!("WALL FRAMING:");
+            for framing in model_wall_framing(&model) {
+                println!("  {:<20} {:.2} m, {} members", framing.wall_id, framing.length, framing.members.len());
+            }
+            println!();
+            println!("BILL OF MATERIALS:");
+            let bom = bill_of_materials(&model);
+            for line in &bom {
+                println!("  {}", bom_line(line));
+            }
+            for (section, total) in section_totals(&bom) {
+                println!("  TOTAL {:<16} {:.1} m", section, total);
+            }
+            let concrete = concrete_total(&bom);
+            if concrete > 0.0 {
+                println!("  TOTAL CONCRETE {:<7} {:.2} m³", CONCRETE_GRADE, concrete);
+            }
+            println!();
+            println!("THERMAL:");
+            for line in sauna_heat_loss(&model).lines() {
+                println!("  {}", line);
+            }
+            println!();
+            println!("VENTILATION:");
+            for line in sauna_ventilation(&model).lines(&model) {
+                println!("  {}", line);
+            }
+            println!();
+            println!("ELECTRICAL:");
+            for line in panel_schedule(&model).lines(&model) {
+                println!("  {}", line);
+            }
+            println!();
+            println!("LIGHTING:");
+            for line in lighting_schedule(&model).lines() {
+                println!("  {}", line);
+            }
+            println!();
+            println!("PLUMBING:");
+            for line in plumbing_check(&model).lines(&model) {
+                println!("  {}", line);
+            }
+            println!();
+            println!("SITE:");
+            for line in site_check(&model).lines() {
+                println!("  {}", line);
+            }
+            println!();
+            println!("SUN AND SHADOWS:");
+            for line in sun_study(&model).lines() {
+                println!("  {}", line);
+            }
+            println!();
+            if let Some(underlay) = &underlay {
+                println!("SURVEY UNDERLAY:");
+                for line in underlay.lines() {
+                    println!("  {}", line);
+                }
+                println!();
+            }
+            println!("HOT TUB LOAD:");
+            for line in hot_tub_load(&model).lines() {
+                println!("  {}", line);
+            }
+            println!();
             println!("LAYERS:");
             println!("  A-WALL       Exterior walls");
             println!("  A-WALL-INTR  Interior partitions");
@@ -71,8 +311,68 @@ fn main() {
             println!("  A-PLMB-FIXT  Hot tub");
             println!("  A-FIXT       Fixtures");
             println!("  A-ROOF       Roof");
+            println!("  A-ELEV-ISOM  Isometric view");
+            println!("  A-WALL-PATT  Wall assembly layers");
+            println!("  A-ROOF-PATT  Roof assembly layers");
+            println!("  A-FLOR-PATT  Floor assembly layers");
+            println!("  A-CLNG       Reflected ceiling plan, roof edge");
+            println!("  A-CLNG-PATT  Ceiling panelling");
+            println!("  A-FLOR-STRS  Deck stairs and stringers");
+            println!("  A-FLOR-HRAL  Deck railings");
+            println!("  A-ANNO-REVS  Revision clouds");
+            println!("  M-HVAC-VENT  Sauna vents and airflow");
+            println!("  E-POWR       Panel, feeds, disconnect and outlets");
+            println!("  E-LITE       Lights, switches and switch legs");
+            println!("  E-ANNO       Circuit numbers and panel schedule");
+            println!("  P-DOMW       Water supply");
+            println!("  P-SANR       Drains");
+            println!("  P-VENT       Plumbing vents");
+            println!("  P-FIXT       Plumbing fixtures and valves");
+            println!("  P-ANNO       Pipe sizes, falls and pipe schedule");
+            for &time in SUN_STUDY_TIMES {
+                println!("  A-SHAD-{}  Shadows at {}", clock(time).replace(':', ""), clock(time));
+            }
+            println!("  C-PROP       Lot lines");
+            println!("  C-PROP-SETB  Setback lines");
+            println!("  S-JOIS       Deck joists");
+            println!("  S-BEAM       Deck beams");
+            println!("  S-FNDN       Foundation");
+            println!("  S-WALL-FRMG  Wall framing elevations");
+            if let Some(underlay) = &underlay {
+                for layer in underlay.layers.keys() {
+                    println!("  {:<12} Survey underlay", layer);
+                }
+            }
+            if model_3d {
+                println!("  A-MODL-WALL  3D walls and posts");
+                println!("  A-MODL-DECK  3D deck platform");
+                println!("  A-MODL-ROOF  3D roof slab");
+            }
             println!();
         }
         Err(e) => eprintln!("Error saving drawing: {}", e),
     }
+
+    // IFC4 export for BIM handover
+    if export_ifc {
+        match save_ifc(&model, "sauna_design.ifc") {
+            Ok(_) => {
+                println!("IFC model saved: sauna_design.ifc");
+                println!("  {} walls, {} rooms, {} furnishings", model.walls.len(), model.rooms.len(), model.furnishings.len());
+                println!();
+            }
+            Err(e) => eprintln!("Error saving IFC model: {}", e),
+        }
+    }
+
+    // Bill of materials as CSV for ordering
+    if export_bom {
+        match save_bom(&model, "sauna_bom.csv") {
+            Ok(_) => {
+                println!("Bill of materials saved: sauna_bom.csv");
+                println!();
+            }
+            Err(e) => eprintln!("Error saving bill of materials: {}", e),
+        }
+    }
 }
@@ -0,0 +1,764 @@
+use dxf::Point;
+
+use crate::benches::bench_seats;
+use crate::constants::*;
+use crate::heaters::{Heater, HeaterKind};
+use crate::layers::*;
+use crate::hot_tub::hot_tub_shell;
+
+// ============================================================================
+// SAUNA BUILDING - 3D BUILDING MODEL
+// Plan coordinates match the floor plan, z is measured up from ground level
+// ============================================================================
+
+/// Kind of opening cut through a wall
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OpeningKind {
+    Door,
+    Window,
+}
+
+/// Door or window opening, positioned along its host wall
+#[derive(Clone, Debug)]
+pub struct Opening {
+    pub id: &'static str,
+    pub kind: OpeningKind,
+    pub offset: f64, // Distance from the wall start (min x or min y)
+    pub width: f64,
+    pub sill: f64, // Height above finished floor
+    pub height: f64,
+    pub glazed: bool, // Windows and glass doors, counted as glass in the heater sizing
+}
+
+/// Straight wall defined by its plan rectangle
+#[derive(Clone, Debug)]
+pub struct Wall {
+    pub id: &'static str,
+    pub exterior: bool,
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+    pub openings: Vec<Opening>,
+}
+
+/// Axis-aligned box in model space
+#[derive(Clone, Debug)]
+pub struct Volume {
+    pub layer: &'static str,
+    pub min: Point,
+    pub max: Point,
+}
+
+/// Named floor area, labelled on the plan
+#[derive(Clone, Debug)]
+pub struct Room {
+    pub name: &'static str,
+    pub interior: bool,
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+    pub label_x: f64,
+    pub label_y: f64,
+    pub ceiling: Option<f64>, // Ceiling or soffit height, None if open to the sky
+}
+
+/// Plan outline of a loose fixture
+#[derive(Clone, Debug)]
+pub enum Footprint {
+    Rect { x1: f64, y1: f64, x2: f64, y2: f64 },
+    Circle { x: f64, y: f64, radius: f64 },
+}
+
+/// Bench, heater or other furnishing standing on the floor
+#[derive(Clone, Debug)]
+pub struct Furnishing {
+    pub id: String,
+    pub footprint: Footprint,
+    pub height: f64, // Top surface above finished floor
+}
+
+/// Direction of the air through a vent
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VentKind {
+    Intake,
+    Exhaust,
+}
+
+/// Square ventilation grille through a wall of the sauna room
+#[derive(Clone, Debug)]
+pub struct Vent {
+    pub id: &'static str,
+    pub kind: VentKind,
+    pub wall: &'static str, // Host wall id
+    pub offset: f64,        // Centre distance from the wall start (min x or min y)
+    pub height: f64,        // Centre height above finished floor
+    pub size: f64,          // Side of the grille
+}
+
+/// Kind of electrical device
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DeviceKind {
+    Panel,
+    HeaterFeed,
+    HeaterControl,
+    Disconnect,
+    Light(&'static str), // Fitting mark in LIGHT_FIXTURES
+    Switch,
+    Outlet,
+}
+
+/// Electrical device placed in plan, fed from a circuit in CIRCUITS
+#[derive(Clone, Debug)]
+pub struct Device {
+    pub id: &'static str,
+    pub kind: DeviceKind,
+    pub x: f64,
+    pub y: f64,
+    pub height: f64,                      // Mounting height above finished floor
+    pub circuit: u32,                     // 0 for the panel itself
+    pub load: f64,                        // W
+    pub controls: &'static [&'static str], // Lights worked by a switch
+}
+
+/// Kind of plumbing fixture or connection
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FixtureKind {
+    ServiceEntry,
+    ShowerValve,
+    ShowerHead,
+    FloorDrain,
+    FillValve,
+    DrainValve,
+    SewerConnection,
+}
+
+/// Plumbing fixture or connection point, height above finished floor
+#[derive(Clone, Debug)]
+pub struct PlumbingFixture {
+    pub id: &'static str,
+    pub kind: FixtureKind,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64, // Negative below the floor
+}
+
+/// What a pipe carries
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PipeService {
+    Supply,
+    Drain,
+    Vent,
+}
+
+/// Pipe run through its bends, from the source (supply) or the fixture
+/// (drain and vent) onwards; z is height above finished floor
+#[derive(Clone, Debug)]
+pub struct Pipe {
+    pub id: &'static str,
+    pub service: PipeService,
+    pub size: f64, // Nominal size in inches
+    pub points: Vec<Point>,
+}
+
+/// Complete building: walls with openings, posts, slabs, rooms, furnishings,
+/// vents, electrical devices and plumbing
+pub struct BuildingModel {
+    pub walls: Vec<Wall>,
+    pub posts: Vec<Volume>,
+    pub deck: Volume,
+    pub roof: Volume,
+    pub rooms: Vec<Room>,
+    pub furnishings: Vec<Furnishing>,
+    pub vents: Vec<Vent>,
+    pub devices: Vec<Device>,
+    pub fixtures: Vec<PlumbingFixture>,
+    pub pipes: Vec<Pipe>,
+    pub heater: Heater,
+}
+
+impl Wall {
+    /// True if the wall runs along the x axis
+    pub fn is_horizontal(&self) -> bool {
+        (self.x2 - self.x1) >= (self.y2 - self.y1)
+    }
+
+    pub fn length(&self) -> f64 {
+        (self.x2 - self.x1).max(self.y2 - self.y1)
+    }
+
+    pub fn thickness(&self) -> f64 {
+        (self.x2 - self.x1).min(self.y2 - self.y1)
+    }
+
+    /// Stretch of the wall (start, end along its length) along a face of the
+    /// room, or None if the wall does not bound it
+    pub fn faces_room(&self, room: &Room) -> Option<(f64, f64)> {
+        let (start, end, touches) = if self.is_horizontal() {
+            let touches = (self.y2 - room.y1).abs() < 1e-6 || (self.y1 - room.y2).abs() < 1e-6;
+            (room.x1.max(self.x1) - self.x1, room.x2.min(self.x2) - self.x1, touches)
+        } else {
+            let touches = (self.x2 - room.x1).abs() < 1e-6 || (self.x1 - room.x2).abs() < 1e-6;
+            (room.y1.max(self.y1) - self.y1, room.y2.min(self.y2) - self.y1, touches)
+        };
+        (touches && end > start).then_some((start, end))
+    }
+
+    /// Box covering the wall between two distances along its length
+    fn segment(&self, start: f64, end: f64, z1: f64, z2: f64) -> Volume {
+        let (min, max) = if self.is_horizontal() {
+            (
+                Point::new(self.x1 + start, self.y1, z1),
+                Point::new(self.x1 + end, self.y2, z2),
+            )
+        } else {
+            (
+                Point::new(self.x1, self.y1 + start, z1),
+                Point::new(self.x2, self.y1 + end, z2),
+            )
+        };
+        Volume { layer: LAYER_MODEL_WALLS, min, max }
+    }
+
+    /// Split the wall into boxes around its openings
+    pub fn volumes(&self, floor_z: f64, top_z: f64) -> Vec<Volume> {
+        let mut openings = self.openings.clone();
+        openings.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+
+        let mut volumes = Vec::new();
+        let mut pos = 0.0;
+        for opening in &openings {
+            if opening.offset > pos {
+                volumes.push(self.segment(pos, opening.offset, floor_z, top_z));
+            }
+            let start = opening.offset;
+            let end = opening.offset + opening.width;
+
+            // Below the opening (window sill)
+            if opening.sill > 0.0 {
+                volumes.push(self.segment(start, end, floor_z, floor_z + opening.sill));
+            }
+
+            // Above the opening (lintel)
+            let head_z = floor_z + opening.sill + opening.height;
+            if head_z < top_z {
+                volumes.push(self.segment(start, end, head_z, top_z));
+            }
+            pos = end;
+        }
+        if pos < self.length() {
+            volumes.push(self.segment(pos, self.length(), floor_z, top_z));
+        }
+        volumes
+    }
+}
+
+// Corner indices of the six box faces (see Volume::corners)
+pub const BOX_FACES: [[usize; 4]; 6] = [
+    [0, 3, 2, 1], // Bottom
+    [4, 5, 6, 7], // Top
+    [0, 1, 5, 4], // South
+    [1, 2, 6, 5], // East
+    [2, 3, 7, 6], // North
+    [3, 0, 4, 7], // West
+];
+
+// Corner index pairs of the twelve box edges
+pub const BOX_EDGES: [[usize; 2]; 12] = [
+    [0, 1], [1, 2], [2, 3], [3, 0],
+    [4, 5], [5, 6], [6, 7], [7, 4],
+    [0, 4], [1, 5], [2, 6], [3, 7],
+];
+
+impl Volume {
+    /// The eight box corners: bottom face then top face
+    pub fn corners(&self) -> [Point; 8] {
+        let (a, b) = (&self.min, &self.max);
+        [
+            Point::new(a.x, a.y, a.z),
+            Point::new(b.x, a.y, a.z),
+            Point::new(b.x, b.y, a.z),
+            Point::new(a.x, b.y, a.z),
+            Point::new(a.x, a.y, b.z),
+            Point::new(b.x, a.y, b.z),
+            Point::new(b.x, b.y, b.z),
+            Point::new(a.x, b.y, b.z),
+        ]
+    }
+}
+
+impl Footprint {
+    pub fn centre(&self) -> (f64, f64) {
+        match *self {
+            Footprint::Circle { x, y, .. } => (x, y),
+            Footprint::Rect { x1, y1, x2, y2 } => ((x1 + x2) / 2.0, (y1 + y2) / 2.0),
+        }
+    }
+
+    /// Plan distance from a point to the outline, zero inside it
+    pub fn distance_to(&self, px: f64, py: f64) -> f64 {
+        match *self {
+            Footprint::Circle { x, y, radius } => (((px - x).powi(2) + (py - y).powi(2)).sqrt() - radius).max(0.0),
+            Footprint::Rect { x1, y1, x2, y2 } => {
+                let dx = (x1 - px).max(px - x2).max(0.0);
+                let dy = (y1 - py).max(py - y2).max(0.0);
+                (dx * dx + dy * dy).sqrt()
+            }
+        }
+    }
+}
+
+impl Room {
+    pub fn area(&self) -> f64 {
+        (self.x2 - self.x1) * (self.y2 - self.y1)
+    }
+
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        x > self.x1 && x < self.x2 && y > self.y1 && y < self.y2
+    }
+}
+
+/// Floor level of the enclosed rooms and covered deck
+pub fn floor_level() -> f64 {
+    DECK_ELEV_HEIGHT
+}
+
+/// Underside of the roof slab
+pub fn roof_level() -> f64 {
+    DECK_ELEV_HEIGHT + BUILDING_HEIGHT
+}
+
+/// Build the building model from the dimensional constants
+pub fn building_model(heater: &Heater) -> BuildingModel {
+    let deck_y = DECK_DEPTH;
+    let building_top = deck_y + BUILDING_DEPTH;
+    let inner_depth = BUILDING_DEPTH - 2.0 * WALL_THICKNESS;
+    let mid_y = (deck_y + building_top) / 2.0;
+
+    let walls = vec![
+        // =====================================================================
+        // EXTERIOR WALLS
+        // =====================================================================
+        Wall {
+            id: "wall-south",
+            exterior: true,
+            x1: 0.0,
+            y1: deck_y,
+            x2: ENCLOSED_WIDTH,
+            y2: deck_y + WALL_THICKNESS,
+            openings: vec![Opening {
+                id: "door-wash-deck",
+                kind: OpeningKind::Door,
+                offset: SAUNA_WIDTH + 0.20,
+                width: DOOR_WIDTH,
+                sill: 0.0,
+                height: DOOR_HEIGHT,
+                glazed: false,
+            }],
+        },
+        Wall {
+            id: "wall-north",
+            exterior: true,
+            x1: 0.0,
+            y1: building_top - WALL_THICKNESS,
+            x2: ENCLOSED_WIDTH,
+            y2: building_top,
+            openings: vec![
+                Opening {
+                    id: "window-sauna-north",
+                    kind: OpeningKind::Window,
+                    offset: SAUNA_WIDTH / 2.0 - WINDOW_WIDTH / 2.0,
+                    width: WINDOW_WIDTH,
+                    sill: WINDOW_SILL_HEIGHT,
+                    height: WINDOW_HEIGHT,
+                    glazed: true,
+                },
+                Opening {
+                    id: "window-changing-north",
+                    kind: OpeningKind::Window,
+                    offset: SAUNA_WIDTH + WASH_WIDTH + SEATING_WIDTH / 2.0 - WINDOW_WIDTH / 2.0,
+                    width: WINDOW_WIDTH,
+                    sill: WINDOW_SILL_HEIGHT,
+                    height: WINDOW_HEIGHT,
+                    glazed: true,
+                },
+            ],
+        },
+        Wall {
+            id: "wall-west",
+            exterior: true,
+            x1: 0.0,
+            y1: deck_y + WALL_THICKNESS,
+            x2: WALL_THICKNESS,
+            y2: building_top - WALL_THICKNESS,
+            openings: vec![Opening {
+                id: "window-sauna-west",
+                kind: OpeningKind::Window,
+                offset: BUILDING_DEPTH / 2.0 - WINDOW_WIDTH / 2.0 - WALL_THICKNESS,
+                width: WINDOW_WIDTH,
+                sill: WINDOW_SILL_HEIGHT,
+                height: WINDOW_HEIGHT,
+                glazed: true,
+            }],
+        },
+        Wall {
+            id: "wall-east",
+            exterior: true,
+            x1: ENCLOSED_WIDTH - WALL_THICKNESS,
+            y1: deck_y + WALL_THICKNESS,
+            x2: ENCLOSED_WIDTH,
+            y2: building_top - WALL_THICKNESS,
+            openings: vec![],
+        },
+        // =====================================================================
+        // INTERIOR PARTITION WALLS
+        // =====================================================================
+        Wall {
+            id: "wall-sauna-wash",
+            exterior: false,
+            x1: SAUNA_WIDTH - WALL_THICKNESS,
+            y1: deck_y + WALL_THICKNESS,
+            x2: SAUNA_WIDTH,
+            y2: building_top - WALL_THICKNESS,
+            openings: vec![Opening {
+                id: "door-sauna-wash",
+                kind: OpeningKind::Door,
+                offset: DOOR_MARGIN,
+                width: DOOR_WIDTH,
+                sill: 0.0,
+                height: DOOR_HEIGHT,
+                glazed: true,
+            }],
+        },
+        Wall {
+            id: "wall-wash-changing",
+            exterior: false,
+            x1: SAUNA_WIDTH + WASH_WIDTH - WALL_THICKNESS,
+            y1: deck_y + WALL_THICKNESS,
+            x2: SAUNA_WIDTH + WASH_WIDTH,
+            y2: building_top - WALL_THICKNESS,
+            openings: vec![Opening {
+                id: "door-wash-changing",
+                kind: OpeningKind::Door,
+                offset: inner_depth - DOOR_MARGIN - DOOR_WIDTH,
+                width: DOOR_WIDTH,
+                sill: 0.0,
+                height: DOOR_HEIGHT,
+                glazed: false,
+            }],
+        },
+    ];
+
+    // Support posts for covered deck
+    let posts = [
+        (TOTAL_WIDTH - POST_INSET, deck_y + POST_INSET),
+        (TOTAL_WIDTH - POST_INSET, building_top - POST_INSET - POST_SIZE),
+    ]
+    .iter()
+    .map(|&(px, py)| Volume {
+        layer: LAYER_MODEL_WALLS,
+        min: Point::new(px, py, floor_level()),
+        max: Point::new(px + POST_SIZE, py + POST_SIZE, roof_level()),
+    })
+    .collect();
+
+    // Deck platform under the terrace, rooms and covered deck
+    let deck = Volume {
+        layer: LAYER_MODEL_DECK,
+        min: Point::new(0.0, 0.0, 0.0),
+        max: Point::new(TOTAL_WIDTH, building_top, DECK_ELEV_HEIGHT),
+    };
+
+    // Flat roof slab with overhang
+    let roof = Volume {
+        layer: LAYER_MODEL_ROOF,
+        min: Point::new(-ROOF_OVERHANG, deck_y - ROOF_OVERHANG, roof_level()),
+        max: Point::new(
+            TOTAL_WIDTH + ROOF_OVERHANG,
+            building_top + ROOF_OVERHANG,
+            roof_level() + ROOF_THICKNESS,
+        ),
+    };
+
+    // =========================================================================
+    // ROOMS (clear floor areas inside walls)
+    // =========================================================================
+
+    let rooms = vec![
+        Room {
+            name: "SAUNA",
+            interior: true,
+            x1: WALL_THICKNESS,
+            y1: deck_y + WALL_THICKNESS,
+            x2: SAUNA_WIDTH - WALL_THICKNESS,
+            y2: building_top - WALL_THICKNESS,
+            // On the free floor in front of the benches
+            label_x: SAUNA_WIDTH / 2.0 + 0.20,
+            label_y: deck_y + WALL_THICKNESS + 0.60,
+            ceiling: Some(BUILDING_HEIGHT),
+        },
+        Room {
+            name: "WASH",
+            interior: true,
+            x1: SAUNA_WIDTH,
+            y1: deck_y + WALL_THICKNESS,
+            x2: SAUNA_WIDTH + WASH_WIDTH - WALL_THICKNESS,
+            y2: building_top - WALL_THICKNESS,
+            label_x: SAUNA_WIDTH + WASH_WIDTH / 2.0,
+            label_y: mid_y,
+            ceiling: Some(BUILDING_HEIGHT),
+        },
+        Room {
+            name: "CHANGING",
+            interior: true,
+            x1: SAUNA_WIDTH + WASH_WIDTH,
+            y1: deck_y + WALL_THICKNESS,
+            x2: ENCLOSED_WIDTH - WALL_THICKNESS,
+            y2: building_top - WALL_THICKNESS,
+            label_x: SAUNA_WIDTH + WASH_WIDTH + SEATING_WIDTH / 2.0,
+            label_y: mid_y,
+            ceiling: Some(BUILDING_HEIGHT),
+        },
+        Room {
+            name: "DECK",
+            interior: false,
+            x1: ENCLOSED_WIDTH,
+            y1: deck_y,
+            x2: TOTAL_WIDTH,
+            y2: building_top,
+            label_x: ENCLOSED_WIDTH + OUTDOOR_SEATING_WIDTH / 2.0,
+            label_y: mid_y,
+            ceiling: Some(BUILDING_HEIGHT),
+        },
+        Room {
+            name: "TERRACE",
+            interior: false,
+            x1: 0.0,
+            y1: 0.0,
+            x2: TOTAL_WIDTH,
+            y2: DECK_DEPTH,
+            label_x: TOTAL_WIDTH / 3.0,
+            label_y: DECK_DEPTH / 2.0,
+            ceiling: None,
+        },
+    ];
+
+    // =========================================================================
+    // SAUNA FURNISHINGS
+    // =========================================================================
+
+    // Heater in the south-west corner, its wall clearance off both walls
+    let heater_x = WALL_THICKNESS + heater.wall_clearance + heater.width / 2.0;
+    let heater_y = deck_y + WALL_THICKNESS + heater.wall_clearance + heater.depth / 2.0;
+    let mut furnishings = vec![Furnishing {
+        id: "heater".to_string(),
+        footprint: heater.footprint(heater_x, heater_y),
+        height: heater.height,
+    }];
+
+    // One furnishing per bench tier and run
+    let back_y = building_top - WALL_THICKNESS;
+    for seat in bench_seats(BENCH_LAYOUT, WALL_THICKNESS, deck_y + WALL_THICKNESS, SAUNA_WIDTH - WALL_THICKNESS, back_y) {
+        furnishings.push(Furnishing {
+            id: seat.id,
+            footprint: Footprint::Rect { x1: seat.x1, y1: seat.y1, x2: seat.x2, y2: seat.y2 },
+            height: seat.height,
+        });
+    }
+
+    // =========================================================================
+    // SAUNA VENTILATION
+    // =========================================================================
+
+    // Fresh air in low beside the heater, out high on the opposite corner
+    let vents = vec![
+        Vent {
+            id: "vent-sauna-intake",
+            kind: VentKind::Intake,
+            wall: "wall-south",
+            offset: heater_x,
+            height: INTAKE_HEIGHT,
+            size: VENT_SIZE,
+        },
+        Vent {
+            id: "vent-sauna-exhaust",
+            kind: VentKind::Exhaust,
+            wall: "wall-north",
+            offset: SAUNA_WIDTH - WALL_THICKNESS - 0.30,
+            height: EXHAUST_HEIGHT,
+            size: VENT_SIZE,
+        },
+    ];
+
+    // =========================================================================
+    // ELECTRICAL
+    // =========================================================================
+
+    let device = |id, kind, x, y, height, circuit, load| Device { id, kind, x, y, height, circuit, load, controls: &[] };
+    let sauna_door_y = deck_y + WALL_THICKNESS + DOOR_MARGIN + DOOR_WIDTH;
+    let changing_door_y = deck_y + WALL_THICKNESS + inner_depth - DOOR_MARGIN - DOOR_WIDTH;
+    let deck_door_x = SAUNA_WIDTH + 0.20;
+    let light = |id, mark, x, y| {
+        let watts = LIGHT_FIXTURES.iter().find(|f| f.mark == mark).map_or(0.0, |f| f.watts);
+        device(id, DeviceKind::Light(mark), x, y, BUILDING_HEIGHT, 3, watts)
+    };
+    // Sub-panel in the changing room, dry side of the building
+    let mut devices = vec![device("panel", DeviceKind::Panel, ENCLOSED_WIDTH - WALL_THICKNESS, deck_y + WALL_THICKNESS + 0.60, 1.50, 0, 0.0)];
+    // An electric heater is fed low behind it and worked from a control
+    // panel outside the sauna, beside the door
+    if heater.kind == HeaterKind::Electric {
+        devices.push(device("heater-feed", DeviceKind::HeaterFeed, WALL_THICKNESS, heater_y, 0.30, 1, heater.power));
+        devices.push(device("heater-control", DeviceKind::HeaterControl, SAUNA_WIDTH, sauna_door_y + 0.40, HEATER_CONTROL_HEIGHT, 1, 0.0));
+    }
+    devices.extend([
+        // Hot tub disconnect on the outside of the east wall, in sight of the tub
+        device("hot-tub-disconnect", DeviceKind::Disconnect, ENCLOSED_WIDTH, deck_y + 0.50, 1.20, 2, HOT_TUB_POWER),
+        // Sauna-rated fitting in the hot room, kept clear of the heater
+        light("light-sauna", "L1", SAUNA_WIDTH / 2.0, mid_y),
+        light("light-wash", "L2", SAUNA_WIDTH + WASH_WIDTH / 2.0, mid_y),
+        light("light-changing", "L2", SAUNA_WIDTH + WASH_WIDTH + SEATING_WIDTH / 2.0, mid_y),
+        light("light-deck", "L3", ENCLOSED_WIDTH + OUTDOOR_SEATING_WIDTH / 2.0, mid_y),
+        // Switches outside the sauna, beside the doors
+        Device {
+            controls: &["light-sauna"],
+            ..device("switch-sauna", DeviceKind::Switch, SAUNA_WIDTH, sauna_door_y + 0.15, 1.10, 3, 0.0)
+        },
+        Device {
+            controls: &["light-wash", "light-deck"],
+            ..device("switch-wash", DeviceKind::Switch, deck_door_x - 0.12, deck_y + WALL_THICKNESS, 1.10, 3, 0.0)
+        },
+        Device {
+            controls: &["light-changing"],
+            ..device("switch-changing", DeviceKind::Switch, SAUNA_WIDTH + WASH_WIDTH, changing_door_y - 0.15, 1.10, 3, 0.0)
+        },
+        device("outlet-changing-east", DeviceKind::Outlet, ENCLOSED_WIDTH - WALL_THICKNESS, building_top - WALL_THICKNESS - 0.80, 0.30, 4, OUTLET_LOAD),
+        device("outlet-changing-north", DeviceKind::Outlet, ENCLOSED_WIDTH - WALL_THICKNESS - 0.40, building_top - WALL_THICKNESS, 0.30, 4, OUTLET_LOAD),
+        device("outlet-deck", DeviceKind::Outlet, ENCLOSED_WIDTH, building_top - WALL_THICKNESS - 0.50, 0.40, 4, OUTLET_LOAD),
+    ]);
+
+    // =========================================================================
+    // PLUMBING
+    // =========================================================================
+
+    // Shower against the north wall of the wash room, services in that wall
+    let shower_x = SAUNA_WIDTH + (WASH_WIDTH - WALL_THICKNESS) / 2.0;
+    let shower_wall_y = building_top - WALL_THICKNESS / 2.0;
+    let drain_y = building_top - WALL_THICKNESS - 0.45;
+    let service_x = SAUNA_WIDTH + WASH_WIDTH - WALL_THICKNESS - 0.15;
+    let outside_y = building_top + 0.50;
+    let supply_z = -SUPPLY_DEPTH;
+    let tub = hot_tub_shell();
+    let (_, tub_south_y, _, tub_rim_y) = tub.bounds();
+    let tub_x = tub.x;
+    let tub_base_z = -DECK_ELEV_HEIGHT + 0.10;
+    let vent_top = BUILDING_HEIGHT + ROOF_THICKNESS + VENT_ROOF_PROJECTION;
+
+    let fixture = |id, kind, x, y, z| PlumbingFixture { id, kind, x, y, z };
+    let fixtures = vec![
+        fixture("water-entry", FixtureKind::ServiceEntry, service_x, outside_y, supply_z),
+        fixture("shower-valve", FixtureKind::ShowerValve, shower_x, shower_wall_y, SHOWER_VALVE_HEIGHT),
+        fixture("shower-head", FixtureKind::ShowerHead, shower_x, shower_wall_y, SHOWER_HEAD_HEIGHT),
+        fixture("floor-drain", FixtureKind::FloorDrain, shower_x, drain_y, 0.0),
+        fixture("hot-tub-fill", FixtureKind::FillValve, tub_x, tub_rim_y, 0.0),
+        fixture("hot-tub-drain", FixtureKind::DrainValve, tub_x, tub_south_y, tub_base_z),
+        fixture("sewer-connection", FixtureKind::SewerConnection, shower_x, outside_y, -DECK_ELEV_HEIGHT),
+    ];
+
+    let p = |x, y, z| Point::new(x, y, z);
+    let pipes = vec![
+        Pipe {
+            id: "supply-service",
+            service: PipeService::Supply,
+            size: SERVICE_PIPE_SIZE,
+            points: vec![p(service_x, outside_y, supply_z), p(service_x, shower_wall_y, supply_z)],
+        },
+        Pipe {
+            id: "supply-shower",
+            service: PipeService::Supply,
+            size: SHOWER_SUPPLY_SIZE,
+            points: vec![
+                p(service_x, shower_wall_y, supply_z),
+                p(shower_x, shower_wall_y, supply_z),
+                p(shower_x, shower_wall_y, SHOWER_VALVE_HEIGHT),
+                p(shower_x, shower_wall_y, SHOWER_HEAD_HEIGHT),
+            ],
+        },
+        // Fill line under the covered deck to the tub rim
+        Pipe {
+            id: "supply-hot-tub",
+            service: PipeService::Supply,
+            size: SERVICE_PIPE_SIZE,
+            points: vec![
+                p(service_x, shower_wall_y, supply_z),
+                p(tub_x, shower_wall_y, supply_z),
+                p(tub_x, tub_rim_y, supply_z),
+                p(tub_x, tub_rim_y, 0.0),
+            ],
+        },
+        Pipe {
+            id: "drain-shower",
+            service: PipeService::Drain,
+            size: SHOWER_DRAIN_SIZE,
+            points: vec![
+                p(shower_x, drain_y, -0.10),
+                p(shower_x, shower_wall_y, -0.12),
+                p(shower_x, outside_y, -0.14),
+                p(shower_x, outside_y, -DECK_ELEV_HEIGHT),
+            ],
+        },
+        // Vent off the trap arm, up inside the north wall and through the roof
+        Pipe {
+            id: "vent-shower",
+            service: PipeService::Vent,
+            size: SHOWER_VENT_SIZE,
+            points: vec![p(shower_x, shower_wall_y, -0.12), p(shower_x, shower_wall_y, vent_top)],
+        },
+        // Hot tub empties through its drain valve to a soakaway south of the tub
+        Pipe {
+            id: "drain-hot-tub",
+            service: PipeService::Drain,
+            size: HOT_TUB_DRAIN_SIZE,
+            points: vec![
+                p(tub_x, tub_south_y, tub_base_z),
+                p(tub_x, tub_south_y - 1.00, tub_base_z - 0.03),
+            ],
+        },
+    ];
+
+    BuildingModel { walls, posts, deck, roof, rooms, furnishings, vents, devices, fixtures, pipes, heater: heater.clone() }
+}
+
+impl BuildingModel {
+    pub fn room(&self, name: &str) -> Option<&Room> {
+        self.rooms.iter().find(|room| room.name == name)
+    }
+
+    /// The sauna room, which every model has
+    pub fn sauna(&self) -> &Room {
+        self.room("SAUNA").expect("model has a sauna room")
+    }
+
+    pub fn wall(&self, id: &str) -> Option<&Wall> {
+        self.walls.iter().find(|wall| wall.id == id)
+    }
+
+    pub fn device(&self, id: &str) -> Option<&Device> {
+        self.devices.iter().find(|device| device.id == id)
+    }
+
+    pub fn furnishing(&self, id: &str) -> Option<&Furnishing> {
+        self.furnishings.iter().find(|furnishing| furnishing.id == id)
+    }
+
+    /// All volumes of the model, walls split around their openings
+    pub fn volumes(&self) -> Vec<Volume> {
+        let mut volumes = vec![self.deck.clone()];
+        for wall in &self.walls {
+            volumes.extend(wall.volumes(floor_level(), roof_level()));
+        }
+        volumes.extend(self.posts.iter().cloned());
+        volumes.push(self.roof.clone());
+        volumes
+    }
+}
@@ -0,0 +1,28 @@
+use dxf::entities::*;
+use dxf::Drawing;
+
+use crate::model::*;
+
+/// Write a volume as six 3DFACE entities on its model layer
+pub fn draw_volume(drawing: &mut Drawing, volume: &Volume) {
+    let corners = volume.corners();
+    for face in BOX_FACES {
+        let face_3d = Face3D {
+            first_corner: corners[face[0]].clone(),
+            second_corner: corners[face[1]].clone(),
+            third_corner: corners[face[2]].clone(),
+            fourth_corner: corners[face[3]].clone(),
+            ..Default::default()
+        };
+        let mut entity = Entity::new(EntityType::Face3D(face_3d));
+        entity.common.layer = volume.layer.to_string();
+        drawing.add_entity(entity);
+    }
+}
+
+/// Draw the extruded building model (deck, walls, posts, roof) at true elevations
+pub fn draw_model_3d(drawing: &mut Drawing, model: &BuildingModel) {
+    for volume in model.volumes() {
+        draw_volume(drawing, &volume);
+    }
+}
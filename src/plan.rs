@@ -3,21 +3,62 @@ use dxf::{Drawing, Point};
 
 use crate::constants::*;
 use crate::helpers::*;
+use crate::hot_tub::{draw_hot_tub_plan, hot_tub_shell, hot_tub_size};
 use crate::layers::*;
+use crate::model::*;
 
 /// Draw all floor plan elements
-pub fn draw_floor_plan(drawing: &mut Drawing) {
+pub fn draw_floor_plan(drawing: &mut Drawing, model: &BuildingModel) {
     draw_walls(drawing);
+    draw_wall_layers(drawing, model);
     draw_doors(drawing);
     draw_windows(drawing);
     draw_deck(drawing);
     draw_hot_tub(drawing);
-    draw_fixtures(drawing);
-    draw_room_labels(drawing);
+    draw_fixtures(drawing, model);
+    draw_room_labels(drawing, model);
     draw_dimensions(drawing);
     draw_annotations(drawing);
 }
 
+/// Joints between the wall assembly layers along every exterior wall,
+/// broken at the openings
+fn draw_wall_layers(drawing: &mut Drawing, model: &BuildingModel) {
+    let centre_x = ENCLOSED_WIDTH / 2.0;
+    let centre_y = DECK_DEPTH + BUILDING_DEPTH / 2.0;
+    let boundaries = WALL_ASSEMBLY.boundaries();
+
+    for wall in model.walls.iter().filter(|wall| wall.exterior) {
+        // Stretches of the wall between its openings
+        let mut stretches = Vec::new();
+        let mut start = 0.0;
+        let mut openings: Vec<&Opening> = wall.openings.iter().collect();
+        openings.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+        for opening in openings {
+            stretches.push((start, opening.offset));
+            start = opening.offset + opening.width;
+        }
+        stretches.push((start, wall.length()));
+
+        for &offset in &boundaries {
+            let depth = offset.min(wall.thickness());
+            for &(s1, s2) in &stretches {
+                let (p1, p2) = if wall.is_horizontal() {
+                    let y = if (wall.y1 + wall.y2) / 2.0 < centre_y { wall.y1 + depth } else { wall.y2 - depth };
+                    (Point::new(wall.x1 + s1, y, 0.0), Point::new(wall.x1 + s2, y, 0.0))
+                } else {
+                    let x = if (wall.x1 + wall.x2) / 2.0 < centre_x { wall.x1 + depth } else { wall.x2 - depth };
+                    (Point::new(x, wall.y1 + s1, 0.0), Point::new(x, wall.y1 + s2, 0.0))
+                };
+                let line = Line { p1, p2, ..Default::default() };
+                let mut entity = Entity::new(EntityType::Line(line));
+                entity.common.layer = LAYER_WALL_LAYERS.to_string();
+                drawing.add_entity(entity);
+            }
+        }
+    }
+}
+
 fn draw_walls(drawing: &mut Drawing) {
     let deck_y = DECK_DEPTH;
     let building_top = deck_y + BUILDING_DEPTH;
@@ -210,142 +251,75 @@ fn draw_deck(drawing: &mut Drawing) {
 }
 
 fn draw_hot_tub(drawing: &mut Drawing) {
-    let hot_tub_x = TOTAL_WIDTH - HOT_TUB_OFFSET_X;
-    let hot_tub_y = -HOT_TUB_RADIUS + HOT_TUB_OFFSET_Y;
+    let (tub_x1, _, tub_x2, _) = hot_tub_shell().bounds();
 
     // Hot tub enclosure on deck
     let enclosure = polyline_from_points(vec![
-        Point::new(hot_tub_x - HOT_TUB_RADIUS - 0.10, DECK_DEPTH, 0.0),
-        Point::new(hot_tub_x + HOT_TUB_RADIUS + 0.10, DECK_DEPTH, 0.0),
-        Point::new(hot_tub_x + HOT_TUB_RADIUS + 0.10, 0.0, 0.0),
-        Point::new(hot_tub_x - HOT_TUB_RADIUS - 0.10, 0.0, 0.0),
-        Point::new(hot_tub_x - HOT_TUB_RADIUS - 0.10, DECK_DEPTH, 0.0),
+        Point::new(tub_x1 - 0.10, DECK_DEPTH, 0.0),
+        Point::new(tub_x2 + 0.10, DECK_DEPTH, 0.0),
+        Point::new(tub_x2 + 0.10, 0.0, 0.0),
+        Point::new(tub_x1 - 0.10, 0.0, 0.0),
+        Point::new(tub_x1 - 0.10, DECK_DEPTH, 0.0),
     ]);
     let mut entity = Entity::new(EntityType::LwPolyline(enclosure));
     entity.common.layer = LAYER_HOT_TUB.to_string();
     drawing.add_entity(entity);
 
-    // Hot tub outer circle
-    let hot_tub_outer = Circle {
-        center: Point::new(hot_tub_x, hot_tub_y, 0.0),
-        radius: HOT_TUB_RADIUS,
-        ..Default::default()
-    };
-    let mut entity = Entity::new(EntityType::Circle(hot_tub_outer));
-    entity.common.layer = LAYER_HOT_TUB.to_string();
-    drawing.add_entity(entity);
+    // Concrete pad under the tub, if one is specified
+    if let Some((x1, y1, x2, y2)) = HOT_TUB_SLAB {
+        let slab = polyline_from_points(vec![
+            Point::new(x1, y1, 0.0),
+            Point::new(x2, y1, 0.0),
+            Point::new(x2, y2, 0.0),
+            Point::new(x1, y2, 0.0),
+            Point::new(x1, y1, 0.0),
+        ]);
+        let mut entity = Entity::new(EntityType::LwPolyline(slab));
+        entity.common.layer = LAYER_HOT_TUB.to_string();
+        drawing.add_entity(entity);
+    }
 
-    // Hot tub inner circle (water line)
-    let hot_tub_inner = Circle {
-        center: Point::new(hot_tub_x, hot_tub_y, 0.0),
-        radius: HOT_TUB_RADIUS - 0.15,
-        ..Default::default()
-    };
-    let mut entity = Entity::new(EntityType::Circle(hot_tub_inner));
-    entity.common.layer = LAYER_HOT_TUB.to_string();
-    drawing.add_entity(entity);
+    // Shell, water line, seats, jets, steps and cover
+    draw_hot_tub_plan(drawing);
 }
 
-fn draw_fixtures(drawing: &mut Drawing) {
-    let deck_y = DECK_DEPTH;
-
+fn draw_fixtures(drawing: &mut Drawing, model: &BuildingModel) {
     // =========================================================================
-    // SAUNA FIXTURES
+    // SAUNA FIXTURES (heater and benches)
     // =========================================================================
 
-    // Sauna heater (circle)
-    let heater_x = WALL_THICKNESS + HEATER_DIAMETER / 2.0 + 0.20;
-    let heater_y = deck_y + WALL_THICKNESS + HEATER_DIAMETER / 2.0 + 0.20;
-    let heater = Circle {
-        center: Point::new(heater_x, heater_y, 0.0),
-        radius: HEATER_DIAMETER / 2.0,
-        ..Default::default()
-    };
-    let mut entity = Entity::new(EntityType::Circle(heater));
-    entity.common.layer = LAYER_FIXTURES.to_string();
-    drawing.add_entity(entity);
-
-    // Lower bench (L-shaped along walls)
-    let bench_lower = polyline_from_points(vec![
-        Point::new(WALL_THICKNESS + 0.10, deck_y + BUILDING_DEPTH - WALL_THICKNESS - BENCH_DEPTH, 0.0),
-        Point::new(SAUNA_WIDTH - WALL_THICKNESS - 0.10, deck_y + BUILDING_DEPTH - WALL_THICKNESS - BENCH_DEPTH, 0.0),
-        Point::new(SAUNA_WIDTH - WALL_THICKNESS - 0.10, deck_y + BUILDING_DEPTH - WALL_THICKNESS - 0.10, 0.0),
-        Point::new(WALL_THICKNESS + 0.10, deck_y + BUILDING_DEPTH - WALL_THICKNESS - 0.10, 0.0),
-        Point::new(WALL_THICKNESS + 0.10, deck_y + BUILDING_DEPTH - WALL_THICKNESS - BENCH_DEPTH, 0.0),
-    ]);
-    let mut entity = Entity::new(EntityType::LwPolyline(bench_lower));
-    entity.common.layer = LAYER_FIXTURES.to_string();
-    drawing.add_entity(entity);
-
-    // Upper bench
-    let bench_upper = polyline_from_points(vec![
-        Point::new(WALL_THICKNESS + 0.10, deck_y + BUILDING_DEPTH - WALL_THICKNESS - BENCH_DEPTH * 2.0 - 0.10, 0.0),
-        Point::new(SAUNA_WIDTH - WALL_THICKNESS - 0.10, deck_y + BUILDING_DEPTH - WALL_THICKNESS - BENCH_DEPTH * 2.0 - 0.10, 0.0),
-        Point::new(SAUNA_WIDTH - WALL_THICKNESS - 0.10, deck_y + BUILDING_DEPTH - WALL_THICKNESS - BENCH_DEPTH - 0.20, 0.0),
-        Point::new(WALL_THICKNESS + 0.10, deck_y + BUILDING_DEPTH - WALL_THICKNESS - BENCH_DEPTH - 0.20, 0.0),
-        Point::new(WALL_THICKNESS + 0.10, deck_y + BUILDING_DEPTH - WALL_THICKNESS - BENCH_DEPTH * 2.0 - 0.10, 0.0),
-    ]);
-    let mut entity = Entity::new(EntityType::LwPolyline(bench_upper));
-    entity.common.layer = LAYER_FIXTURES.to_string();
-    drawing.add_entity(entity);
+    for furnishing in &model.furnishings {
+        let specific = match furnishing.footprint {
+            Footprint::Circle { x, y, radius } => EntityType::Circle(Circle {
+                center: Point::new(x, y, 0.0),
+                radius,
+                ..Default::default()
+            }),
+            Footprint::Rect { x1, y1, x2, y2 } => EntityType::LwPolyline(polyline_from_points(vec![
+                Point::new(x1, y1, 0.0),
+                Point::new(x2, y1, 0.0),
+                Point::new(x2, y2, 0.0),
+                Point::new(x1, y2, 0.0),
+                Point::new(x1, y1, 0.0),
+            ])),
+        };
+        let mut entity = Entity::new(specific);
+        entity.common.layer = LAYER_FIXTURES.to_string();
+        drawing.add_entity(entity);
+    }
 }
 
-fn draw_room_labels(drawing: &mut Drawing) {
-    let deck_y = DECK_DEPTH;
-    let building_top = deck_y + BUILDING_DEPTH;
-
-    // Sauna label
-    draw_room_label(
-        drawing,
-        SAUNA_WIDTH / 2.0,
-        (deck_y + building_top) / 2.0,
-        "SAUNA",
-        sauna_area(),
-    );
-
-    // Wash label
-    draw_room_label(
-        drawing,
-        SAUNA_WIDTH + WASH_WIDTH / 2.0,
-        (deck_y + building_top) / 2.0,
-        "WASH",
-        wash_area(),
-    );
-
-    // Seating label
-    draw_room_label(
-        drawing,
-        SAUNA_WIDTH + WASH_WIDTH + SEATING_WIDTH / 2.0,
-        (deck_y + building_top) / 2.0,
-        "CHANGING",
-        seating_area(),
-    );
-
-    // Deck label (covered portion)
-    draw_room_label(
-        drawing,
-        ENCLOSED_WIDTH + OUTDOOR_SEATING_WIDTH / 2.0,
-        (deck_y + building_top) / 2.0,
-        "DECK",
-        outdoor_area(),
-    );
-
-    // Deck label (open terrace strip)
-    draw_room_label(
-        drawing,
-        TOTAL_WIDTH / 3.0,
-        DECK_DEPTH / 2.0,
-        "TERRACE",
-        deck_area(),
-    );
+fn draw_room_labels(drawing: &mut Drawing, model: &BuildingModel) {
+    for room in &model.rooms {
+        draw_room_label(drawing, room.label_x, room.label_y, room.name, room.area());
+    }
 
     // Hot tub label
-    let hot_tub_x = TOTAL_WIDTH - HOT_TUB_OFFSET_X;
-    let hot_tub_y = -HOT_TUB_RADIUS + HOT_TUB_OFFSET_Y;
+    let tub = hot_tub_shell();
     let ht_label = Text {
-        location: Point::new(hot_tub_x - 0.30, hot_tub_y, 0.0),
+        location: Point::new(tub.x - 0.30, tub.y, 0.0),
         text_height: DIM_TEXT_HEIGHT,
-        value: format!("HOT TUB\n{}m dia.", HOT_TUB_DIAMETER),
+        value: format!("HOT TUB\n{}", hot_tub_size()),
         ..Default::default()
     };
     let mut entity = Entity::new(EntityType::Text(ht_label));
@@ -362,7 +336,7 @@ fn draw_dimensions(drawing: &mut Drawing) {
     // =========================================================================
 
     // Overall width
-    draw_dim_horizontal(drawing, 0.0, TOTAL_WIDTH, -HOT_TUB_RADIUS - 0.50, -DIM_OFFSET * 2.0, "");
+    draw_dim_horizontal(drawing, 0.0, TOTAL_WIDTH, -HOT_TUB_SIZE.1 / 2.0 - 0.50, -DIM_OFFSET * 2.0, "");
 
     // Room widths (chain dimension)
     // Sauna width
@@ -407,19 +381,16 @@ fn draw_dimensions(drawing: &mut Drawing) {
 }
 
 fn draw_annotations(drawing: &mut Drawing) {
-    // North arrow
-    draw_north_arrow(drawing, -1.0, DECK_DEPTH + BUILDING_DEPTH + 0.50, 0.50);
-
-    // Title block
-    draw_title_block(drawing, TOTAL_WIDTH - 4.0, -HOT_TUB_RADIUS - 2.0, 4.0, 1.5);
+    // North arrow, turned to true north for the building's orientation on the site
+    draw_north_arrow(drawing, -1.0, DECK_DEPTH + BUILDING_DEPTH + 0.50, 0.50, -SITE_ROTATION);
 
     // Drawing border
     let border = polyline_from_points(vec![
-        Point::new(-2.0, -HOT_TUB_RADIUS - 2.5, 0.0),
-        Point::new(TOTAL_WIDTH + 1.0, -HOT_TUB_RADIUS - 2.5, 0.0),
+        Point::new(-2.0, -HOT_TUB_SIZE.1 / 2.0 - 2.5, 0.0),
+        Point::new(TOTAL_WIDTH + 1.0, -HOT_TUB_SIZE.1 / 2.0 - 2.5, 0.0),
         Point::new(TOTAL_WIDTH + 1.0, DECK_DEPTH + BUILDING_DEPTH + 1.5, 0.0),
         Point::new(-2.0, DECK_DEPTH + BUILDING_DEPTH + 1.5, 0.0),
-        Point::new(-2.0, -HOT_TUB_RADIUS - 2.5, 0.0),
+        Point::new(-2.0, -HOT_TUB_SIZE.1 / 2.0 - 2.5, 0.0),
     ]);
     let mut entity = Entity::new(EntityType::LwPolyline(border));
     entity.common.layer = LAYER_TITLE.to_string();
@@ -0,0 +1,328 @@
+use dxf::{Drawing, Point};
+
+use crate::constants::*;
+use crate::helpers::{add_circle, add_line, add_polygon, add_rectangle, add_rotated_text, add_text};
+use crate::layers::*;
+use crate::hot_tub::draw_hot_tub_outline;
+use crate::model::{BuildingModel, FixtureKind, Pipe, PipeService};
+
+// ============================================================================
+// PLUMBING
+// Supply runs from the water service under the floor to the shower valve and
+// the hot tub fill; the shower floor drain falls to the sewer connection north
+// of the building with its vent taken off the trap arm up through the roof.
+// Drains are checked for fall on every horizontal run and floor drains for a
+// vent within TRAP_ARM_MAX. The plumbing plan draws runs in plan with risers
+// as circles; heights are in the schedule.
+// ============================================================================
+
+/// Riser circle and fixture symbol size in plan
+const RISER_RADIUS: f64 = 0.05;
+const SYMBOL_SIZE: f64 = 0.16;
+
+/// Fall of one horizontal drain segment
+pub struct DrainSlope {
+    pub pipe: &'static str,
+    pub segment: usize, // 1-based, in the pipe's point order
+    pub run: f64,       // m, horizontal
+    pub fall: f64,      // m, negative if it runs uphill
+}
+
+impl DrainSlope {
+    pub fn slope(&self) -> f64 {
+        self.fall / self.run
+    }
+}
+
+pub struct PlumbingCheck {
+    pub slopes: Vec<DrainSlope>,
+    pub problems: Vec<String>,
+}
+
+/// Nominal pipe size in inches with vulgar fractions, as on the drawings
+pub fn nominal_size(inches: f64) -> String {
+    let whole = inches.trunc();
+    let fraction = match ((inches - whole) * 4.0).round() as i64 {
+        1 => "¼",
+        2 => "½",
+        3 => "¾",
+        _ => "",
+    };
+    if whole == 0.0 {
+        format!("{}\"", fraction)
+    } else {
+        format!("{:.0}{}\"", whole, fraction)
+    }
+}
+
+impl PipeService {
+    pub fn name(self) -> &'static str {
+        match self {
+            PipeService::Supply => "SUPPLY",
+            PipeService::Drain => "DRAIN",
+            PipeService::Vent => "VENT",
+        }
+    }
+}
+
+fn fixture_code(kind: FixtureKind) -> &'static str {
+    match kind {
+        FixtureKind::ServiceEntry => "WS",
+        FixtureKind::ShowerValve => "MV",
+        FixtureKind::ShowerHead => "SH",
+        FixtureKind::FloorDrain => "FD",
+        FixtureKind::FillValve => "FV",
+        FixtureKind::DrainValve => "DV",
+        FixtureKind::SewerConnection => "SC",
+    }
+}
+
+fn horizontal(a: &Point, b: &Point) -> f64 {
+    ((b.x - a.x).powi(2) + (b.y - a.y).powi(2)).sqrt()
+}
+
+fn same_plan_point(a: &Point, b: &Point) -> bool {
+    horizontal(a, b) < 0.01
+}
+
+impl Pipe {
+    /// Developed length in m, risers included
+    pub fn length(&self) -> f64 {
+        self.points
+            .windows(2)
+            .map(|w| (horizontal(&w[0], &w[1]).powi(2) + (w[1].z - w[0].z).powi(2)).sqrt())
+            .sum()
+    }
+}
+
+/// Drain falls and vent distances for the model's pipes
+pub fn plumbing_check(model: &BuildingModel) -> PlumbingCheck {
+    let mut slopes = Vec::new();
+    let mut problems = Vec::new();
+
+    for pipe in model.pipes.iter().filter(|p| p.service == PipeService::Drain) {
+        for (i, w) in pipe.points.windows(2).enumerate() {
+            let run = horizontal(&w[0], &w[1]);
+            if run < 0.01 {
+                continue; // Stack or drop
+            }
+            let slope = DrainSlope { pipe: pipe.id, segment: i + 1, run, fall: w[0].z - w[1].z };
+            if slope.slope() < 0.0 {
+                problems.push(format!("{} SEGMENT {} RUNS UPHILL", pipe.id.to_uppercase(), slope.segment));
+            } else if slope.slope() < DRAIN_MIN_SLOPE {
+                problems.push(format!(
+                    "{} SEGMENT {}: {:.1}% FALL (MIN {:.1}%)",
+                    pipe.id.to_uppercase(),
+                    slope.segment,
+                    slope.slope() * 100.0,
+                    DRAIN_MIN_SLOPE * 100.0
+                ));
+            }
+            slopes.push(slope);
+        }
+    }
+
+    // Every floor drain needs a drain from it and a vent off that drain's trap arm
+    for fixture in model.fixtures.iter().filter(|f| f.kind == FixtureKind::FloorDrain) {
+        let name = fixture.id.to_uppercase();
+        let at = Point::new(fixture.x, fixture.y, fixture.z);
+        let Some(drain) = model
+            .pipes
+            .iter()
+            .find(|p| p.service == PipeService::Drain && same_plan_point(&p.points[0], &at))
+        else {
+            problems.push(format!("{}: NO DRAIN", name));
+            continue;
+        };
+
+        let mut arm = 0.0;
+        let mut vented = None;
+        for (i, point) in drain.points.iter().enumerate() {
+            if i > 0 {
+                arm += horizontal(&drain.points[i - 1], point);
+            }
+            let vent = model.pipes.iter().any(|p| {
+                p.service == PipeService::Vent
+                    && same_plan_point(&p.points[0], point)
+                    && (p.points[0].z - point.z).abs() < 0.01
+            });
+            if vent {
+                vented = Some(arm);
+                break;
+            }
+        }
+        match vented {
+            None => problems.push(format!("{}: {} IS NOT VENTED", name, drain.id.to_uppercase())),
+            Some(arm) if arm > TRAP_ARM_MAX => problems.push(format!(
+                "{}: TRAP ARM {:.2} m TO ITS VENT (MAX {:.2} m)",
+                name, arm, TRAP_ARM_MAX
+            )),
+            Some(_) => {}
+        }
+    }
+
+    PlumbingCheck { slopes, problems }
+}
+
+impl PlumbingCheck {
+    /// Report lines for the sheet and the console
+    pub fn lines(&self, model: &BuildingModel) -> Vec<String> {
+        let mut lines = Vec::new();
+        for pipe in &model.pipes {
+            lines.push(format!(
+                "{:<6} {:<16} {:<4} {:>5.2} m",
+                pipe.service.name(),
+                pipe.id.to_uppercase(),
+                nominal_size(pipe.size),
+                pipe.length()
+            ));
+        }
+        for slope in &self.slopes {
+            lines.push(format!(
+                "{} SEGMENT {}: {:.2} m FALLS {:.0} mm = {:.1}%",
+                slope.pipe.to_uppercase(),
+                slope.segment,
+                slope.run,
+                slope.fall * 1000.0,
+                slope.slope() * 100.0
+            ));
+        }
+        if self.problems.is_empty() {
+            lines.push(format!(
+                "DRAINS FALL AT LEAST {:.1}% AND FLOOR DRAINS ARE VENTED",
+                DRAIN_MIN_SLOPE * 100.0
+            ));
+        }
+        lines.extend(self.problems.iter().map(|problem| format!("WARNING: {}", problem)));
+        lines
+    }
+}
+
+fn pipe_layer(service: PipeService) -> &'static str {
+    match service {
+        PipeService::Supply => LAYER_PLUMB_SUPPLY,
+        PipeService::Drain => LAYER_PLUMB_DRAIN,
+        PipeService::Vent => LAYER_PLUMB_VENT,
+    }
+}
+
+/// Plan symbol for a fixture centred at (x, y)
+fn draw_fixture_symbol(drawing: &mut Drawing, kind: FixtureKind, x: f64, y: f64) {
+    let s = SYMBOL_SIZE / 2.0;
+    match kind {
+        // Floor drain: grate with a cross
+        FixtureKind::FloorDrain => {
+            add_rectangle(drawing, LAYER_PLUMB_FIXTURES, x - s, y - s, x + s, y + s);
+            add_line(drawing, LAYER_PLUMB_FIXTURES, x - s, y - s, x + s, y + s);
+            add_line(drawing, LAYER_PLUMB_FIXTURES, x - s, y + s, x + s, y - s);
+        }
+        // Valves: bow tie
+        FixtureKind::ShowerValve | FixtureKind::FillValve | FixtureKind::DrainValve => {
+            let points = [(x - s, y - s * 0.6), (x + s, y + s * 0.6), (x + s, y - s * 0.6), (x - s, y + s * 0.6)];
+            add_polygon(drawing, LAYER_PLUMB_FIXTURES, &points);
+        }
+        _ => add_circle(drawing, LAYER_PLUMB_FIXTURES, x, y, s),
+    }
+}
+
+/// Tag along a run, reading left to right or upwards, just clear of the line
+/// on its left
+fn add_pipe_tag(drawing: &mut Drawing, x: f64, y: f64, a: &Point, b: &Point, value: String) {
+    let run = horizontal(a, b);
+    let (mut ux, mut uy) = ((b.x - a.x) / run, (b.y - a.y) / run);
+    let mut start = a;
+    if ux < -1e-9 || (ux.abs() < 1e-9 && uy < 0.0) {
+        (ux, uy, start) = (-ux, -uy, b);
+    }
+    add_rotated_text(
+        drawing,
+        LAYER_PLUMB_ANNO,
+        x + start.x + ux * 0.10 - uy * 0.05,
+        y + start.y + uy * 0.10 + ux * 0.05,
+        DIM_TEXT_HEIGHT * 0.8,
+        uy.atan2(ux).to_degrees(),
+        value,
+    );
+}
+
+/// Plumbing plan: walls, deck and hot tub as background, pipe runs with size
+/// and fall tags, risers and fixtures. The model origin is drawn at (x, y).
+pub fn create_plumbing_plan(drawing: &mut Drawing, model: &BuildingModel, x: f64, y: f64) {
+    add_rectangle(drawing, LAYER_DECK, x, y, x + TOTAL_WIDTH, y + DECK_DEPTH + BUILDING_DEPTH);
+    for wall in &model.walls {
+        let layer = if wall.exterior { LAYER_WALLS } else { LAYER_WALLS_INNER };
+        add_rectangle(drawing, layer, x + wall.x1, y + wall.y1, x + wall.x2, y + wall.y2);
+    }
+    draw_hot_tub_outline(drawing, |tub_x, tub_y| (x + tub_x, y + tub_y));
+
+    for pipe in &model.pipes {
+        let layer = pipe_layer(pipe.service);
+        let mut tagged = false;
+        for w in pipe.points.windows(2) {
+            let (a, b) = (&w[0], &w[1]);
+            if same_plan_point(a, b) {
+                add_circle(drawing, layer, x + a.x, y + a.y, RISER_RADIUS);
+                continue;
+            }
+            add_line(drawing, layer, x + a.x, y + a.y, x + b.x, y + b.y);
+
+            // Size on the first run of each pipe, fall on every drain run
+            let mut tag = if tagged { String::new() } else { nominal_size(pipe.size) };
+            tagged = true;
+            if pipe.service == PipeService::Drain {
+                let slope = (a.z - b.z) / horizontal(a, b);
+                tag = format!("{} @ {:.1}%", tag, slope * 100.0).trim().to_string();
+            }
+            if tag.is_empty() {
+                continue;
+            }
+            add_pipe_tag(drawing, x, y, a, b, tag);
+        }
+        if pipe.points.len() == 2 && same_plan_point(&pipe.points[0], &pipe.points[1]) {
+            let a = &pipe.points[0];
+            add_text(
+                drawing,
+                LAYER_PLUMB_ANNO,
+                x + a.x - 0.60,
+                y + a.y + 0.10,
+                DIM_TEXT_HEIGHT * 0.8,
+                format!("{} {}", nominal_size(pipe.size), pipe.service.name()),
+            );
+        }
+    }
+
+    // Fixtures at the same plan point get their codes stacked
+    for (i, fixture) in model.fixtures.iter().enumerate() {
+        draw_fixture_symbol(drawing, fixture.kind, x + fixture.x, y + fixture.y);
+        let at = Point::new(fixture.x, fixture.y, 0.0);
+        let below = model.fixtures[..i]
+            .iter()
+            .filter(|f| same_plan_point(&Point::new(f.x, f.y, 0.0), &at))
+            .count();
+        add_text(
+            drawing,
+            LAYER_PLUMB_ANNO,
+            x + fixture.x + SYMBOL_SIZE * 0.7,
+            y + fixture.y - SYMBOL_SIZE * 0.5 - below as f64 * DIM_TEXT_HEIGHT,
+            DIM_TEXT_HEIGHT * 0.8,
+            fixture_code(fixture.kind).to_string(),
+        );
+    }
+
+    add_text(
+        drawing,
+        LAYER_TEXT,
+        x,
+        y + DECK_DEPTH + BUILDING_DEPTH + 1.05,
+        LABEL_TEXT_HEIGHT,
+        "PLUMBING PLAN".to_string(),
+    );
+    add_text(
+        drawing,
+        LAYER_PLUMB_ANNO,
+        x,
+        y + DECK_DEPTH + BUILDING_DEPTH + 0.80,
+        DIM_TEXT_HEIGHT,
+        "WS WATER SERVICE, MV MIXING VALVE, SH SHOWER HEAD, FD FLOOR DRAIN, FV FILL, DV DRAIN VALVE, SC SEWER".to_string(),
+    );
+}
@@ -0,0 +1,310 @@
+use dxf::Drawing;
+
+use crate::constants::*;
+use crate::helpers::add_rectangle;
+use crate::layers::*;
+use crate::hot_tub::hot_tub_shell;
+use crate::model::BuildingModel;
+use crate::stairs::stair_schedule;
+
+// ============================================================================
+// DECK RAILINGS
+// Guards along the deck edges in DECK_RAILINGS. Each edge is broken where an
+// exterior wall stands on it, at the stairs and at the hot tub surround, and
+// every open stretch left gets posts at no more than RAILING_POST_SPACING,
+// evenly spaced, a top and a bottom rail, and enough balusters per bay to keep
+// the clear gap under BALUSTER_MAX_GAP. Stretches shorter than
+// RAILING_MIN_LENGTH are left open rather than railed as a stub. Open
+// stretches are flagged once the deck stands higher than GUARD_REQUIRED_ABOVE.
+// ============================================================================
+
+/// Surround of the hot tub in plan, beyond its shell
+const HOT_TUB_SURROUND: f64 = 0.10;
+
+/// Railing along one open stretch of a deck edge
+pub struct Railing {
+    pub edge: DeckEdge,
+    pub line: f64,       // Plan x or y of the deck edge
+    pub start: f64,      // Along the edge, plan x or y
+    pub end: f64,
+    pub posts: Vec<f64>, // Post centres along the edge
+    pub balusters_per_bay: u32,
+    pub gap: f64,        // Clear gap between balusters
+}
+
+pub struct RailingCheck {
+    pub railings: Vec<Railing>,
+    pub left_open: Vec<(DeckEdge, f64, f64)>, // Stretches too short for a railing
+    pub problems: Vec<String>,
+}
+
+impl Railing {
+    pub fn length(&self) -> f64 {
+        self.end - self.start
+    }
+
+    pub fn balusters(&self) -> u32 {
+        self.balusters_per_bay * (self.posts.len() as u32 - 1)
+    }
+
+    /// Plan point at `along` the edge, `inset` in from the deck edge
+    fn plan_point(&self, along: f64, inset: f64) -> (f64, f64) {
+        match self.edge {
+            DeckEdge::North => (along, self.line - inset),
+            DeckEdge::East => (self.line - inset, along),
+            DeckEdge::South => (along, self.line + inset),
+            DeckEdge::West => (self.line + inset, along),
+        }
+    }
+}
+
+/// Baluster length between the bottom and the top rail
+pub fn baluster_length() -> f64 {
+    RAILING_HEIGHT - RAIL_SIZE.0 - RAILING_BOTTOM_GAP - RAIL_SIZE.0
+}
+
+/// Deck edge line, and the stretch of it the deck spans, for each edge
+fn edge_extent(model: &BuildingModel, edge: DeckEdge) -> (f64, f64, f64) {
+    let (x1, y1, x2, y2) = (model.deck.min.x, model.deck.min.y, model.deck.max.x, model.deck.max.y);
+    match edge {
+        DeckEdge::North => (y2, x1, x2),
+        DeckEdge::East => (x2, y1, y2),
+        DeckEdge::South => (y1, x1, x2),
+        DeckEdge::West => (x1, y1, y2),
+    }
+}
+
+/// Stretches of a deck edge clear of walls, stairs and the hot tub
+fn open_stretches(model: &BuildingModel, edge: DeckEdge) -> Vec<(f64, f64)> {
+    let (line, start, end) = edge_extent(model, edge);
+    let across = |x: f64, y: f64| match edge {
+        DeckEdge::North | DeckEdge::South => (x, y),
+        DeckEdge::East | DeckEdge::West => (y, x),
+    };
+
+    // Plan boxes standing on the edge, as (along1, across1, along2, across2)
+    let mut boxes: Vec<(f64, f64, f64, f64)> = model
+        .walls
+        .iter()
+        .filter(|wall| wall.exterior)
+        .map(|wall| {
+            let (a1, c1) = across(wall.x1, wall.y1);
+            let (a2, c2) = across(wall.x2, wall.y2);
+            (a1, c1, a2, c2)
+        })
+        .collect();
+    for stair in stair_schedule(model).stairs {
+        let (x1, y1, x2, y2) = stair.footprint;
+        let (a1, c1) = across(x1, y1);
+        let (a2, c2) = across(x2, y2);
+        boxes.push((a1, c1, a2, c2));
+    }
+    let (tx1, ty1, tx2, ty2) = hot_tub_shell().inset(-HOT_TUB_SURROUND).bounds();
+    let (a1, c1) = across(tx1, ty1);
+    let (a2, c2) = across(tx2, ty2);
+    boxes.push((a1, c1, a2, c2));
+
+    let mut blocked: Vec<(f64, f64)> = boxes
+        .into_iter()
+        .filter(|&(_, c1, _, c2)| c1 <= line + 1e-9 && c2 >= line - 1e-9)
+        .map(|(a1, _, a2, _)| (a1.max(start), a2.min(end)))
+        .filter(|(a1, a2)| a2 > a1)
+        .collect();
+    blocked.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut stretches = Vec::new();
+    let mut from = start;
+    for (a1, a2) in blocked {
+        if a1 > from {
+            stretches.push((from, a1));
+        }
+        from = from.max(a2);
+    }
+    if end > from {
+        stretches.push((from, end));
+    }
+    stretches.retain(|(a1, a2)| a2 - a1 > 1e-6);
+    stretches
+}
+
+/// Railings along the chosen deck edges, and the guard rules
+pub fn railing_check(model: &BuildingModel) -> RailingCheck {
+    let mut railings = Vec::new();
+    let mut left_open = Vec::new();
+    let mut problems = Vec::new();
+    for edge in [DeckEdge::North, DeckEdge::East, DeckEdge::South, DeckEdge::West] {
+        let (line, _, _) = edge_extent(model, edge);
+        for (start, end) in open_stretches(model, edge) {
+            let short = end - start < RAILING_MIN_LENGTH;
+            if short && DECK_RAILINGS.contains(&edge) {
+                left_open.push((edge, start, end));
+            }
+            if short || !DECK_RAILINGS.contains(&edge) {
+                if DECK_ELEV_HEIGHT > GUARD_REQUIRED_ABOVE {
+                    problems.push(format!(
+                        "OPEN {} EDGE {:.2}-{:.2} m NEEDS A GUARD, DECK {:.2} m ABOVE GRADE",
+                        edge.name(),
+                        start,
+                        end,
+                        DECK_ELEV_HEIGHT
+                    ));
+                }
+                continue;
+            }
+
+            // Posts evenly spaced between the end posts, flush with the stretch ends
+            let span = end - start - RAILING_POST_SIZE;
+            let bays = ((span / RAILING_POST_SPACING - 1e-9).ceil() as u32).max(1);
+            let centres = span / bays as f64;
+            let posts = (0..=bays).map(|i| start + RAILING_POST_SIZE / 2.0 + i as f64 * centres).collect();
+            let clear = centres - RAILING_POST_SIZE;
+            let balusters_per_bay = ((clear - BALUSTER_MAX_GAP) / (BALUSTER_SIZE + BALUSTER_MAX_GAP) - 1e-9).ceil() as u32;
+            let gap = (clear - balusters_per_bay as f64 * BALUSTER_SIZE) / (balusters_per_bay + 1) as f64;
+            if gap > BALUSTER_MAX_GAP + 1e-9 {
+                problems.push(format!(
+                    "{} RAILING {:.2}-{:.2} m: BALUSTER GAP {:.0} mm OVER {:.0} mm",
+                    edge.name(),
+                    start,
+                    end,
+                    gap * 1000.0,
+                    BALUSTER_MAX_GAP * 1000.0
+                ));
+            }
+            railings.push(Railing { edge, line, start, end, posts, balusters_per_bay, gap });
+        }
+    }
+
+    if RAILING_HEIGHT < RAILING_MIN_HEIGHT {
+        problems.push(format!(
+            "RAILING HEIGHT {:.2} m UNDER {:.2} m",
+            RAILING_HEIGHT, RAILING_MIN_HEIGHT
+        ));
+    }
+    if RAILING_BOTTOM_GAP > BALUSTER_MAX_GAP {
+        problems.push(format!(
+            "GAP UNDER THE BOTTOM RAIL {:.0} mm OVER {:.0} mm",
+            RAILING_BOTTOM_GAP * 1000.0,
+            BALUSTER_MAX_GAP * 1000.0
+        ));
+    }
+    RailingCheck { railings, left_open, problems }
+}
+
+impl RailingCheck {
+    /// Post positions in plan to the millimetre, with corner posts shared
+    /// between two railings counted once
+    pub fn post_positions(&self) -> Vec<(i64, i64)> {
+        let mut posts: Vec<(i64, i64)> = self
+            .railings
+            .iter()
+            .flat_map(|railing| {
+                railing.posts.iter().map(|&along| {
+                    let (x, y) = railing.plan_point(along, RAILING_POST_SIZE / 2.0);
+                    ((x * 1000.0).round() as i64, (y * 1000.0).round() as i64)
+                })
+            })
+            .collect();
+        posts.sort();
+        posts.dedup();
+        posts
+    }
+
+    /// Report lines for the sheet and the console
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for railing in &self.railings {
+            lines.push(format!(
+                "{:<6} {:>5.2}-{:>5.2} m  {} POSTS AT {:.2} m  {} BALUSTERS  GAP {:.0} mm",
+                railing.edge.name(),
+                railing.start,
+                railing.end,
+                railing.posts.len(),
+                railing.posts[1] - railing.posts[0],
+                railing.balusters(),
+                railing.gap * 1000.0
+            ));
+        }
+        for &(edge, start, end) in &self.left_open {
+            lines.push(format!(
+                "{:<6} {:>5.2}-{:>5.2} m  LEFT OPEN, SHORTER THAN {:.2} m",
+                edge.name(),
+                start,
+                end,
+                RAILING_MIN_LENGTH
+            ));
+        }
+        lines.push(format!(
+            "{} POSTS, {} BALUSTERS, RAILING {:.2} m HIGH",
+            self.post_positions().len(),
+            self.railings.iter().map(Railing::balusters).sum::<u32>(),
+            RAILING_HEIGHT
+        ));
+        if self.problems.is_empty() {
+            lines.push("RAILING HEIGHT AND BALUSTER GAPS WITHIN LIMITS".to_string());
+        }
+        lines.extend(self.problems.iter().map(|problem| format!("WARNING: {}", problem)));
+        lines
+    }
+}
+
+/// Railings in the floor plan: the rails between the end posts and every post
+pub fn draw_railings_plan(drawing: &mut Drawing, check: &RailingCheck) {
+    let half = RAILING_POST_SIZE / 2.0;
+    for railing in &check.railings {
+        let (x1, y1) = railing.plan_point(railing.start, half - RAIL_SIZE.1 / 2.0);
+        let (x2, y2) = railing.plan_point(railing.end, half + RAIL_SIZE.1 / 2.0);
+        add_rectangle(drawing, LAYER_RAILINGS, x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2));
+        for &along in &railing.posts {
+            let (x, y) = railing.plan_point(along, half);
+            add_rectangle(drawing, LAYER_RAILINGS, x - half, y - half, x + half, y + half);
+        }
+    }
+}
+
+/// A railing seen square on, with `along` mapped to the view x and the deck
+/// surface at `deck_y`: posts, rails and balusters
+fn draw_railing_face(drawing: &mut Drawing, railing: &Railing, view_x: impl Fn(f64) -> f64, deck_y: f64) {
+    let (a1, a2) = (view_x(railing.start), view_x(railing.end));
+    let (left, right) = (a1.min(a2), a1.max(a2));
+    let top = deck_y + RAILING_HEIGHT;
+    let bottom = deck_y + RAILING_BOTTOM_GAP;
+    add_rectangle(drawing, LAYER_RAILINGS, left, top - RAIL_SIZE.0, right, top);
+    add_rectangle(drawing, LAYER_RAILINGS, left, bottom, right, bottom + RAIL_SIZE.0);
+
+    let half = RAILING_POST_SIZE / 2.0;
+    for (i, &along) in railing.posts.iter().enumerate() {
+        let x = view_x(along);
+        add_rectangle(drawing, LAYER_RAILINGS, x - half, deck_y, x + half, top);
+
+        // Balusters evenly spaced in the bay to the next post
+        let Some(&next) = railing.posts.get(i + 1) else { continue };
+        let pitch = (next - along - RAILING_POST_SIZE - railing.gap) / railing.balusters_per_bay.max(1) as f64;
+        for j in 0..railing.balusters_per_bay {
+            let b = along + half + railing.gap + j as f64 * pitch;
+            let (b1, b2) = (view_x(b), view_x(b + BALUSTER_SIZE));
+            add_rectangle(drawing, LAYER_RAILINGS, b1.min(b2), bottom + RAIL_SIZE.0, b1.max(b2), top - RAIL_SIZE.0);
+        }
+    }
+}
+
+/// A railing seen end on at view x, as its end post
+fn draw_railing_end(drawing: &mut Drawing, x: f64, deck_y: f64) {
+    let half = RAILING_POST_SIZE / 2.0;
+    add_rectangle(drawing, LAYER_RAILINGS, x - half, deck_y, x + half, deck_y + RAILING_HEIGHT);
+}
+
+/// Railings in the front elevation, which looks north: south railings square
+/// on, east and west railings end on; north railings stand behind the south
+/// ones and are left out
+pub fn draw_railings_front_elevation(drawing: &mut Drawing, check: &RailingCheck, offset_x: f64, offset_y: f64) {
+    let deck_y = offset_y + DECK_ELEV_HEIGHT;
+    let half = RAILING_POST_SIZE / 2.0;
+    for railing in &check.railings {
+        match railing.edge {
+            DeckEdge::South => draw_railing_face(drawing, railing, |along| offset_x + along, deck_y),
+            DeckEdge::East => draw_railing_end(drawing, offset_x + railing.line - half, deck_y),
+            DeckEdge::West => draw_railing_end(drawing, offset_x + railing.line + half, deck_y),
+            DeckEdge::North => {}
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use dxf::enums::AcadVersion;
+use dxf::Drawing;
+
+use crate::benches::{bench_check, create_bench_section, draw_benches_plan, draw_benches_section};
+use crate::ceiling::{create_reflected_ceiling_plan, lighting_schedule};
+use crate::constants::*;
+use crate::diff::{add_model_record, entity_extents};
+use crate::electrical::{create_electrical_plan, panel_schedule};
+use crate::foundation::{create_foundation_plan, draw_foundation_section, foundation};
+use crate::framing::{create_framing_plan, deck_framing};
+use crate::heaters::{draw_heater_front_elevation, draw_heater_plan, draw_heater_roof_plan, draw_heater_section, heater_check};
+use crate::helpers::{draw_note_block, draw_revision_clouds, draw_revision_table, Extents};
+use crate::layers::*;
+use crate::loads::hot_tub_load;
+use crate::model::BuildingModel;
+use crate::model3d::draw_model_3d;
+use crate::plan::draw_floor_plan;
+use crate::plumbing::{create_plumbing_plan, plumbing_check};
+use crate::railings::{draw_railings_front_elevation, draw_railings_plan, railing_check};
+use crate::site::{create_site_plan, site_check};
+use crate::stairs::{create_stair_section, draw_stairs_front_elevation, draw_stairs_plan, stair_schedule};
+use crate::sun::{create_shadow_study, sun_study};
+use crate::thermal::sauna_heat_loss;
+use crate::title_block::TitleBlockTemplate;
+use crate::ventilation::{draw_vents_front_elevation, draw_vents_plan, draw_vents_section, sauna_ventilation};
+use crate::views::*;
+use crate::wall_framing::create_wall_framing_elevations;
+
+/// Where the site origin sits on the sheet: the site plan hangs below the
+/// foundation plan, so its lot top clears the foundation dimensions
+pub fn site_plan_origin() -> (f64, f64) {
+    let framing_y = -(DECK_DEPTH + BUILDING_DEPTH) - 6.0;
+    let lot_top = LOT_LINES.iter().map(|line| line.y).fold(0.0, f64::max);
+    (0.0, framing_y - 43.0 - lot_top)
+}
+
+/// Draw a view and return the extents of what it added
+fn measured(drawing: &mut Drawing, draw: impl FnOnce(&mut Drawing)) -> Option<Extents> {
+    let start = drawing.entities().count();
+    draw(drawing);
+    drawing.entities().skip(start).filter_map(entity_extents).reduce(|a, b| {
+        (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3))
+    })
+}
+
+/// Build the complete drawing sheet: plan, elevations, section and
+/// presentation views, plus the extruded 3D model when requested
+pub fn build_drawing(model: &BuildingModel, model_3d: bool, title_block: &TitleBlockTemplate) -> Drawing {
+    let mut drawing = Drawing::new();
+    drawing.header.version = AcadVersion::R2013;
+
+    // Set up drawing units (meters)
+    drawing.header.default_drawing_units = dxf::enums::Units::Meters;
+
+    // Set up all layers
+    setup_layers(&mut drawing);
+
+    // Model elements for a later diff
+    add_model_record(&mut drawing, model);
+
+    // Draw floor plan with all elements
+    let floor_plan = measured(&mut drawing, |drawing| draw_floor_plan(drawing, model));
+
+    // Title block in the lower-right corner of the plan, revision table to its left
+    let title_x = TOTAL_WIDTH - title_block.width;
+    let title_y = -HOT_TUB_SIZE.1 / 2.0 - 2.0;
+    title_block.draw(&mut drawing, title_x, title_y);
+    draw_revision_table(&mut drawing, title_x, title_y, title_block.width);
+
+    // Create elevation views offset from plan view
+    let offset_x = TOTAL_WIDTH + 2.0;
+    let offset_y = DECK_DEPTH;
+
+    let front = measured(&mut drawing, |drawing| create_front_elevation(drawing, offset_x, offset_y));
+    create_top_view(&mut drawing, offset_x, offset_y - 5.0);
+    let side = measured(&mut drawing, |drawing| create_side_elevation(drawing, offset_x + 5.0, offset_y));
+    let section = measured(&mut drawing, |drawing| create_section_cut(drawing, offset_x + 8.0, offset_y));
+    let iso = measured(&mut drawing, |drawing| create_iso_view(drawing, model, offset_x + 12.0, offset_y));
+
+    // Sauna heater flue in plan, roof plan, front elevation and section
+    draw_heater_plan(&mut drawing, model);
+    draw_heater_roof_plan(&mut drawing, model, offset_x, offset_y - 5.0);
+    draw_heater_front_elevation(&mut drawing, model, offset_x, offset_y);
+    draw_heater_section(&mut drawing, model, offset_x + 8.0, offset_y);
+
+    // Sauna vents in plan, front elevation and section
+    draw_vents_plan(&mut drawing, model);
+    draw_vents_front_elevation(&mut drawing, model, offset_x, offset_y);
+    draw_vents_section(&mut drawing, model, offset_x + 8.0, offset_y);
+
+    // Sauna benches in plan and section
+    let benches = bench_check(model);
+    draw_benches_plan(&mut drawing, &benches);
+    draw_benches_section(&mut drawing, model, &benches, offset_x + 8.0, offset_y);
+
+    // Deck stairs in plan and front elevation
+    let stairs = stair_schedule(model);
+    draw_stairs_plan(&mut drawing, &stairs);
+    draw_stairs_front_elevation(&mut drawing, &stairs, offset_x, offset_y);
+
+    // Deck railings in plan and front elevation
+    let railings = railing_check(model);
+    draw_railings_plan(&mut drawing, &railings);
+    draw_railings_front_elevation(&mut drawing, &railings, offset_x, offset_y);
+
+    // Deck framing plan below the floor plan
+    let framing = deck_framing(model);
+    let framing_y = -(DECK_DEPTH + BUILDING_DEPTH) - 6.0;
+    create_framing_plan(&mut drawing, &framing, 0.0, framing_y);
+
+    // Foundation below grade in section A-A
+    let foundation = foundation(&framing);
+    draw_foundation_section(&mut drawing, model, &foundation, offset_x + 8.0, offset_y);
+
+    // Hot tub load check beside the framing plan
+    let lines = hot_tub_load(model).lines();
+    draw_note_block(&mut drawing, "HOT TUB LOAD CHECK", lines, LAYER_ANNO, offset_x, framing_y + framing.y2);
+
+    // Assembly schedule and sauna heat loss below the hot tub load check
+    let lines = sauna_heat_loss(model).lines();
+    draw_note_block(&mut drawing, "ASSEMBLIES AND SAUNA HEAT LOSS", lines, LAYER_ANNO, offset_x, framing_y + framing.y2 - 1.60);
+
+    // Ventilation check to the right of the load and heat loss notes
+    let lines = sauna_ventilation(model).lines(model);
+    draw_note_block(&mut drawing, "SAUNA VENTILATION", lines, LAYER_ANNO, offset_x + 9.0, framing_y + framing.y2);
+
+    // Bench section right of the ventilation check, bench schedule beside it
+    create_bench_section(&mut drawing, model, &benches, offset_x + 18.0, framing_y + framing.y2 - BUILDING_HEIGHT - 1.0);
+    draw_note_block(&mut drawing, "SAUNA BENCHES", benches.lines(), LAYER_ANNO, offset_x + 22.0, framing_y + framing.y2);
+
+    // Heater check below the bench schedule
+    let lines = heater_check(model).lines();
+    draw_note_block(&mut drawing, "SAUNA HEATER", lines, LAYER_ANNO, offset_x + 22.0, framing_y + framing.y2 - 3.0);
+
+    // Wall framing elevations in a row below the framing plan
+    create_wall_framing_elevations(&mut drawing, model, 0.0, framing_y - 8.0);
+
+    // Electrical plan below the wall framing elevations, panel schedule beside it
+    create_electrical_plan(&mut drawing, model, 0.0, framing_y - 16.0);
+    let lines = panel_schedule(model).lines(model);
+    draw_note_block(&mut drawing, "PANEL SCHEDULE", lines, LAYER_ELEC_ANNO, offset_x, framing_y - 16.0 + DECK_DEPTH + BUILDING_DEPTH);
+
+    // Plumbing plan below the electrical plan, pipe schedule beside it
+    create_plumbing_plan(&mut drawing, model, 0.0, framing_y - 24.0);
+    let lines = plumbing_check(model).lines(model);
+    let plumbing_y = framing_y - 24.0 + DECK_DEPTH + BUILDING_DEPTH;
+    draw_note_block(&mut drawing, "PIPE SCHEDULE AND DRAIN FALLS", lines, LAYER_PLUMB_ANNO, offset_x, plumbing_y);
+
+    // Reflected ceiling plan below the plumbing plan, lighting schedule beside it
+    create_reflected_ceiling_plan(&mut drawing, model, 0.0, framing_y - 33.0);
+    let lines = lighting_schedule(model).lines();
+    let lighting_y = framing_y - 33.0 + DECK_DEPTH + BUILDING_DEPTH;
+    draw_note_block(&mut drawing, "LIGHTING FIXTURE SCHEDULE", lines, LAYER_ELEC_ANNO, offset_x, lighting_y);
+
+    // Foundation plan below the reflected ceiling plan, summary beside it
+    create_foundation_plan(&mut drawing, model, &foundation, 0.0, framing_y - 40.0);
+    draw_note_block(&mut drawing, "FOUNDATION", foundation.lines(), LAYER_ANNO, offset_x, framing_y - 40.0 + DECK_DEPTH + BUILDING_DEPTH);
+
+    // Stair schedule right of the foundation summary, a section through each stair below it
+    draw_note_block(&mut drawing, "STAIRS", stairs.lines(), LAYER_ANNO, offset_x + 9.0, framing_y - 40.0 + DECK_DEPTH + BUILDING_DEPTH);
+    for (i, stair) in stairs.stairs.iter().enumerate() {
+        create_stair_section(&mut drawing, stair, offset_x + 9.5 + i as f64 * 3.5, framing_y - 40.0 + 0.5);
+    }
+
+    // Railing schedule right of the stair schedule
+    draw_note_block(&mut drawing, "RAILINGS", railings.lines(), LAYER_ANNO, offset_x + 18.0, framing_y - 40.0 + DECK_DEPTH + BUILDING_DEPTH);
+
+    // Site plan below the foundation plan, setback check beside it
+    let (site_x, site_y) = site_plan_origin();
+    let site = site_check(model);
+    create_site_plan(&mut drawing, model, &site, site_x, site_y);
+    let lot_right = LOT_LINES.iter().map(|line| line.x).fold(0.0, f64::max);
+    let lot_top = LOT_LINES.iter().map(|line| line.y).fold(0.0, f64::max);
+    draw_note_block(&mut drawing, "SITE AND SETBACKS", site.lines(), LAYER_ANNO, site_x + lot_right + 4.0, site_y + lot_top);
+
+    // Shadow study right of the setback check, sun positions above it
+    let study = sun_study(model);
+    let study_x = site_x + lot_right + 20.0;
+    create_shadow_study(&mut drawing, model, &study, study_x, site_y);
+    draw_note_block(&mut drawing, "SUN POSITIONS", study.lines(), LAYER_ANNO, site_x + lot_right + 4.0, site_y + lot_top - 3.0);
+
+    // Clouds around views changed in a tagged revision
+    let views = [
+        (SheetView::FloorPlan, floor_plan),
+        (SheetView::FrontElevation, front),
+        (SheetView::SideElevation, side),
+        (SheetView::Section, section),
+        (SheetView::Iso, iso),
+    ];
+    let views: Vec<(SheetView, Extents)> =
+        views.into_iter().filter_map(|(view, extents)| extents.map(|extents| (view, extents))).collect();
+    draw_revision_clouds(&mut drawing, REVISION_CLOUDS, &views);
+
+    // 3D model (walls, deck and roof as 3DFACE solids at true elevations)
+    if model_3d {
+        setup_model_layers(&mut drawing);
+        draw_model_3d(&mut drawing, model);
+    }
+
+    drawing
+}
@@ -0,0 +1,236 @@
+use dxf::Drawing;
+
+use crate::constants::*;
+use crate::helpers::{add_polygon, add_rotated_text, add_text, draw_dim_aligned, draw_north_arrow};
+use crate::layers::*;
+use crate::hot_tub::{draw_hot_tub_outline, hot_tub_shell};
+use crate::model::BuildingModel;
+
+// ============================================================================
+// SITE PLAN
+// The lot in site coordinates (x east, y north) with the building placed at
+// SITE_ORIGIN and turned by SITE_ROTATION. Setbacks are measured square to
+// each lot line, to the nearest corner of the deck and roof or to the edge of
+// the hot tub.
+// ============================================================================
+
+/// Distance from one lot line to the nearest part of the building
+pub struct SetbackCheck {
+    pub line: &'static str,
+    pub required: f64,
+    pub actual: f64,               // Negative when the building crosses the line
+    pub nearest: (f64, f64),       // Nearest point of the building
+    pub foot: (f64, f64),          // Where it is square to the lot line
+}
+
+pub struct SiteCheck {
+    pub setbacks: Vec<SetbackCheck>,
+    pub problems: Vec<String>,
+}
+
+/// Model plan coordinates to site coordinates
+pub fn to_site(x: f64, y: f64) -> (f64, f64) {
+    let (sin, cos) = SITE_ROTATION.to_radians().sin_cos();
+    (SITE_ORIGIN.0 + x * cos - y * sin, SITE_ORIGIN.1 + x * sin + y * cos)
+}
+
+/// Lot area in m², negative if the corners run clockwise
+fn signed_lot_area() -> f64 {
+    let n = LOT_LINES.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (&LOT_LINES[i], &LOT_LINES[(i + 1) % n]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Unit normal of lot line `i`, pointing into the lot
+fn inward_normal(i: usize) -> (f64, f64) {
+    let (a, b) = (&LOT_LINES[i], &LOT_LINES[(i + 1) % LOT_LINES.len()]);
+    let length = ((b.x - a.x).powi(2) + (b.y - a.y).powi(2)).sqrt();
+    let side = signed_lot_area().signum();
+    (-(b.y - a.y) / length * side, (b.x - a.x) / length * side)
+}
+
+/// Distance from lot line `i` to a site point, positive inside the lot
+fn lot_distance(i: usize, x: f64, y: f64) -> f64 {
+    let (nx, ny) = inward_normal(i);
+    (x - LOT_LINES[i].x) * nx + (y - LOT_LINES[i].y) * ny
+}
+
+/// Corners of the deck and roof outline in model coordinates
+fn outline(model: &BuildingModel) -> [(f64, f64); 4] {
+    let (x1, y1) = (model.deck.min.x.min(model.roof.min.x), model.deck.min.y.min(model.roof.min.y));
+    let (x2, y2) = (model.deck.max.x.max(model.roof.max.x), model.deck.max.y.max(model.roof.max.y));
+    [(x1, y1), (x2, y1), (x2, y2), (x1, y2)]
+}
+
+/// Setback from every lot line to the building outline and the hot tub
+pub fn site_check(model: &BuildingModel) -> SiteCheck {
+    let corners: Vec<(f64, f64)> = outline(model).iter().map(|&(x, y)| to_site(x, y)).collect();
+    let tub = hot_tub_shell();
+    let (sin, cos) = SITE_ROTATION.to_radians().sin_cos();
+
+    let mut setbacks = Vec::new();
+    let mut problems = Vec::new();
+    for (i, line) in LOT_LINES.iter().enumerate() {
+        // Tub edge nearest the line: farthest out against its inward normal,
+        // turned back from site to model axes
+        let (nx, ny) = inward_normal(i);
+        let (tub_x, tub_y) = tub.extreme(-nx * cos - ny * sin, nx * sin - ny * cos);
+        let tub_edge = to_site(tub_x, tub_y);
+        let (actual, nearest) = corners
+            .iter()
+            .chain([&tub_edge])
+            .map(|&(x, y)| (lot_distance(i, x, y), (x, y)))
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .expect("outline has corners");
+
+        if actual < 0.0 {
+            problems.push(format!("{}: BUILDING {:.2} m OVER THE LOT LINE", line.name, -actual));
+        } else if actual < line.setback {
+            problems.push(format!("{}: {:.2} m TO THE LOT LINE (MIN {:.2} m)", line.name, actual, line.setback));
+        }
+        setbacks.push(SetbackCheck {
+            line: line.name,
+            required: line.setback,
+            actual,
+            nearest,
+            foot: (nearest.0 - nx * actual, nearest.1 - ny * actual),
+        });
+    }
+
+    SiteCheck { setbacks, problems }
+}
+
+impl SiteCheck {
+    /// Report lines for the sheet and the console
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "LOT {:.0} m², BUILDING AT ({:.2}, {:.2}) TURNED {:.1}° FROM NORTH",
+            signed_lot_area().abs(),
+            SITE_ORIGIN.0,
+            SITE_ORIGIN.1,
+            SITE_ROTATION
+        )];
+        for setback in &self.setbacks {
+            lines.push(format!(
+                "{:<10} SETBACK {:>5.2} m  ACTUAL {:>5.2} m  {}",
+                setback.line,
+                setback.required,
+                setback.actual,
+                if setback.actual >= setback.required { "OK" } else { "VIOLATION" }
+            ));
+        }
+        if self.problems.is_empty() {
+            lines.push("BUILDING, DECK AND HOT TUB WITHIN THE SETBACKS".to_string());
+        }
+        lines.extend(self.problems.iter().map(|problem| format!("WARNING: {}", problem)));
+        lines
+    }
+}
+
+/// Model rectangle placed on the site, drawn with the site origin at (x, y)
+fn add_site_rectangle(drawing: &mut Drawing, layer: &str, x: f64, y: f64, corners: (f64, f64, f64, f64)) {
+    let (x1, y1, x2, y2) = corners;
+    let points: Vec<(f64, f64)> = [(x1, y1), (x2, y1), (x2, y2), (x1, y2)]
+        .iter()
+        .map(|&(px, py)| {
+            let (sx, sy) = to_site(px, py);
+            (x + sx, y + sy)
+        })
+        .collect();
+    add_polygon(drawing, layer, &points);
+}
+
+/// Deck, exterior walls, roof edge and hot tub turned onto the site, with the
+/// site origin drawn at (x, y)
+pub fn draw_site_building(drawing: &mut Drawing, model: &BuildingModel, x: f64, y: f64) {
+    let deck = &model.deck;
+    add_site_rectangle(drawing, LAYER_DECK, x, y, (deck.min.x, deck.min.y, deck.max.x, deck.max.y));
+    for wall in model.walls.iter().filter(|wall| wall.exterior) {
+        add_site_rectangle(drawing, LAYER_WALLS, x, y, (wall.x1, wall.y1, wall.x2, wall.y2));
+    }
+    let roof = &model.roof;
+    add_site_rectangle(drawing, LAYER_ROOF, x, y, (roof.min.x, roof.min.y, roof.max.x, roof.max.y));
+    draw_hot_tub_outline(drawing, |tub_x, tub_y| {
+        let (tub_x, tub_y) = to_site(tub_x, tub_y);
+        (x + tub_x, y + tub_y)
+    });
+}
+
+/// Site plan with the site origin drawn at (x, y): lot lines, the setback
+/// line, the building turned to its true orientation and the setback to each
+/// lot line dimensioned
+pub fn create_site_plan(drawing: &mut Drawing, model: &BuildingModel, check: &SiteCheck, x: f64, y: f64) {
+    let n = LOT_LINES.len();
+    let lot: Vec<(f64, f64)> = LOT_LINES.iter().map(|line| (x + line.x, y + line.y)).collect();
+    add_polygon(drawing, LAYER_PROPERTY, &lot);
+
+    // Setback line: each lot line moved in by its setback, corners where they meet
+    let offset_line = |i: usize| {
+        let (nx, ny) = inward_normal(i);
+        let (a, b) = (&LOT_LINES[i], &LOT_LINES[(i + 1) % n]);
+        let s = a.setback;
+        ((a.x + nx * s, a.y + ny * s), (b.x - a.x, b.y - a.y))
+    };
+    let setback: Vec<(f64, f64)> = (0..n)
+        .map(|i| {
+            let ((px, py), (dx, dy)) = offset_line((i + n - 1) % n);
+            let ((qx, qy), (ex, ey)) = offset_line(i);
+            let cross = dx * ey - dy * ex;
+            let t = if cross.abs() < 1e-12 { 1.0 } else { ((qx - px) * ey - (qy - py) * ex) / cross };
+            (x + px + dx * t, y + py + dy * t)
+        })
+        .collect();
+    add_polygon(drawing, LAYER_SETBACK, &setback);
+
+    // Lot line names and setbacks, along each line just inside the lot
+    for (i, line) in LOT_LINES.iter().enumerate() {
+        let next = &LOT_LINES[(i + 1) % n];
+        let (nx, ny) = inward_normal(i);
+        let (mut dx, mut dy) = (next.x - line.x, next.y - line.y);
+        if dx < -1e-9 || (dx.abs() <= 1e-9 && dy < 0.0) {
+            (dx, dy) = (-dx, -dy);
+        }
+        let length = (dx * dx + dy * dy).sqrt();
+        let inset = if ny * dx - nx * dy > 0.0 { 0.25 } else { 0.25 + DIM_TEXT_HEIGHT };
+        add_rotated_text(
+            drawing,
+            LAYER_PROPERTY,
+            x + (line.x + next.x) / 2.0 + nx * inset - dx / length * 1.5,
+            y + (line.y + next.y) / 2.0 + ny * inset - dy / length * 1.5,
+            DIM_TEXT_HEIGHT,
+            dy.atan2(dx).to_degrees(),
+            format!("{} {:.2} m", line.name, line.setback),
+        );
+    }
+
+    draw_site_building(drawing, model, x, y);
+
+    for setback in &check.setbacks {
+        draw_dim_aligned(
+            drawing,
+            x + setback.foot.0,
+            y + setback.foot.1,
+            x + setback.nearest.0,
+            y + setback.nearest.1,
+            "",
+        );
+    }
+
+    // North arrow (site coordinates are on true north), title and note
+    let (max_x, max_y) = LOT_LINES.iter().fold((0.0f64, 0.0f64), |(mx, my), line| (mx.max(line.x), my.max(line.y)));
+    draw_north_arrow(drawing, x + max_x + 1.5, y + max_y - 2.0, 1.0, 0.0);
+    add_text(drawing, LAYER_TEXT, x, y + max_y + 0.90, LABEL_TEXT_HEIGHT, "SITE PLAN".to_string());
+    add_text(
+        drawing,
+        LAYER_ANNO,
+        x,
+        y + max_y + 0.60,
+        DIM_TEXT_HEIGHT,
+        "LOT LINES WITH REQUIRED SETBACKS, DIMENSIONS TO THE NEAREST PART OF THE BUILDING".to_string(),
+    );
+}
@@ -0,0 +1,374 @@
+use dxf::entities::*;
+use dxf::{Drawing, Point};
+
+use crate::constants::*;
+use crate::helpers::{
+    add_line, add_polygon, add_rectangle, add_text, draw_arrow, draw_dim_aligned, draw_dim_horizontal, draw_dim_vertical,
+    polyline_from_points,
+};
+use crate::layers::*;
+use crate::hot_tub::hot_tub_shell;
+use crate::model::BuildingModel;
+
+// ============================================================================
+// DECK STAIRS
+// Access stairs off the deck edges. The deck height is split into the fewest
+// risers no higher than STAIR_MAX_RISER, and the tread is sized so that two
+// risers and a tread land mid-way in STAIR_STEP_LENGTH, never under
+// STAIR_MIN_TREAD. The top riser steps onto the deck, so a stair has one tread
+// fewer than risers. Stringers are notched from STAIR_STRINGER_DEPTH boards,
+// hung off the deck rim with a plumb cut and standing on grade with a level
+// cut; the notches sit a tread thickness below each tread surface.
+// ============================================================================
+
+/// Arrow head size for the UP arrows in plan
+const ARROW_HEAD: f64 = 0.12;
+
+/// One stair worked out for the deck height
+pub struct StairDesign {
+    pub id: &'static str,
+    pub edge: DeckEdge,
+    pub width: f64,
+    pub risers: u32,
+    pub rise: f64,                       // m per riser
+    pub going: f64,                      // m per tread
+    pub stringers: u32,
+    pub footprint: (f64, f64, f64, f64), // Plan x1, y1, x2, y2
+    pub edge_line: f64,                  // Plan x or y of the deck edge
+}
+
+pub struct StairSchedule {
+    pub stairs: Vec<StairDesign>,
+    pub problems: Vec<String>,
+}
+
+impl DeckEdge {
+    pub fn name(self) -> &'static str {
+        match self {
+            DeckEdge::North => "NORTH",
+            DeckEdge::East => "EAST",
+            DeckEdge::South => "SOUTH",
+            DeckEdge::West => "WEST",
+        }
+    }
+}
+
+impl StairDesign {
+    pub fn treads(&self) -> u32 {
+        self.risers - 1
+    }
+
+    /// Horizontal length from the bottom riser to the deck edge
+    pub fn run(&self) -> f64 {
+        self.going * self.treads() as f64
+    }
+
+    pub fn height(&self) -> f64 {
+        self.rise * self.risers as f64
+    }
+
+    pub fn step_length(&self) -> f64 {
+        2.0 * self.rise + self.going
+    }
+
+    /// Stringer pitch in degrees
+    pub fn pitch(&self) -> f64 {
+        self.rise.atan2(self.going).to_degrees()
+    }
+
+    /// Depth of stringer left square to the pitch below the notches
+    pub fn throat(&self) -> f64 {
+        STAIR_STRINGER_DEPTH - self.rise * self.going / self.rise.hypot(self.going)
+    }
+
+    /// Stringer outline in stair coordinates: x from the bottom riser face
+    /// towards the deck, z up from grade
+    pub fn stringer_outline(&self) -> Vec<(f64, f64)> {
+        let (rise, going, t) = (self.rise, self.going, STAIR_TREAD_THICKNESS);
+        let depth = STAIR_STRINGER_DEPTH / self.pitch().to_radians().cos();
+        // Where the underside of the stringer meets grade
+        let level_x = (depth - rise + t) * going / rise;
+        let mut outline = if level_x > 0.0 {
+            vec![(0.0, 0.0), (level_x, 0.0)]
+        } else {
+            vec![(0.0, rise - t - depth)]
+        };
+        outline.push((self.run(), self.height() - t - depth));
+        for k in (1..self.risers).rev() {
+            let x = (k - 1) as f64 * going;
+            outline.push((x + going, k as f64 * rise - t));
+            outline.push((x, k as f64 * rise - t));
+        }
+        outline
+    }
+
+    /// Level cut at grade, 0 if the stringer stops short of grade
+    pub fn level_cut(&self) -> f64 {
+        let outline = self.stringer_outline();
+        if outline[0].1 == 0.0 {
+            outline[1].0
+        } else {
+            0.0
+        }
+    }
+
+    /// Plumb cut against the deck rim
+    pub fn plumb_cut(&self) -> f64 {
+        STAIR_STRINGER_DEPTH / self.pitch().to_radians().cos() - self.rise
+    }
+
+    /// Board length the stringer is cut from, measured along the pitch
+    pub fn stringer_length(&self) -> f64 {
+        let (sin, cos) = self.pitch().to_radians().sin_cos();
+        let along: Vec<f64> = self.stringer_outline().iter().map(|&(x, z)| x * cos + z * sin).collect();
+        along.iter().copied().fold(f64::MIN, f64::max) - along.iter().copied().fold(f64::MAX, f64::min)
+    }
+}
+
+/// Risers and tread for the deck height, and each stair's place off the deck
+pub fn stair_schedule(model: &BuildingModel) -> StairSchedule {
+    let height = model.deck.max.z - model.deck.min.z;
+    let risers = ((height / STAIR_MAX_RISER - 1e-9).ceil() as u32).max(2);
+    let rise = height / risers as f64;
+    let target = (STAIR_STEP_LENGTH.0 + STAIR_STEP_LENGTH.1) / 2.0;
+    // Rounded up to the centimetre for setting out
+    let going = ((target - 2.0 * rise).max(STAIR_MIN_TREAD) * 100.0 - 1e-6).ceil() / 100.0;
+
+    let (x1, y1, x2, y2) = (model.deck.min.x, model.deck.min.y, model.deck.max.x, model.deck.max.y);
+    let tub = hot_tub_shell();
+    let mut problems = Vec::new();
+    let stairs: Vec<StairDesign> = DECK_STAIRS
+        .iter()
+        .map(|stair| {
+            let run = going * (risers - 1) as f64;
+            let (start, end) = (stair.offset, stair.offset + stair.width);
+            let (footprint, edge_line, edge_length) = match stair.edge {
+                DeckEdge::North => ((x1 + start, y2, x1 + end, y2 + run), y2, x2 - x1),
+                DeckEdge::East => ((x2, y1 + start, x2 + run, y1 + end), x2, y2 - y1),
+                DeckEdge::South => ((x1 + start, y1 - run, x1 + end, y1), y1, x2 - x1),
+                DeckEdge::West => ((x1 - run, y1 + start, x1, y1 + end), x1, y2 - y1),
+            };
+            let design = StairDesign {
+                id: stair.id,
+                edge: stair.edge,
+                width: stair.width,
+                risers,
+                rise,
+                going,
+                stringers: (stair.width / STAIR_STRINGER_SPACING).ceil() as u32 + 1,
+                footprint,
+                edge_line,
+            };
+
+            if rise < STAIR_MIN_RISER {
+                problems.push(format!(
+                    "{}: RISER {:.0} mm UNDER {:.0} mm",
+                    stair.id,
+                    rise * 1000.0,
+                    STAIR_MIN_RISER * 1000.0
+                ));
+            }
+            let step = design.step_length();
+            if step < STAIR_STEP_LENGTH.0 - 1e-9 || step > STAIR_STEP_LENGTH.1 + 1e-9 {
+                problems.push(format!(
+                    "{}: 2R+T {:.0} mm OUTSIDE {:.0}-{:.0} mm",
+                    stair.id,
+                    step * 1000.0,
+                    STAIR_STEP_LENGTH.0 * 1000.0,
+                    STAIR_STEP_LENGTH.1 * 1000.0
+                ));
+            }
+            if design.throat() < STAIR_MIN_THROAT {
+                problems.push(format!(
+                    "{}: STRINGER THROAT {:.0} mm UNDER {:.0} mm, USE A DEEPER BOARD",
+                    stair.id,
+                    design.throat() * 1000.0,
+                    STAIR_MIN_THROAT * 1000.0
+                ));
+            }
+            if start < 0.0 || end > edge_length + 1e-9 {
+                problems.push(format!("{}: STAIR RUNS PAST THE END OF THE {} DECK EDGE", stair.id, stair.edge.name()));
+            }
+            let (fx1, fy1, fx2, fy2) = footprint;
+            if tub.overlaps(fx1, fy1, fx2, fy2) {
+                problems.push(format!("{}: STAIR OVERLAPS THE HOT TUB", stair.id));
+            }
+            design
+        })
+        .collect();
+    StairSchedule { stairs, problems }
+}
+
+impl StairSchedule {
+    /// Report lines for the sheet and the console
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for stair in &self.stairs {
+            lines.push(format!(
+                "{:<4} {} EDGE, {:.2} m WIDE, {} RISERS x {:.0} mm, {} TREADS x {:.0} mm",
+                stair.id,
+                stair.edge.name(),
+                stair.width,
+                stair.risers,
+                stair.rise * 1000.0,
+                stair.treads(),
+                stair.going * 1000.0
+            ));
+            lines.push(format!(
+                "     2R+T {:.0} mm, PITCH {:.1}°, RUN {:.2} m",
+                stair.step_length() * 1000.0,
+                stair.pitch(),
+                stair.run()
+            ));
+            lines.push(format!(
+                "     {} STRINGERS {:.0}x{:.0} {} x {:.3} m, PLUMB CUT {:.0}, LEVEL CUT {:.0}, THROAT {:.0}",
+                stair.stringers,
+                JOIST_WIDTH * 1000.0,
+                STAIR_STRINGER_DEPTH * 1000.0,
+                TIMBER_GRADE,
+                stair.stringer_length(),
+                stair.plumb_cut() * 1000.0,
+                stair.level_cut() * 1000.0,
+                stair.throat() * 1000.0
+            ));
+        }
+        if self.problems.is_empty() {
+            lines.push("RISERS, TREADS AND STRINGERS WITHIN LIMITS".to_string());
+        }
+        lines.extend(self.problems.iter().map(|problem| format!("WARNING: {}", problem)));
+        lines
+    }
+}
+
+/// Stairs in the floor plan: outline, tread nosings and an UP arrow from
+/// the bottom riser onto the deck
+pub fn draw_stairs_plan(drawing: &mut Drawing, schedule: &StairSchedule) {
+    for stair in &schedule.stairs {
+        let (x1, y1, x2, y2) = stair.footprint;
+        add_rectangle(drawing, LAYER_STAIRS, x1, y1, x2, y2);
+
+        // Unit vector up the stair, and a point on the centre line at a
+        // distance out from the deck edge
+        let (ux, uy) = match stair.edge {
+            DeckEdge::North => (0.0, -1.0),
+            DeckEdge::East => (-1.0, 0.0),
+            DeckEdge::South => (0.0, 1.0),
+            DeckEdge::West => (1.0, 0.0),
+        };
+        let centre = |out: f64| match stair.edge {
+            DeckEdge::North | DeckEdge::South => ((x1 + x2) / 2.0, stair.edge_line - uy * out),
+            DeckEdge::East | DeckEdge::West => (stair.edge_line - ux * out, (y1 + y2) / 2.0),
+        };
+
+        for j in 1..stair.treads() {
+            let (cx, cy) = centre(j as f64 * stair.going);
+            let (hx, hy) = (uy.abs() * stair.width / 2.0, ux.abs() * stair.width / 2.0);
+            add_line(drawing, LAYER_STAIRS, cx - hx, cy - hy, cx + hx, cy + hy);
+        }
+
+        let (ax, ay) = centre(stair.run() - 0.05);
+        let (bx, by) = centre(-0.25);
+        draw_arrow(drawing, LAYER_STAIRS, ax, ay, bx, by, ARROW_HEAD);
+        let (tx, ty) = centre(stair.run() + 0.10 + DIM_TEXT_HEIGHT);
+        add_text(drawing, LAYER_ANNO, tx - 0.10, ty - DIM_TEXT_HEIGHT / 2.0, DIM_TEXT_HEIGHT, "UP".to_string());
+        add_text(
+            drawing,
+            LAYER_ANNO,
+            tx + 0.15,
+            ty - DIM_TEXT_HEIGHT / 2.0,
+            DIM_TEXT_HEIGHT,
+            format!("{} {}R x {:.0} / {}T x {:.0}", stair.id, stair.risers, stair.rise * 1000.0, stair.treads(), stair.going * 1000.0),
+        );
+    }
+}
+
+/// Stairs in the front elevation, which looks north: south stairs show their
+/// risers, east and west stairs their profile, north stairs are hidden
+pub fn draw_stairs_front_elevation(drawing: &mut Drawing, schedule: &StairSchedule, offset_x: f64, offset_y: f64) {
+    for stair in &schedule.stairs {
+        let (x1, _, x2, _) = stair.footprint;
+        let height = stair.height();
+        let side = match stair.edge {
+            DeckEdge::North => continue,
+            DeckEdge::South => {
+                for k in 1..stair.risers {
+                    let (z1, z2) = ((k - 1) as f64 * stair.rise, k as f64 * stair.rise);
+                    add_rectangle(drawing, LAYER_STAIRS, offset_x + x1, offset_y + z1, offset_x + x2, offset_y + z2);
+                }
+                continue;
+            }
+            DeckEdge::East => 1.0,
+            DeckEdge::West => -1.0,
+        };
+
+        // Stepped profile down and away from the deck edge
+        let mut points = vec![(0.0, height)];
+        for j in 1..stair.risers {
+            let z = height - j as f64 * stair.rise;
+            points.push(((j - 1) as f64 * stair.going, z));
+            points.push((j as f64 * stair.going, z));
+        }
+        points.push((stair.run(), 0.0));
+        let points: Vec<Point> = points
+            .into_iter()
+            .map(|(out, z)| Point::new(offset_x + stair.edge_line + side * out, offset_y + z, 0.0))
+            .collect();
+        let mut entity = Entity::new(EntityType::LwPolyline(polyline_from_points(points)));
+        entity.common.layer = LAYER_STAIRS.to_string();
+        drawing.add_entity(entity);
+    }
+}
+
+/// Section through a stair along its run, with grade at the bottom riser
+/// face at (x, y) and the deck on the right: treads, stringer and the
+/// dimensions needed to set out and cut the stringers
+pub fn create_stair_section(drawing: &mut Drawing, stair: &StairDesign, x: f64, y: f64) {
+    let (run, height, t) = (stair.run(), stair.height(), STAIR_TREAD_THICKNESS);
+
+    // Grade, deck boards and rim joist
+    add_line(drawing, LAYER_SECTION, x - 0.50, y, x + run + 0.80, y);
+    add_rectangle(drawing, LAYER_DECK, x + run, y + height - t, x + run + 0.60, y + height);
+    add_rectangle(drawing, LAYER_DECK, x + run, y + height - t - JOIST_DEPTH, x + run + JOIST_WIDTH, y + height - t);
+
+    // Treads and the stringer
+    for k in 1..stair.risers {
+        let (x1, z) = ((k - 1) as f64 * stair.going, k as f64 * stair.rise);
+        add_rectangle(drawing, LAYER_STAIRS, x + x1, y + z - t, x + x1 + stair.going, y + z);
+    }
+    let outline: Vec<(f64, f64)> = stair.stringer_outline().iter().map(|&(px, pz)| (x + px, y + pz)).collect();
+    add_polygon(drawing, LAYER_STAIRS, &outline);
+
+    // Riser, going, total rise and run
+    draw_dim_vertical(drawing, x, y, y + stair.rise, -0.35, "R ");
+    draw_dim_horizontal(drawing, x, x + stair.going, y + stair.rise, 0.35, "T ");
+    draw_dim_vertical(drawing, x + run + 0.60, y, y + height, 0.25, "H ");
+    draw_dim_horizontal(drawing, x, x + run, y, -0.35, "RUN ");
+
+    // Stringer length along the pitch, below its underside
+    let (sin, cos) = stair.pitch().to_radians().sin_cos();
+    let along: Vec<f64> = stair.stringer_outline().iter().map(|&(px, pz)| px * cos + pz * sin).collect();
+    let across = stair.stringer_outline().iter().map(|&(px, pz)| px * sin - pz * cos).fold(f64::MIN, f64::max) + 0.20;
+    let point = |a: f64| (x + a * cos + across * sin, y + a * sin - across * cos);
+    let (p1, p2) = (
+        point(along.iter().copied().fold(f64::MAX, f64::min)),
+        point(along.iter().copied().fold(f64::MIN, f64::max)),
+    );
+    draw_dim_aligned(drawing, p1.0, p1.1, p2.0, p2.1, "STRINGER ");
+
+    add_text(drawing, LAYER_TEXT, x - 0.50, y + height + 0.50, LABEL_TEXT_HEIGHT, format!("STAIR SECTION {}", stair.id));
+    add_text(
+        drawing,
+        LAYER_ANNO,
+        x - 0.50,
+        y - 0.85,
+        DIM_TEXT_HEIGHT,
+        format!(
+            "PITCH {:.1}°, PLUMB CUT {:.0}, LEVEL CUT {:.0}, THROAT {:.0}, BOTTOM RISER DROPPED {:.0}",
+            stair.pitch(),
+            stair.plumb_cut() * 1000.0,
+            stair.level_cut() * 1000.0,
+            stair.throat() * 1000.0,
+            t * 1000.0
+        ),
+    );
+}
@@ -0,0 +1,220 @@
+use std::f64::consts::PI;
+
+use dxf::Drawing;
+
+use crate::constants::*;
+use crate::helpers::{add_polygon, add_text, draw_north_arrow};
+use crate::layers::*;
+use crate::loads::hot_tub_centre;
+use crate::model::{floor_level, BuildingModel};
+use crate::site::{draw_site_building, to_site};
+
+// ============================================================================
+// SUN AND SHADOWS
+// Sun azimuth and altitude from the NOAA solar position equations for the
+// site latitude and longitude, and the shadows of the enclosure, posts and
+// roof cast on the deck plane at each study time. Shadows are worked out in
+// site coordinates (y on true north) and drawn on one layer per time.
+// ============================================================================
+
+pub struct SunPosition {
+    pub azimuth: f64,  // Degrees clockwise from true north
+    pub altitude: f64, // Degrees above the horizon
+}
+
+/// Shadows and the hot tub at one study time
+pub struct ShadowStep {
+    pub time: f64,                        // Local clock hours
+    pub sun: SunPosition,
+    pub shadows: Vec<Vec<(f64, f64)>>,    // Outlines in site coordinates
+    pub tub_in_sun: Option<bool>,         // None when the sun is too low to cast a shadow
+}
+
+pub struct SunStudy {
+    pub steps: Vec<ShadowStep>,
+}
+
+/// Day number in the year, 1 for January 1st
+fn day_of_year(year: i32, month: u32, day: u32) -> u32 {
+    const DAYS_BEFORE: [u32; 12] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    DAYS_BEFORE[(month as usize - 1).min(11)] + day + u32::from(leap && month > 2)
+}
+
+/// Sun position at the site at local clock time `time` (hours) on the study date
+pub fn sun_position(time: f64) -> SunPosition {
+    let (year, month, day) = SUN_STUDY_DATE;
+    let days = if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 { 366.0 } else { 365.0 };
+    let utc = time - SITE_UTC_OFFSET;
+    let gamma = 2.0 * PI / days * (day_of_year(year, month, day) as f64 - 1.0 + (utc - 12.0) / 24.0);
+
+    // Equation of time (minutes) and declination (radians)
+    let equation = 229.18
+        * (0.000075 + 0.001868 * gamma.cos()
+            - 0.032077 * gamma.sin()
+            - 0.014615 * (2.0 * gamma).cos()
+            - 0.040849 * (2.0 * gamma).sin());
+    let declination = 0.006918 - 0.399912 * gamma.cos() + 0.070257 * gamma.sin() - 0.006758 * (2.0 * gamma).cos()
+        + 0.000907 * (2.0 * gamma).sin()
+        - 0.002697 * (3.0 * gamma).cos()
+        + 0.00148 * (3.0 * gamma).sin();
+
+    // Hour angle from true solar time
+    let solar_minutes = utc * 60.0 + equation + 4.0 * SITE_LONGITUDE;
+    let hour_angle = (solar_minutes / 4.0 - 180.0).to_radians();
+
+    let latitude = SITE_LATITUDE.to_radians();
+    let altitude = (latitude.sin() * declination.sin() + latitude.cos() * declination.cos() * hour_angle.cos()).asin();
+    let azimuth = hour_angle
+        .sin()
+        .atan2(hour_angle.cos() * latitude.sin() - declination.tan() * latitude.cos())
+        .to_degrees()
+        + 180.0;
+    SunPosition { azimuth: azimuth.rem_euclid(360.0), altitude: altitude.to_degrees() }
+}
+
+/// Convex hull of a point set, anticlockwise
+fn convex_hull(mut points: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+    points.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    let cross = |o: (f64, f64), a: (f64, f64), b: (f64, f64)| (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0);
+    let mut hull: Vec<(f64, f64)> = Vec::new();
+    for pass in [points.clone(), points.into_iter().rev().collect()] {
+        let start = hull.len();
+        for point in pass {
+            while hull.len() >= start + 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0.0 {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        hull.pop();
+    }
+    hull
+}
+
+/// True if the point is inside an anticlockwise convex outline
+fn inside(outline: &[(f64, f64)], x: f64, y: f64) -> bool {
+    (0..outline.len()).all(|i| {
+        let (a, b) = (outline[i], outline[(i + 1) % outline.len()]);
+        (b.0 - a.0) * (y - a.1) - (b.1 - a.1) * (x - a.0) >= 0.0
+    })
+}
+
+/// Boxes casting shadows, in model coordinates with heights above the deck:
+/// the enclosure up to the roof, the posts and the roof slab
+fn shadow_casters(model: &BuildingModel) -> Vec<(f64, f64, f64, f64, f64, f64)> {
+    let exterior = model.walls.iter().filter(|wall| wall.exterior);
+    let (x1, y1, x2, y2) = exterior.fold((f64::MAX, f64::MAX, f64::MIN, f64::MIN), |(x1, y1, x2, y2), wall| {
+        (x1.min(wall.x1), y1.min(wall.y1), x2.max(wall.x2), y2.max(wall.y2))
+    });
+    let floor = floor_level();
+    let mut casters = vec![(x1, y1, x2, y2, 0.0, model.roof.min.z - floor)];
+    for volume in model.posts.iter().chain([&model.roof]) {
+        casters.push((volume.min.x, volume.min.y, volume.max.x, volume.max.y, volume.min.z - floor, volume.max.z - floor));
+    }
+    casters
+}
+
+/// Sun positions and shadows for every study time
+pub fn sun_study(model: &BuildingModel) -> SunStudy {
+    let (tub_x, tub_y) = hot_tub_centre();
+    let tub = to_site(tub_x, tub_y);
+    let steps = SUN_STUDY_TIMES
+        .iter()
+        .map(|&time| {
+            let sun = sun_position(time);
+            if sun.altitude < SHADOW_MIN_ALTITUDE {
+                return ShadowStep { time, sun, shadows: Vec::new(), tub_in_sun: None };
+            }
+
+            // Every point is pushed away from the sun by its height over tan(altitude)
+            let reach = 1.0 / sun.altitude.to_radians().tan();
+            let (sin, cos) = sun.azimuth.to_radians().sin_cos();
+            let shadows: Vec<Vec<(f64, f64)>> = shadow_casters(model)
+                .into_iter()
+                .map(|(x1, y1, x2, y2, z1, z2)| {
+                    let corners = [(x1, y1), (x2, y1), (x2, y2), (x1, y2)].map(|(x, y)| to_site(x, y));
+                    let points = [z1, z2]
+                        .iter()
+                        .flat_map(|&z| corners.iter().map(move |&(x, y)| (x - sin * z * reach, y - cos * z * reach)))
+                        .collect();
+                    convex_hull(points)
+                })
+                .collect();
+            let tub_in_sun = Some(!shadows.iter().any(|shadow| inside(shadow, tub.0, tub.1)));
+            ShadowStep { time, sun, shadows, tub_in_sun }
+        })
+        .collect();
+    SunStudy { steps }
+}
+
+/// Local clock time as HH:MM
+pub fn clock(time: f64) -> String {
+    let minutes = (time * 60.0).round() as i64;
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+impl SunStudy {
+    /// Report lines for the sheet and the console
+    pub fn lines(&self) -> Vec<String> {
+        let (year, month, day) = SUN_STUDY_DATE;
+        let mut lines = vec![format!(
+            "{}-{:02}-{:02}, LAT {:.2}, LON {:.2}, UTC{:+}",
+            year, month, day, SITE_LATITUDE, SITE_LONGITUDE, SITE_UTC_OFFSET
+        )];
+        for step in &self.steps {
+            let tub = match step.tub_in_sun {
+                Some(true) => "HOT TUB IN SUN",
+                Some(false) => "HOT TUB IN SHADE",
+                None => "SUN TOO LOW",
+            };
+            lines.push(format!(
+                "{}  AZ {:>5.1}°  ALT {:>4.1}°  {}",
+                clock(step.time),
+                step.sun.azimuth,
+                step.sun.altitude,
+                tub
+            ));
+        }
+        lines
+    }
+}
+
+/// Shadow study with the site origin drawn at (x, y): the building on the
+/// site, each time's shadows on its own layer, tagged with the time at the
+/// far end of the roof shadow
+pub fn create_shadow_study(drawing: &mut Drawing, model: &BuildingModel, study: &SunStudy, x: f64, y: f64) {
+    draw_site_building(drawing, model, x, y);
+
+    for step in &study.steps {
+        let layer = layer_shadow(step.time);
+        for shadow in &step.shadows {
+            let points: Vec<(f64, f64)> = shadow.iter().map(|&(px, py)| (x + px, y + py)).collect();
+            add_polygon(drawing, &layer, &points);
+        }
+
+        // Time tag beyond the shadow point furthest from the sun
+        let (sin, cos) = step.sun.azimuth.to_radians().sin_cos();
+        let far = step
+            .shadows
+            .iter()
+            .flatten()
+            .copied()
+            .max_by(|a, b| (-a.0 * sin - a.1 * cos).total_cmp(&(-b.0 * sin - b.1 * cos)));
+        if let Some((px, py)) = far {
+            add_text(drawing, &layer, x + px - sin * 0.3, y + py - cos * 0.3, DIM_TEXT_HEIGHT, clock(step.time));
+        }
+    }
+
+    let (ox, oy) = to_site(0.0, 0.0);
+    let (top_x, top_y) = to_site(model.roof.min.x, model.roof.max.y);
+    draw_north_arrow(drawing, x + ox - 2.0, y + top_y + 1.0, 1.0, 0.0);
+    add_text(drawing, LAYER_TEXT, x + top_x, y + top_y + 2.5, LABEL_TEXT_HEIGHT, "SHADOW STUDY".to_string());
+    add_text(
+        drawing,
+        LAYER_ANNO,
+        x + top_x,
+        y + top_y + 2.2,
+        DIM_TEXT_HEIGHT,
+        format!("SHADOWS ON THE DECK PLANE, NORTH UP, BUILDING ORIGIN AT SITE ({:.2}, {:.2})", ox, oy),
+    );
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use dxf::entities::*;
+use dxf::{Block, Drawing, DxfResult, Point};
+
+use crate::constants::*;
+use crate::layers::{setup_survey_layer, LAYER_SURVEY_PREFIX};
+use crate::site::to_site;
+
+// ============================================================================
+// SURVEY UNDERLAY
+// The surveyor's lot drawing, read with the dxf crate and copied under the
+// site plan like an xref: only the layers in SURVEY_LAYERS, each onto its own
+// V- layer, into the SURVEY block in survey coordinates with SURVEY_BASE as
+// its base point. The block is inserted at the site origin of the site plan,
+// so the transform from survey to sheet is in the file and the survey
+// coordinates are kept: the building origin sits at SURVEY_BASE plus
+// to_site(0, 0), turned by SITE_ROTATION. Survey and site plan are both
+// north up, so the insert is not turned. Entities that refer to the survey's
+// own blocks or that the dxf crate cannot copy (inserts, hatches, dimensions)
+// are left out and counted, and everything copied is drawn by layer.
+// ============================================================================
+
+/// Block holding the survey in survey coordinates
+const SURVEY_BLOCK: &str = "SURVEY";
+
+/// What was copied from the survey drawing
+pub struct SurveyUnderlay {
+    pub layers: BTreeMap<String, usize>, // Underlay layer and entity count
+    pub skipped: usize,
+}
+
+/// Load the survey drawing
+pub fn load_survey(path: &str) -> DxfResult<Drawing> {
+    Drawing::load_file(path)
+}
+
+/// True if the surveyor's layer is one to copy
+fn wanted(layer: &str) -> bool {
+    SURVEY_LAYERS.is_empty() || SURVEY_LAYERS.iter().any(|name| name.eq_ignore_ascii_case(layer))
+}
+
+/// True for the entity types copied into the survey block
+fn copied(specific: &EntityType) -> bool {
+    matches!(
+        specific,
+        EntityType::Line(_)
+            | EntityType::LwPolyline(_)
+            | EntityType::Polyline(_)
+            | EntityType::Circle(_)
+            | EntityType::Arc(_)
+            | EntityType::Ellipse(_)
+            | EntityType::Text(_)
+            | EntityType::MText(_)
+            | EntityType::ModelPoint(_)
+    )
+}
+
+/// Copy the wanted survey layers into the survey block and insert it under
+/// the site plan, whose site origin is drawn at (x, y)
+pub fn draw_survey_underlay(drawing: &mut Drawing, survey: &Drawing, x: f64, y: f64) -> SurveyUnderlay {
+    let mut block = Block {
+        name: SURVEY_BLOCK.to_string(),
+        base_point: Point::new(SURVEY_BASE.0, SURVEY_BASE.1, 0.0),
+        ..Default::default()
+    };
+    let mut layers = BTreeMap::new();
+    let mut skipped = 0;
+    for entity in survey.entities().filter(|entity| wanted(&entity.common.layer)) {
+        if !copied(&entity.specific) {
+            skipped += 1;
+            continue;
+        }
+        let layer = format!("{}{}", LAYER_SURVEY_PREFIX, entity.common.layer.to_uppercase());
+        let mut copy = Entity::new(entity.specific.clone());
+        copy.common.layer = layer.clone();
+        *layers.entry(layer).or_insert(0) += 1;
+        block.entities.push(copy);
+    }
+    drawing.add_block(block);
+
+    let insert_layer = format!("{}{}", LAYER_SURVEY_PREFIX, SURVEY_BLOCK);
+    let insert = Insert { name: SURVEY_BLOCK.to_string(), location: Point::new(x, y, 0.0), ..Default::default() };
+    let mut entity = Entity::new(EntityType::Insert(insert));
+    entity.common.layer = insert_layer.clone();
+    drawing.add_entity(entity);
+
+    if !layers.contains_key(&insert_layer) {
+        setup_survey_layer(drawing, &insert_layer);
+    }
+    for layer in layers.keys() {
+        setup_survey_layer(drawing, layer);
+    }
+    SurveyUnderlay { layers, skipped }
+}
+
+impl SurveyUnderlay {
+    /// Report lines for the console
+    pub fn lines(&self) -> Vec<String> {
+        let (east, north) = to_site(0.0, 0.0);
+        let mut lines = vec![format!(
+            "BUILDING ORIGIN AT E {:.2} N {:.2}, TURNED {:.1}°",
+            SURVEY_BASE.0 + east,
+            SURVEY_BASE.1 + north,
+            SITE_ROTATION
+        )];
+        for (layer, count) in &self.layers {
+            lines.push(format!("{:<20} {} ENTITIES", layer, count));
+        }
+        if self.layers.is_empty() {
+            lines.push("WARNING: NO SURVEY LAYERS COPIED, CHECK SURVEY_LAYERS".to_string());
+        }
+        if self.skipped > 0 {
+            lines.push(format!("{} BLOCKS, HATCHES, DIMENSIONS OR OTHER ENTITIES LEFT OUT", self.skipped));
+        }
+        lines
+    }
+}
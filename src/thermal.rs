@@ -0,0 +1,286 @@
+use crate::constants::*;
+use crate::model::{BuildingModel, OpeningKind};
+
+// ============================================================================
+// THERMAL ENVELOPE
+// U-values of the wall, roof and floor assemblies, and the steady-state heat
+// loss of the sauna room at SAUNA_TEMPERATURE, used to size the heater and
+// estimate the energy of a session. Partitions around the sauna use the wall
+// assembly and lose heat to ADJACENT_ROOM_TEMPERATURE; the floor loses heat
+// to outdoor air under the deck.
+// ============================================================================
+
+impl AssemblyLayer {
+    /// Conductivity with the bridging timber averaged in
+    fn effective_conductivity(&self) -> Option<f64> {
+        self.conductivity
+            .map(|k| k * (1.0 - self.timber_fraction) + TIMBER_CONDUCTIVITY * self.timber_fraction)
+    }
+}
+
+impl Assembly {
+    pub fn thickness(&self) -> f64 {
+        self.layers.iter().map(|layer| layer.thickness).sum()
+    }
+
+    /// Offsets of the joints between layers, measured from the outside face
+    pub fn boundaries(&self) -> Vec<f64> {
+        let mut offset = 0.0;
+        let mut boundaries = Vec::new();
+        for layer in &self.layers[..self.layers.len() - 1] {
+            offset += layer.thickness;
+            boundaries.push(offset);
+        }
+        boundaries
+    }
+
+    /// Total thermal resistance in m²K/W, surfaces included
+    pub fn resistance(&self) -> f64 {
+        let mut resistance = self.inside_resistance;
+        for layer in self.layers.iter().rev() {
+            match layer.effective_conductivity() {
+                Some(k) => resistance += layer.thickness / k,
+                // Ventilated cavity: outside of it only still air counts
+                None => return resistance + self.inside_resistance,
+            }
+        }
+        resistance + self.outside_resistance
+    }
+
+    /// U-value in W/m²K
+    pub fn u_value(&self) -> f64 {
+        1.0 / self.resistance()
+    }
+}
+
+/// One line of the assembly schedule
+pub fn assembly_line(assembly: &Assembly) -> String {
+    let layers: Vec<String> = assembly
+        .layers
+        .iter()
+        .map(|layer| {
+            let vented = if layer.conductivity.is_none() { " (VENTILATED)" } else { "" };
+            format!("{} {:.0}{}", layer.name, layer.thickness * 1000.0, vented)
+        })
+        .collect();
+    format!(
+        "{} {:.0} mm: {}  U = {:.2} W/m²K",
+        assembly.name,
+        assembly.thickness() * 1000.0,
+        layers.join(" / "),
+        assembly.u_value()
+    )
+}
+
+/// Assemblies that do not fit the thickness drawn for them
+pub fn assembly_problems() -> Vec<String> {
+    let mut problems = Vec::new();
+    // Walls and roof are drawn at their thickness; the floor only has to fit
+    // between the deck top and the ground
+    for (assembly, drawn, constant, exact) in [
+        (&WALL_ASSEMBLY, WALL_THICKNESS, "WALL_THICKNESS", true),
+        (&ROOF_ASSEMBLY, ROOF_THICKNESS, "ROOF_THICKNESS", true),
+        (&FLOOR_ASSEMBLY, DECK_ELEV_HEIGHT, "DECK_ELEV_HEIGHT", false),
+    ] {
+        let thickness = assembly.thickness();
+        let fits = if exact { (thickness - drawn).abs() < 0.0005 } else { thickness < drawn + 0.0005 };
+        if !fits {
+            problems.push(format!(
+                "{} ASSEMBLY {:.0} mm DOES NOT FIT {} {:.0} mm",
+                assembly.name,
+                thickness * 1000.0,
+                constant,
+                drawn * 1000.0
+            ));
+        }
+    }
+    problems
+}
+
+/// One surface of the sauna room losing heat
+pub struct HeatLossElement {
+    pub name: String,
+    pub area: f64,    // m²
+    pub u_value: f64, // W/m²K
+    pub delta_t: f64, // K
+}
+
+impl HeatLossElement {
+    pub fn watts(&self) -> f64 {
+        self.area * self.u_value * self.delta_t
+    }
+}
+
+pub struct SaunaHeatLoss {
+    pub elements: Vec<HeatLossElement>,
+    pub volume: f64,       // m³
+    pub glass_area: f64,   // m², glazed windows and doors
+}
+
+/// Heat loss of the sauna room through every surface around it
+pub fn sauna_heat_loss(model: &BuildingModel) -> SaunaHeatLoss {
+    let room = model.sauna();
+    let floor_area = (room.x2 - room.x1) * (room.y2 - room.y1);
+    let mut elements = Vec::new();
+    let mut glass_area = 0.0;
+
+    for wall in &model.walls {
+        let Some((start, end)) = wall.faces_room(room) else {
+            continue;
+        };
+        let delta_t = if wall.exterior {
+            SAUNA_TEMPERATURE - OUTDOOR_DESIGN_TEMPERATURE
+        } else {
+            SAUNA_TEMPERATURE - ADJACENT_ROOM_TEMPERATURE
+        };
+
+        let mut wall_area = (end - start) * BUILDING_HEIGHT;
+        for opening in &wall.openings {
+            if opening.offset >= end || opening.offset + opening.width <= start {
+                continue;
+            }
+            let area = opening.width * opening.height;
+            wall_area -= area;
+            if opening.glazed {
+                glass_area += area;
+            }
+            elements.push(HeatLossElement {
+                name: opening.id.to_uppercase(),
+                area,
+                u_value: if opening.kind == OpeningKind::Door { DOOR_U_VALUE } else { WINDOW_U_VALUE },
+                delta_t,
+            });
+        }
+        elements.push(HeatLossElement {
+            name: wall.id.to_uppercase(),
+            area: wall_area,
+            u_value: WALL_ASSEMBLY.u_value(),
+            delta_t,
+        });
+    }
+
+    let outdoor = SAUNA_TEMPERATURE - OUTDOOR_DESIGN_TEMPERATURE;
+    elements.push(HeatLossElement {
+        name: "CEILING".to_string(),
+        area: floor_area,
+        u_value: ROOF_ASSEMBLY.u_value(),
+        delta_t: outdoor,
+    });
+    elements.push(HeatLossElement {
+        name: "FLOOR".to_string(),
+        area: floor_area,
+        u_value: FLOOR_ASSEMBLY.u_value(),
+        delta_t: outdoor,
+    });
+
+    SaunaHeatLoss {
+        elements,
+        volume: floor_area * BUILDING_HEIGHT,
+        glass_area,
+    }
+}
+
+impl SaunaHeatLoss {
+    pub fn total_watts(&self) -> f64 {
+        self.elements.iter().map(HeatLossElement::watts).sum()
+    }
+
+    /// Room volume with glass counted extra, as the sizing tables take it
+    pub fn effective_volume(&self) -> f64 {
+        self.volume + self.glass_area * GLASS_EQUIVALENT_VOLUME
+    }
+
+    /// Heater output: the smallest size in HEATER_SIZES for the effective
+    /// volume (past the table, the largest scaled up), or the steady-state
+    /// loss if that is higher
+    pub fn heater_kw(&self) -> f64 {
+        let volume = self.effective_volume();
+        let &(largest_volume, largest_kw) = HEATER_SIZES.last().unwrap();
+        let sized = HEATER_SIZES
+            .iter()
+            .find(|&&(max_volume, _)| volume <= max_volume)
+            .map_or(volume / largest_volume * largest_kw, |&(_, kw)| kw);
+        sized.max(self.total_watts() / 1000.0)
+    }
+
+    /// Energy of one session: heat-up at full power, then the losses
+    pub fn session_kwh(&self) -> f64 {
+        self.heater_kw() * HEAT_UP_HOURS + self.total_watts() / 1000.0 * SESSION_HOURS
+    }
+
+    /// Report lines for the sheet and the console
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = [&WALL_ASSEMBLY, &ROOF_ASSEMBLY, &FLOOR_ASSEMBLY]
+            .into_iter()
+            .map(assembly_line)
+            .collect();
+        lines.extend(assembly_problems().into_iter().map(|problem| format!("WARNING: {}", problem)));
+        for element in &self.elements {
+            lines.push(format!(
+                "{:<22} {:>5.2} m² x U {:.2} x {:.0} K = {:>4.0} W",
+                element.name,
+                element.area,
+                element.u_value,
+                element.delta_t,
+                element.watts()
+            ));
+        }
+        lines.push(format!(
+            "SAUNA HEAT LOSS {:.2} kW AT {:.0} °C INSIDE, {:.0} °C OUTSIDE",
+            self.total_watts() / 1000.0,
+            SAUNA_TEMPERATURE,
+            OUTDOOR_DESIGN_TEMPERATURE
+        ));
+        lines.push(format!(
+            "HEATER {:.1} kW MIN FOR {:.1} m³ ({:.2} m³ + {:.2} m² GLASS x {:.1} m³/m²)",
+            self.heater_kw(),
+            self.effective_volume(),
+            self.volume,
+            self.glass_area,
+            GLASS_EQUIVALENT_VOLUME
+        ));
+        lines.push(format!(
+            "ENERGY PER SESSION {:.1} kWh ({:.1} h HEAT-UP, {:.1} h IN USE)",
+            self.session_kwh(),
+            HEAT_UP_HOURS,
+            SESSION_HOURS
+        ));
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heaters::HeaterCatalogue;
+    use crate::model::building_model;
+
+    fn room(volume: f64, glass_area: f64) -> SaunaHeatLoss {
+        SaunaHeatLoss { elements: Vec::new(), volume, glass_area }
+    }
+
+    #[test]
+    fn heater_size_comes_from_the_sizing_table() {
+        assert_eq!(room(5.0, 0.0).heater_kw(), 4.5);
+        assert_eq!(room(13.0, 0.0).heater_kw(), 8.0);
+        // 1 m² of glass adds GLASS_EQUIVALENT_VOLUME and takes the room a size up
+        assert_eq!(room(12.5, 1.0).heater_kw(), 9.0);
+        // Past the table the largest size scales with the volume
+        assert!((room(64.0, 0.0).heater_kw() - 36.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn only_glazed_openings_count_as_glass() {
+        let mut model = building_model(&HeaterCatalogue::default().select(HEATER_MODEL).unwrap());
+        let glazed = sauna_heat_loss(&model).glass_area;
+        let door = model
+            .walls
+            .iter_mut()
+            .flat_map(|wall| wall.openings.iter_mut())
+            .find(|opening| opening.id == "door-sauna-wash")
+            .unwrap();
+        door.glazed = false;
+        let door_area = door.width * door.height;
+        assert!((glazed - sauna_heat_loss(&model).glass_area - door_area).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,239 @@
+use std::fs;
+
+use dxf::entities::*;
+use dxf::{Drawing, Point};
+
+use crate::constants::*;
+use crate::helpers::polyline_from_points;
+use crate::layers::*;
+
+// ============================================================================
+// TITLE BLOCK TEMPLATES
+// A template lists the borders, dividers, logo and text fields of the title
+// block as fractions of its size; text fields are filled from the project
+// metadata. See templates/title_block.txt for the format.
+// ============================================================================
+
+const DEFAULT_TEMPLATE: &str = include_str!("../templates/title_block.txt");
+
+const PLACEHOLDERS: [&str; 12] = [
+    "project",
+    "sheet_title",
+    "client",
+    "address",
+    "project_number",
+    "drawn_by",
+    "checked_by",
+    "sheet",
+    "sheets",
+    "scale",
+    "date",
+    "rev",
+];
+
+enum Item {
+    Border,
+    Line(f64, f64, f64, f64),
+    Logo(f64, f64, f64, f64),
+    Text { x: f64, y: f64, height: f64, value: String },
+}
+
+pub struct TitleBlockTemplate {
+    pub width: f64,
+    pub height: f64,
+    items: Vec<Item>,
+}
+
+/// Value of a template placeholder from the project metadata
+fn placeholder_value(name: &str) -> String {
+    let revision = current_revision();
+    match name {
+        "project" => PROJECT_NAME.to_string(),
+        "sheet_title" => SHEET_TITLE.to_string(),
+        "client" => PROJECT_CLIENT.to_string(),
+        "address" => PROJECT_ADDRESS.to_string(),
+        "project_number" => PROJECT_NUMBER.to_string(),
+        "drawn_by" => DRAWN_BY.to_string(),
+        "checked_by" => CHECKED_BY.to_string(),
+        "sheet" => SHEET_NUMBER.to_string(),
+        "sheets" => SHEET_COUNT.to_string(),
+        "scale" => DRAWING_SCALE.to_string(),
+        "date" => revision.date.to_string(),
+        "rev" => revision.number.to_string(),
+        _ => unreachable!("placeholders are checked when the template is parsed"),
+    }
+}
+
+/// Replace every {placeholder} in a text field
+fn fill(value: &str) -> String {
+    let mut text = value.to_string();
+    for name in PLACEHOLDERS {
+        text = text.replace(&format!("{{{}}}", name), &placeholder_value(name));
+    }
+    text
+}
+
+/// Project monogram for the logo: initials of the project name
+fn monogram() -> String {
+    PROJECT_NAME.split_whitespace().filter_map(|word| word.chars().next()).collect()
+}
+
+fn numbers<const N: usize>(fields: &[&str], line: usize) -> Result<[f64; N], String> {
+    if fields.len() != N {
+        return Err(format!("line {}: expected {} numbers, found {}", line, N, fields.len()));
+    }
+    let mut values = [0.0; N];
+    for (value, field) in values.iter_mut().zip(fields) {
+        *value = field
+            .parse()
+            .map_err(|_| format!("line {}: '{}' is not a number", line, field))?;
+    }
+    Ok(values)
+}
+
+/// The rest of a line after its first `count` whitespace-separated fields,
+/// however many spaces or tabs separate them
+fn after_fields(line: &str, count: usize) -> &str {
+    let mut rest = line;
+    for _ in 0..count {
+        rest = rest.trim_start();
+        rest = &rest[rest.find(char::is_whitespace).unwrap_or(rest.len())..];
+    }
+    rest.trim()
+}
+
+impl TitleBlockTemplate {
+    /// Parse a template; errors name the offending line
+    pub fn parse(text: &str) -> Result<TitleBlockTemplate, String> {
+        let mut size = None;
+        let mut items = Vec::new();
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            let raw = raw.trim();
+            if raw.is_empty() || raw.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = raw.split_whitespace().collect();
+            match fields[0] {
+                "size" => size = Some(numbers::<2>(&fields[1..], line)?),
+                "border" => items.push(Item::Border),
+                "line" => {
+                    let [x1, y1, x2, y2] = numbers(&fields[1..], line)?;
+                    items.push(Item::Line(x1, y1, x2, y2));
+                }
+                "logo" => {
+                    let [x1, y1, x2, y2] = numbers(&fields[1..], line)?;
+                    items.push(Item::Logo(x1, y1, x2, y2));
+                }
+                "text" => {
+                    if fields.len() < 5 {
+                        return Err(format!("line {}: expected text <x> <y> <style> <value>", line));
+                    }
+                    let [x, y] = numbers(&fields[1..3], line)?;
+                    let height = match fields[3] {
+                        "title" => TITLE_TEXT_HEIGHT,
+                        "label" => LABEL_TEXT_HEIGHT,
+                        "dim" => DIM_TEXT_HEIGHT,
+                        other => return Err(format!("line {}: unknown text style '{}'", line, other)),
+                    };
+                    // Keep the value as written after the style keyword
+                    let value = after_fields(raw, 4);
+                    let mut rest = value;
+                    while let Some(start) = rest.find('{') {
+                        let end = rest[start..]
+                            .find('}')
+                            .ok_or_else(|| format!("line {}: unclosed '{{'", line))?;
+                        let name = &rest[start + 1..start + end];
+                        if !PLACEHOLDERS.contains(&name) {
+                            return Err(format!("line {}: unknown placeholder {{{}}}", line, name));
+                        }
+                        rest = &rest[start + end + 1..];
+                    }
+                    items.push(Item::Text { x, y, height, value: value.to_string() });
+                }
+                other => return Err(format!("line {}: unknown item '{}'", line, other)),
+            }
+        }
+        let [width, height] = size.ok_or("missing 'size <width> <height>' line")?;
+        if width <= 0.0 || height <= 0.0 {
+            return Err("title block size must be positive".to_string());
+        }
+        Ok(TitleBlockTemplate { width, height, items })
+    }
+
+    /// Load a template file
+    pub fn load(path: &str) -> Result<TitleBlockTemplate, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        TitleBlockTemplate::parse(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    /// Draw the title block with its lower-left corner at (x, y)
+    pub fn draw(&self, drawing: &mut Drawing, x: f64, y: f64) {
+        let at = |fx: f64, fy: f64| Point::new(x + self.width * fx, y + self.height * fy, 0.0);
+        let rectangle = |x1: f64, y1: f64, x2: f64, y2: f64| {
+            polyline_from_points(vec![at(x1, y1), at(x2, y1), at(x2, y2), at(x1, y2), at(x1, y1)])
+        };
+
+        for item in &self.items {
+            match item {
+                Item::Border => {
+                    let mut entity = Entity::new(EntityType::LwPolyline(rectangle(0.0, 0.0, 1.0, 1.0)));
+                    entity.common.layer = LAYER_TITLE.to_string();
+                    drawing.add_entity(entity);
+                }
+                Item::Line(x1, y1, x2, y2) => {
+                    let line = Line {
+                        p1: at(*x1, *y1),
+                        p2: at(*x2, *y2),
+                        ..Default::default()
+                    };
+                    let mut entity = Entity::new(EntityType::Line(line));
+                    entity.common.layer = LAYER_TITLE.to_string();
+                    drawing.add_entity(entity);
+                }
+                Item::Logo(x1, y1, x2, y2) => {
+                    let mut entity = Entity::new(EntityType::LwPolyline(rectangle(*x1, *y1, *x2, *y2)));
+                    entity.common.layer = LAYER_TITLE.to_string();
+                    drawing.add_entity(entity);
+
+                    // Monogram centred in the frame
+                    let text = monogram();
+                    let (box_width, box_height) = (self.width * (x2 - x1), self.height * (y2 - y1));
+                    let text_height = TITLE_TEXT_HEIGHT.min(box_height * 0.6);
+                    let text_width = text.chars().count() as f64 * text_height * 0.8;
+                    let location = at(*x1, *y1);
+                    let logo = Text {
+                        location: Point::new(
+                            location.x + (box_width - text_width) / 2.0,
+                            location.y + (box_height - text_height) / 2.0,
+                            0.0,
+                        ),
+                        text_height,
+                        value: text,
+                        ..Default::default()
+                    };
+                    let mut entity = Entity::new(EntityType::Text(logo));
+                    entity.common.layer = LAYER_TITLE.to_string();
+                    drawing.add_entity(entity);
+                }
+                Item::Text { x: fx, y: fy, height, value } => {
+                    let text = Text {
+                        location: at(*fx, *fy),
+                        text_height: *height,
+                        value: fill(value),
+                        ..Default::default()
+                    };
+                    let mut entity = Entity::new(EntityType::Text(text));
+                    entity.common.layer = LAYER_TITLE.to_string();
+                    drawing.add_entity(entity);
+                }
+            }
+        }
+    }
+}
+
+impl Default for TitleBlockTemplate {
+    fn default() -> Self {
+        TitleBlockTemplate::parse(DEFAULT_TEMPLATE).expect("built-in title block template is valid")
+    }
+}
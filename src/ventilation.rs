@@ -0,0 +1,277 @@
+use dxf::Drawing;
+
+use crate::constants::*;
+use crate::helpers::{add_line, add_rectangle, add_text, draw_arrow};
+use crate::layers::*;
+use crate::model::{BuildingModel, Room, Vent, VentKind, Wall};
+
+// ============================================================================
+// SAUNA VENTILATION
+// Intake and exhaust grilles through the walls of the sauna room. Fresh air
+// should come in low near the heater and leave high on the far side of the
+// room; together the grilles must pass SAUNA_AIR_CHANGES room volumes an hour
+// at VENT_AIR_SPEED.
+// ============================================================================
+
+/// Arrow head size for airflow arrows
+const ARROW_HEAD: f64 = 0.10;
+
+pub struct Ventilation {
+    pub volume: f64,        // m³
+    pub required_flow: f64, // m³/h
+    pub required_area: f64, // m² free area, each way
+    pub intake_area: f64,   // m²
+    pub exhaust_area: f64,  // m²
+    pub problems: Vec<String>,
+}
+
+fn heater_centre(model: &BuildingModel) -> Option<(f64, f64)> {
+    model.furnishing("heater").map(|heater| heater.footprint.centre())
+}
+
+/// Plan centre of a vent on its wall's centre line, and the unit vector
+/// pointing into the room
+fn vent_plan(wall: &Wall, vent: &Vent, room: &Room) -> ((f64, f64), (f64, f64)) {
+    let (room_x, room_y) = ((room.x1 + room.x2) / 2.0, (room.y1 + room.y2) / 2.0);
+    if wall.is_horizontal() {
+        let y = (wall.y1 + wall.y2) / 2.0;
+        ((wall.x1 + vent.offset, y), (0.0, (room_y - y).signum()))
+    } else {
+        let x = (wall.x1 + wall.x2) / 2.0;
+        ((x, wall.y1 + vent.offset), ((room_x - x).signum(), 0.0))
+    }
+}
+
+impl VentKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            VentKind::Intake => "INTAKE",
+            VentKind::Exhaust => "EXHAUST",
+        }
+    }
+}
+
+/// Check the sauna vents against the placement rules and the air change rate
+pub fn sauna_ventilation(model: &BuildingModel) -> Ventilation {
+    let room = model.sauna();
+    let volume = (room.x2 - room.x1) * (room.y2 - room.y1) * BUILDING_HEIGHT;
+    let required_flow = volume * SAUNA_AIR_CHANGES;
+    let required_area = required_flow / 3600.0 / VENT_AIR_SPEED;
+    let heater = heater_centre(model);
+    let mut problems = Vec::new();
+    let mut intakes = Vec::new();
+    let mut exhausts = Vec::new();
+
+    for vent in &model.vents {
+        let name = vent.id.to_uppercase();
+        let Some(wall) = model.wall(vent.wall) else {
+            problems.push(format!("{}: NO WALL {}", name, vent.wall.to_uppercase()));
+            continue;
+        };
+        let Some((start, end)) = wall.faces_room(room) else {
+            problems.push(format!("{}: {} DOES NOT BOUND THE SAUNA", name, vent.wall.to_uppercase()));
+            continue;
+        };
+
+        // Grille inside the room face, clear of doors and windows
+        let half = vent.size / 2.0;
+        if vent.offset - half < start || vent.offset + half > end || vent.height - half < 0.0 || vent.height + half > BUILDING_HEIGHT {
+            problems.push(format!("{}: OUTSIDE THE SAUNA FACE OF {}", name, vent.wall.to_uppercase()));
+        }
+        for opening in &wall.openings {
+            let clear_along = vent.offset + half <= opening.offset || vent.offset - half >= opening.offset + opening.width;
+            let clear_up = vent.height + half <= opening.sill || vent.height - half >= opening.sill + opening.height;
+            if !clear_along && !clear_up {
+                problems.push(format!("{}: OVERLAPS {}", name, opening.id.to_uppercase()));
+            }
+        }
+
+        let (centre, _) = vent_plan(wall, vent, room);
+        match vent.kind {
+            VentKind::Intake => {
+                if vent.height > INTAKE_MAX_HEIGHT {
+                    problems.push(format!(
+                        "{}: INTAKE AT {:.2} m, NOT LOW (MAX {:.2} m)",
+                        name, vent.height, INTAKE_MAX_HEIGHT
+                    ));
+                }
+                if let Some((hx, hy)) = heater {
+                    let distance = ((centre.0 - hx).powi(2) + (centre.1 - hy).powi(2)).sqrt();
+                    if distance > INTAKE_MAX_HEATER_DISTANCE {
+                        problems.push(format!(
+                            "{}: {:.2} m FROM THE HEATER (MAX {:.2} m)",
+                            name, distance, INTAKE_MAX_HEATER_DISTANCE
+                        ));
+                    }
+                }
+                intakes.push((vent, centre));
+            }
+            VentKind::Exhaust => {
+                if vent.height < EXHAUST_MIN_HEIGHT {
+                    problems.push(format!(
+                        "{}: EXHAUST AT {:.2} m, NOT HIGH (MIN {:.2} m)",
+                        name, vent.height, EXHAUST_MIN_HEIGHT
+                    ));
+                }
+                exhausts.push((vent, centre));
+            }
+        }
+    }
+
+    // Exhausts across the room from every intake so the air sweeps it
+    for (exhaust, (ex, ey)) in &exhausts {
+        for (intake, (ix, iy)) in &intakes {
+            let distance = ((ex - ix).powi(2) + (ey - iy).powi(2)).sqrt();
+            if distance < VENT_MIN_SEPARATION {
+                problems.push(format!(
+                    "{}: {:.2} m FROM {} (MIN {:.2} m)",
+                    exhaust.id.to_uppercase(),
+                    distance,
+                    intake.id.to_uppercase(),
+                    VENT_MIN_SEPARATION
+                ));
+            }
+        }
+    }
+
+    let intake_area: f64 = intakes.iter().map(|(vent, _)| vent.size * vent.size).sum();
+    let exhaust_area: f64 = exhausts.iter().map(|(vent, _)| vent.size * vent.size).sum();
+    for (kind, area) in [(VentKind::Intake, intake_area), (VentKind::Exhaust, exhaust_area)] {
+        if area == 0.0 {
+            problems.push(format!("NO {} VENT IN THE SAUNA", kind.name()));
+        } else if area < required_area {
+            problems.push(format!(
+                "{} FREE AREA {:.3} m² BELOW {:.3} m² NEEDED",
+                kind.name(),
+                area,
+                required_area
+            ));
+        }
+    }
+
+    Ventilation { volume, required_flow, required_area, intake_area, exhaust_area, problems }
+}
+
+impl Ventilation {
+    /// Report lines for the sheet and the console
+    pub fn lines(&self, model: &BuildingModel) -> Vec<String> {
+        let mut lines = vec![
+            format!(
+                "SAUNA {:.2} m³ x {:.0} AIR CHANGES/h = {:.0} m³/h ({:.0} l/s)",
+                self.volume,
+                SAUNA_AIR_CHANGES,
+                self.required_flow,
+                self.required_flow / 3.6
+            ),
+            format!(
+                "FREE AREA NEEDED {:.3} m² EACH WAY AT {:.1} m/s: INTAKE {:.3} m², EXHAUST {:.3} m²",
+                self.required_area, VENT_AIR_SPEED, self.intake_area, self.exhaust_area
+            ),
+        ];
+        for vent in &model.vents {
+            lines.push(format!(
+                "{:<8} {:<20} {:.0}x{:.0} AT {:.2} m IN {}",
+                vent.kind.name(),
+                vent.id.to_uppercase(),
+                vent.size * 1000.0,
+                vent.size * 1000.0,
+                vent.height,
+                vent.wall.to_uppercase()
+            ));
+        }
+        if self.problems.is_empty() {
+            lines.push("VENTILATION MEETS THE PLACEMENT RULES AND AIR CHANGE RATE".to_string());
+        }
+        lines.extend(self.problems.iter().map(|problem| format!("WARNING: {}", problem)));
+        lines
+    }
+}
+
+/// Grille seen face on: a square with horizontal slats
+fn draw_grille(drawing: &mut Drawing, x: f64, y: f64, size: f64) {
+    let half = size / 2.0;
+    add_rectangle(drawing, LAYER_VENTS, x - half, y - half, x + half, y + half);
+    for i in 1..4 {
+        let slat_y = y - half + size * i as f64 / 4.0;
+        add_line(drawing, LAYER_VENTS, x - half, slat_y, x + half, slat_y);
+    }
+}
+
+/// Vents in plan: the grille through the wall and an arrow in or out of the
+/// room, labelled outside the building
+pub fn draw_vents_plan(drawing: &mut Drawing, model: &BuildingModel) {
+    let room = model.sauna();
+    for vent in &model.vents {
+        let Some(wall) = model.wall(vent.wall) else { continue };
+        let ((cx, cy), (nx, ny)) = vent_plan(wall, vent, room);
+        let (half, depth) = (vent.size / 2.0, wall.thickness() / 2.0);
+
+        // Grille through the wall thickness
+        let (ax, ay) = (ny.abs() * half + nx.abs() * depth, nx.abs() * half + ny.abs() * depth);
+        add_rectangle(drawing, LAYER_VENTS, cx - ax, cy - ay, cx + ax, cy + ay);
+
+        // Airflow across the wall, into the room for intakes
+        let reach = depth + 0.35;
+        let (outside, inside) = ((cx - nx * reach, cy - ny * reach), (cx + nx * reach, cy + ny * reach));
+        let (from, to) = match vent.kind {
+            VentKind::Intake => (outside, inside),
+            VentKind::Exhaust => (inside, outside),
+        };
+        draw_arrow(drawing, LAYER_VENTS, from.0, from.1, to.0, to.1, ARROW_HEAD);
+        add_text(
+            drawing,
+            LAYER_ANNO,
+            outside.0 + 0.08,
+            outside.1 - DIM_TEXT_HEIGHT / 2.0,
+            DIM_TEXT_HEIGHT,
+            vent.kind.name().to_string(),
+        );
+    }
+}
+
+/// Vents on the south face in the front elevation, drawn from outside
+pub fn draw_vents_front_elevation(drawing: &mut Drawing, model: &BuildingModel, offset_x: f64, offset_y: f64) {
+    let room = model.sauna();
+    for vent in &model.vents {
+        let Some(wall) = model.wall(vent.wall) else { continue };
+        let ((cx, _), (_, ny)) = vent_plan(wall, vent, room);
+        if wall.exterior && ny > 0.0 {
+            draw_grille(drawing, offset_x + cx, offset_y + DECK_ELEV_HEIGHT + vent.height, vent.size);
+        }
+    }
+}
+
+/// Vents and the airflow path through the sauna in section A-A, which looks
+/// south so model x runs from right to left
+pub fn draw_vents_section(drawing: &mut Drawing, model: &BuildingModel, offset_x: f64, offset_y: f64) {
+    let room = model.sauna();
+    let floor = offset_y + DECK_ELEV_HEIGHT;
+    let section_x = |x: f64| offset_x + SAUNA_WIDTH - x;
+
+    let mut intake = None;
+    let mut exhaust = None;
+    for vent in &model.vents {
+        let Some(wall) = model.wall(vent.wall) else { continue };
+        let ((cx, _), _) = vent_plan(wall, vent, room);
+        let (x, y) = (section_x(cx), floor + vent.height);
+        draw_grille(drawing, x, y, vent.size);
+        match vent.kind {
+            VentKind::Intake => intake = intake.or(Some((x, y))),
+            VentKind::Exhaust => exhaust = exhaust.or(Some((x, y))),
+        }
+    }
+
+    // Fresh air up past the heater, across under the ceiling and out
+    if let (Some(intake), Some(exhaust), Some((heater_x, _))) = (intake, exhaust, heater_centre(model)) {
+        let heater_x = section_x(heater_x);
+        let path = [
+            intake,
+            (heater_x, floor + model.heater.height + 0.30),
+            (heater_x, floor + BUILDING_HEIGHT - 0.30),
+            exhaust,
+        ];
+        for pair in path.windows(2) {
+            draw_arrow(drawing, LAYER_VENTS, pair[0].0, pair[0].1, pair[1].0, pair[1].1, ARROW_HEAD);
+        }
+    }
+}
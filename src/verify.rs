@@ -0,0 +1,163 @@
+use std::collections::{BTreeMap, HashSet};
+
+use dxf::entities::*;
+use dxf::Drawing;
+
+use crate::layers::{LAYER_MODEL_WALLS, LAYER_SURVEY_PREFIX};
+use crate::model::BuildingModel;
+use crate::sheet::build_drawing;
+use crate::title_block::TitleBlockTemplate;
+
+const POINT_TOLERANCE: f64 = 1e-9;
+
+/// True if a point list ends where it starts (a closed outline drawn open)
+fn repeats_first_point(points: &[(f64, f64)]) -> bool {
+    match (points.first(), points.last()) {
+        (Some(first), Some(last)) if points.len() > 2 => {
+            (first.0 - last.0).abs() < POINT_TOLERANCE && (first.1 - last.1).abs() < POINT_TOLERANCE
+        }
+        _ => false,
+    }
+}
+
+/// DXF type name for the entities the generator writes
+pub fn entity_kind(specific: &EntityType) -> &'static str {
+    match specific {
+        EntityType::Line(_) => "LINE",
+        EntityType::LwPolyline(_) => "LWPOLYLINE",
+        EntityType::Polyline(_) => "POLYLINE",
+        EntityType::Circle(_) => "CIRCLE",
+        EntityType::Arc(_) => "ARC",
+        EntityType::Text(_) => "TEXT",
+        EntityType::MText(_) => "MTEXT",
+        EntityType::Face3D(_) => "3DFACE",
+        EntityType::Insert(_) => "INSERT",
+        _ => "ENTITY",
+    }
+}
+
+/// Entity counts per layer
+fn layer_counts(drawing: &Drawing) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for entity in drawing.entities() {
+        *counts.entry(entity.common.layer.clone()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Problems that would make a CAD application reject or misread the drawing
+pub fn check_drawing(drawing: &Drawing) -> Vec<String> {
+    let layers: HashSet<&str> = drawing.layers().map(|layer| layer.name.as_str()).collect();
+    let mut problems = Vec::new();
+
+    for (index, entity) in drawing.entities().enumerate() {
+        let kind = entity_kind(&entity.specific);
+
+        // Every entity must sit on a layer defined in the LAYER table
+        if !layers.contains(entity.common.layer.as_str()) {
+            problems.push(format!(
+                "#{} {}: layer '{}' is not in the LAYER table",
+                index, kind, entity.common.layer
+            ));
+        }
+
+        match &entity.specific {
+            // Outlines that repeat their first point must carry the closed flag
+            EntityType::LwPolyline(polyline) => {
+                let points: Vec<(f64, f64)> = polyline.vertices.iter().map(|v| (v.x, v.y)).collect();
+                if repeats_first_point(&points) && !polyline.is_closed() {
+                    problems.push(format!(
+                        "#{} {} on {}: repeats its first point but is not flagged closed",
+                        index, kind, entity.common.layer
+                    ));
+                }
+            }
+            EntityType::Polyline(polyline) => {
+                let points: Vec<(f64, f64)> = polyline
+                    .vertices()
+                    .map(|v| (v.location.x, v.location.y))
+                    .collect();
+                if repeats_first_point(&points) && !polyline.is_closed() {
+                    problems.push(format!(
+                        "#{} {} on {}: repeats its first point but is not flagged closed",
+                        index, kind, entity.common.layer
+                    ));
+                }
+            }
+            // Text must have a positive height
+            EntityType::Text(text) if text.text_height <= 0.0 => {
+                problems.push(format!(
+                    "#{} {} '{}': text height {} is not positive",
+                    index, kind, text.value, text.text_height
+                ));
+            }
+            EntityType::MText(text) if text.initial_text_height <= 0.0 => {
+                problems.push(format!(
+                    "#{} {} '{}': text height {} is not positive",
+                    index, kind, text.text, text.initial_text_height
+                ));
+            }
+            _ => {}
+        }
+    }
+    problems
+}
+
+/// Load a DXF file, check it and compare its layer contents with the sheet
+/// the model and title block produce. Returns false if the file cannot be
+/// loaded or has problems.
+pub fn verify_file(path: &str, model: &BuildingModel, title_block: &TitleBlockTemplate) -> bool {
+    let drawing = match Drawing::load_file(path) {
+        Ok(drawing) => drawing,
+        Err(e) => {
+            eprintln!("Error loading {}: {}", path, e);
+            return false;
+        }
+    };
+
+    println!("========================================");
+    println!("  VERIFY: {}", path);
+    println!("========================================");
+    println!();
+
+    // Regenerate the sheet from the model to know what each layer should hold
+    let model_3d = drawing.entities().any(|entity| entity.common.layer == LAYER_MODEL_WALLS);
+    let expected = layer_counts(&build_drawing(model, model_3d, title_block));
+    // Survey underlay layers come from outside the model
+    let mut actual = layer_counts(&drawing);
+    actual.retain(|name, _| !name.starts_with(LAYER_SURVEY_PREFIX));
+
+    println!("ENTITIES PER LAYER:      file   model");
+    let mut layer_names: Vec<&String> = expected.keys().chain(actual.keys()).collect();
+    layer_names.sort();
+    layer_names.dedup();
+    let mut mismatches = 0;
+    for name in layer_names {
+        let file_count = actual.get(name).copied().unwrap_or(0);
+        let model_count = expected.get(name).copied().unwrap_or(0);
+        let marker = if file_count == model_count {
+            ""
+        } else {
+            mismatches += 1;
+            "  <- differs"
+        };
+        println!("  {:<20} {:>7} {:>7}{}", name, file_count, model_count, marker);
+    }
+    println!();
+
+    let problems = check_drawing(&drawing);
+    if problems.is_empty() {
+        println!("No problems found.");
+    } else {
+        println!("PROBLEMS ({}):", problems.len());
+        for problem in &problems {
+            println!("  {}", problem);
+        }
+    }
+    if mismatches > 0 {
+        println!("Warning: {} layer(s) differ from the current model (file may be out of date)", mismatches);
+    }
+    println!();
+
+    problems.is_empty()
+}
@@ -0,0 +1,201 @@
+use dxf::entities::*;
+use dxf::{Drawing, Point};
+
+use crate::constants::*;
+use crate::layers::*;
+use crate::model::*;
+
+const EPSILON: f64 = 1e-6;
+
+/// Parallel projection with the x axis drawn at `angle_x` and the y axis at
+/// `angle_y` above horizontal (30°/30° gives a true isometric)
+struct Projection {
+    x_axis: (f64, f64),
+    y_axis: (f64, f64),
+    view: (f64, f64, f64), // Viewing direction, pointing into the scene
+}
+
+/// Projected point: screen position plus distance along the view direction
+#[derive(Clone, Copy)]
+struct ScreenPoint {
+    x: f64,
+    y: f64,
+    depth: f64,
+}
+
+/// Box face in screen space with its depth plane (depth = a·x + b·y + c)
+struct ScreenFace {
+    corners: [ScreenPoint; 4],
+    plane: (f64, f64, f64),
+}
+
+impl Projection {
+    fn new(angle_x: f64, angle_y: f64) -> Projection {
+        let (ax, ay) = (angle_x.to_radians(), angle_y.to_radians());
+        Projection {
+            x_axis: (ax.cos(), ax.sin()),
+            y_axis: (-ay.cos(), ay.sin()),
+            view: (ay.cos(), ax.cos(), -(ax + ay).sin()),
+        }
+    }
+
+    fn project(&self, p: &Point) -> ScreenPoint {
+        ScreenPoint {
+            x: p.x * self.x_axis.0 + p.y * self.y_axis.0,
+            y: p.x * self.x_axis.1 + p.y * self.y_axis.1 + p.z,
+            depth: p.x * self.view.0 + p.y * self.view.1 + p.z * self.view.2,
+        }
+    }
+}
+
+impl ScreenFace {
+    /// Build a face, or None if it is seen edge-on
+    fn new(corners: [ScreenPoint; 4]) -> Option<ScreenFace> {
+        let [a, b, c, _] = corners;
+        let det = (b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y);
+        if det.abs() < EPSILON {
+            return None;
+        }
+        // Solve the depth plane through three corners
+        let pa = ((b.depth - a.depth) * (c.y - a.y) - (c.depth - a.depth) * (b.y - a.y)) / det;
+        let pb = ((c.depth - a.depth) * (b.x - a.x) - (b.depth - a.depth) * (c.x - a.x)) / det;
+        let pc = a.depth - pa * a.x - pb * a.y;
+        Some(ScreenFace { corners, plane: (pa, pb, pc) })
+    }
+
+    /// True if (x, y) lies strictly inside the projected quad
+    fn contains(&self, x: f64, y: f64) -> bool {
+        let mut sign = 0.0;
+        for i in 0..4 {
+            let a = self.corners[i];
+            let b = self.corners[(i + 1) % 4];
+            let cross = (b.x - a.x) * (y - a.y) - (b.y - a.y) * (x - a.x);
+            if cross.abs() < EPSILON {
+                return false;
+            }
+            if sign == 0.0 {
+                sign = cross.signum();
+            } else if cross.signum() != sign {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn depth_at(&self, x: f64, y: f64) -> f64 {
+        self.plane.0 * x + self.plane.1 * y + self.plane.2
+    }
+}
+
+/// Parameter along p→q where it crosses segment a→b, if it does
+fn crossing(p: ScreenPoint, q: ScreenPoint, a: ScreenPoint, b: ScreenPoint) -> Option<f64> {
+    let (rx, ry) = (q.x - p.x, q.y - p.y);
+    let (sx, sy) = (b.x - a.x, b.y - a.y);
+    let denom = rx * sy - ry * sx;
+    if denom.abs() < EPSILON {
+        return None;
+    }
+    let t = ((a.x - p.x) * sy - (a.y - p.y) * sx) / denom;
+    let u = ((a.x - p.x) * ry - (a.y - p.y) * rx) / denom;
+    if t > EPSILON && t < 1.0 - EPSILON && (-EPSILON..=1.0 + EPSILON).contains(&u) {
+        Some(t)
+    } else {
+        None
+    }
+}
+
+/// Visible pieces of the edge p→q, as parameter ranges along it
+fn visible_ranges(p: ScreenPoint, q: ScreenPoint, faces: &[ScreenFace]) -> Vec<(f64, f64)> {
+    let mut cuts = vec![0.0, 1.0];
+    for face in faces {
+        for i in 0..4 {
+            if let Some(t) = crossing(p, q, face.corners[i], face.corners[(i + 1) % 4]) {
+                cuts.push(t);
+            }
+        }
+    }
+    cuts.sort_by(|a, b| a.total_cmp(b));
+
+    let mut ranges: Vec<(f64, f64)> = Vec::new();
+    for pair in cuts.windows(2) {
+        let (t1, t2) = (pair[0], pair[1]);
+        if t2 - t1 < EPSILON {
+            continue;
+        }
+        let t = (t1 + t2) / 2.0;
+        let x = p.x + (q.x - p.x) * t;
+        let y = p.y + (q.y - p.y) * t;
+        let depth = p.depth + (q.depth - p.depth) * t;
+        let hidden = faces
+            .iter()
+            .any(|face| face.contains(x, y) && face.depth_at(x, y) < depth - EPSILON);
+        if hidden {
+            continue;
+        }
+        // Merge with the previous piece when they touch
+        match ranges.last_mut() {
+            Some(last) if (last.1 - t1).abs() < EPSILON => last.1 = t2,
+            _ => ranges.push((t1, t2)),
+        }
+    }
+    ranges
+}
+
+pub fn create_iso_view(drawing: &mut Drawing, model: &BuildingModel, offset_x: f64, offset_y: f64) {
+    let projection = Projection::new(AXO_ANGLE_X, AXO_ANGLE_Y);
+    let volumes = model.volumes();
+
+    // Project every box once
+    let projected: Vec<[ScreenPoint; 8]> = volumes
+        .iter()
+        .map(|volume| volume.corners().map(|corner| projection.project(&corner)))
+        .collect();
+
+    let faces: Vec<ScreenFace> = projected
+        .iter()
+        .flat_map(|corners| {
+            BOX_FACES
+                .iter()
+                .filter_map(|face| ScreenFace::new(face.map(|i| corners[i])))
+        })
+        .collect();
+
+    // Shift the view so its lower-left extent sits at the offset
+    let min_x = projected.iter().flatten().map(|p| p.x).fold(f64::INFINITY, f64::min);
+    let min_y = projected.iter().flatten().map(|p| p.y).fold(f64::INFINITY, f64::min);
+    let max_y = projected.iter().flatten().map(|p| p.y).fold(f64::NEG_INFINITY, f64::max);
+    let to_sheet = |x: f64, y: f64| Point::new(offset_x + x - min_x, offset_y + y - min_y, 0.0);
+
+    // Hidden line removal: draw only the visible pieces of each box edge
+    for corners in &projected {
+        for [i, j] in BOX_EDGES {
+            let (p, q) = (corners[i], corners[j]);
+            for (t1, t2) in visible_ranges(p, q, &faces) {
+                let line = Line {
+                    p1: to_sheet(p.x + (q.x - p.x) * t1, p.y + (q.y - p.y) * t1),
+                    p2: to_sheet(p.x + (q.x - p.x) * t2, p.y + (q.y - p.y) * t2),
+                    ..Default::default()
+                };
+                let mut entity = Entity::new(EntityType::Line(line));
+                entity.common.layer = LAYER_ISO.to_string();
+                drawing.add_entity(entity);
+            }
+        }
+    }
+
+    // Label
+    let title = if AXO_ANGLE_X == 30.0 && AXO_ANGLE_Y == 30.0 {
+        "ISOMETRIC VIEW".to_string()
+    } else {
+        format!("AXONOMETRIC VIEW {:.0}°/{:.0}°", AXO_ANGLE_X, AXO_ANGLE_Y)
+    };
+    let label = Text {
+        location: Point::new(offset_x, offset_y + max_y - min_y + 0.50, 0.0),
+        text_height: LABEL_TEXT_HEIGHT,
+        value: title,
+        ..Default::default()
+    };
+    let mut entity = Entity::new(EntityType::Text(label));
+    entity.common.layer = LAYER_TEXT.to_string();
+    drawing.add_entity(entity);
+}
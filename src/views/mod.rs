@@ -2,8 +2,10 @@ pub mod front;
 pub mod top;
 pub mod side;
 pub mod section;
+pub mod iso;
 
 pub use front::create_front_elevation;
 pub use top::create_top_view;
 pub use side::create_side_elevation;
 pub use section::create_section_cut;
+pub use iso::create_iso_view;
@@ -63,41 +63,33 @@ pub fn create_section_cut(drawing: &mut Drawing, offset_x: f64, offset_y: f64) {
     entity.common.layer = LAYER_SECTION.to_string();
     drawing.add_entity(entity);
 
-    // Lower bench
-    let bench_lower = polyline_from_points(vec![
-        Point::new(offset_x + WALL_THICKNESS + 0.10, offset_y + DECK_ELEV_HEIGHT + BENCH_HEIGHT_LOWER, 0.0),
-        Point::new(offset_x + WALL_THICKNESS + 0.10 + BENCH_DEPTH, offset_y + DECK_ELEV_HEIGHT + BENCH_HEIGHT_LOWER, 0.0),
-        Point::new(offset_x + WALL_THICKNESS + 0.10 + BENCH_DEPTH, offset_y + DECK_ELEV_HEIGHT + BENCH_HEIGHT_LOWER + 0.05, 0.0),
-        Point::new(offset_x + WALL_THICKNESS + 0.10, offset_y + DECK_ELEV_HEIGHT + BENCH_HEIGHT_LOWER + 0.05, 0.0),
-        Point::new(offset_x + WALL_THICKNESS + 0.10, offset_y + DECK_ELEV_HEIGHT + BENCH_HEIGHT_LOWER, 0.0),
-    ]);
-    let mut entity = Entity::new(EntityType::LwPolyline(bench_lower));
-    entity.common.layer = LAYER_FIXTURES.to_string();
-    drawing.add_entity(entity);
-
-    // Upper bench
-    let bench_upper = polyline_from_points(vec![
-        Point::new(offset_x + WALL_THICKNESS + 0.10, offset_y + DECK_ELEV_HEIGHT + BENCH_HEIGHT_UPPER, 0.0),
-        Point::new(offset_x + WALL_THICKNESS + 0.10 + BENCH_DEPTH, offset_y + DECK_ELEV_HEIGHT + BENCH_HEIGHT_UPPER, 0.0),
-        Point::new(offset_x + WALL_THICKNESS + 0.10 + BENCH_DEPTH, offset_y + DECK_ELEV_HEIGHT + BENCH_HEIGHT_UPPER + 0.05, 0.0),
-        Point::new(offset_x + WALL_THICKNESS + 0.10, offset_y + DECK_ELEV_HEIGHT + BENCH_HEIGHT_UPPER + 0.05, 0.0),
-        Point::new(offset_x + WALL_THICKNESS + 0.10, offset_y + DECK_ELEV_HEIGHT + BENCH_HEIGHT_UPPER, 0.0),
-    ]);
-    let mut entity = Entity::new(EntityType::LwPolyline(bench_upper));
-    entity.common.layer = LAYER_FIXTURES.to_string();
-    drawing.add_entity(entity);
+    // Wall build-up in the right (west, exterior) wall: the section looks
+    // south, so its outside face is on the right
+    for offset in WALL_ASSEMBLY.boundaries() {
+        let x = offset_x + SAUNA_WIDTH - offset;
+        let line = Line {
+            p1: Point::new(x, offset_y + DECK_ELEV_HEIGHT, 0.0),
+            p2: Point::new(x, offset_y + DECK_ELEV_HEIGHT + BUILDING_HEIGHT, 0.0),
+            ..Default::default()
+        };
+        let mut entity = Entity::new(EntityType::Line(line));
+        entity.common.layer = LAYER_WALL_LAYERS.to_string();
+        drawing.add_entity(entity);
+    }
 
-    // Heater
-    let heater = polyline_from_points(vec![
-        Point::new(offset_x + SAUNA_WIDTH - WALL_THICKNESS - 0.60, offset_y + DECK_ELEV_HEIGHT, 0.0),
-        Point::new(offset_x + SAUNA_WIDTH - WALL_THICKNESS - 0.60, offset_y + DECK_ELEV_HEIGHT + 0.70, 0.0),
-        Point::new(offset_x + SAUNA_WIDTH - WALL_THICKNESS - 0.30, offset_y + DECK_ELEV_HEIGHT + 0.70, 0.0),
-        Point::new(offset_x + SAUNA_WIDTH - WALL_THICKNESS - 0.30, offset_y + DECK_ELEV_HEIGHT, 0.0),
-        Point::new(offset_x + SAUNA_WIDTH - WALL_THICKNESS - 0.60, offset_y + DECK_ELEV_HEIGHT, 0.0),
-    ]);
-    let mut entity = Entity::new(EntityType::LwPolyline(heater));
-    entity.common.layer = LAYER_FIXTURES.to_string();
-    drawing.add_entity(entity);
+    // Floor build-up under the sauna, inside face at floor level
+    let floor = FLOOR_ASSEMBLY.thickness();
+    for offset in FLOOR_ASSEMBLY.boundaries().into_iter().chain([0.0]) {
+        let y = offset_y + DECK_ELEV_HEIGHT - floor + offset;
+        let line = Line {
+            p1: Point::new(offset_x, y, 0.0),
+            p2: Point::new(offset_x + SAUNA_WIDTH, y, 0.0),
+            ..Default::default()
+        };
+        let mut entity = Entity::new(EntityType::Line(line));
+        entity.common.layer = LAYER_FLOOR_LAYERS.to_string();
+        drawing.add_entity(entity);
+    }
 
     // Flat roof
     let roof = polyline_from_points(vec![
@@ -119,6 +111,19 @@ pub fn create_section_cut(drawing: &mut Drawing, offset_x: f64, offset_y: f64) {
     entity.common.layer = LAYER_ROOF.to_string();
     drawing.add_entity(entity);
 
+    // Roof build-up, outside face on top
+    let roof_top = offset_y + DECK_ELEV_HEIGHT + BUILDING_HEIGHT + ROOF_THICKNESS;
+    for offset in ROOF_ASSEMBLY.boundaries() {
+        let line = Line {
+            p1: Point::new(offset_x - ROOF_OVERHANG, roof_top - offset, 0.0),
+            p2: Point::new(offset_x + SAUNA_WIDTH + ROOF_OVERHANG, roof_top - offset, 0.0),
+            ..Default::default()
+        };
+        let mut entity = Entity::new(EntityType::Line(line));
+        entity.common.layer = LAYER_ROOF_LAYERS.to_string();
+        drawing.add_entity(entity);
+    }
+
     // Ceiling line
     let ceiling = Line {
         p1: Point::new(offset_x + WALL_THICKNESS, offset_y + DECK_ELEV_HEIGHT + BUILDING_HEIGHT, 0.0),
@@ -131,8 +136,6 @@ pub fn create_section_cut(drawing: &mut Drawing, offset_x: f64, offset_y: f64) {
 
     // Dimensions
     draw_dim_horizontal(drawing, offset_x, offset_x + SAUNA_WIDTH, offset_y + DECK_ELEV_HEIGHT + BUILDING_HEIGHT + ROOF_THICKNESS, 0.30, "");
-    draw_dim_vertical(drawing, offset_x - 0.50, offset_y + DECK_ELEV_HEIGHT, offset_y + DECK_ELEV_HEIGHT + BENCH_HEIGHT_LOWER, -0.20, "");
-    draw_dim_vertical(drawing, offset_x - 0.50, offset_y + DECK_ELEV_HEIGHT, offset_y + DECK_ELEV_HEIGHT + BENCH_HEIGHT_UPPER, -0.40, "");
     draw_dim_vertical(drawing, offset_x + SAUNA_WIDTH, offset_y + DECK_ELEV_HEIGHT, offset_y + DECK_ELEV_HEIGHT + BUILDING_HEIGHT, 0.30, "");
 
     // Label
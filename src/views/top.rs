@@ -42,16 +42,6 @@ pub fn create_top_view(drawing: &mut Drawing, offset_x: f64, offset_y: f64) {
     entity.common.layer = LAYER_WALLS_INNER.to_string();
     drawing.add_entity(entity);
 
-    // Vent/chimney for sauna heater
-    let vent = Circle {
-        center: Point::new(offset_x + HEATER_DIAMETER, offset_y + BUILDING_DEPTH / 2.0, 0.0),
-        radius: 0.15,
-        ..Default::default()
-    };
-    let mut entity = Entity::new(EntityType::Circle(vent));
-    entity.common.layer = LAYER_FIXTURES.to_string();
-    drawing.add_entity(entity);
-
     // Dimensions
     draw_dim_horizontal(drawing, offset_x - ROOF_OVERHANG, offset_x + TOTAL_WIDTH + ROOF_OVERHANG, offset_y + BUILDING_DEPTH + ROOF_OVERHANG, 0.30, "");
     draw_dim_vertical(drawing, offset_x + TOTAL_WIDTH + ROOF_OVERHANG, offset_y - ROOF_OVERHANG, offset_y + BUILDING_DEPTH + ROOF_OVERHANG, 0.30, "");
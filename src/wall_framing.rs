@@ -0,0 +1,256 @@
+use dxf::Drawing;
+
+use crate::constants::*;
+use crate::helpers::{add_line, add_rectangle, add_text};
+use crate::layers::*;
+use crate::model::{BuildingModel, OpeningKind, Wall};
+
+// ============================================================================
+// WALL FRAMING
+// Every wall stands on a bottom plate under TOP_PLATES top plates, with studs
+// at STUD_SPACING centres from its start. Each door and window gets king and
+// jack studs either side and a header over it; windows also get a sill plate.
+// Common studs falling in an opening become cripples above the header and
+// below the sill. Elevations are laid out along the wall from its start
+// (min x or min y) with z up from the finished floor.
+// ============================================================================
+
+/// Header depth for the clear span between king studs
+const HEADER_TABLE: [(f64, f64); 4] = [
+    (1.0, 0.145),
+    (1.5, 0.195),
+    (2.1, 0.245),
+    (3.0, 0.295),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum MemberKind {
+    BottomPlate,
+    TopPlate,
+    Stud,
+    KingStud,
+    JackStud,
+    Header,
+    SillPlate,
+    Cripple,
+}
+
+impl MemberKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            MemberKind::BottomPlate => "BOTTOM PLATE",
+            MemberKind::TopPlate => "TOP PLATE",
+            MemberKind::Stud => "STUD",
+            MemberKind::KingStud => "KING STUD",
+            MemberKind::JackStud => "JACK STUD",
+            MemberKind::Header => "HEADER",
+            MemberKind::SillPlate => "SILL PLATE",
+            MemberKind::Cripple => "CRIPPLE",
+        }
+    }
+}
+
+/// One framing member as a rectangle in wall elevation coordinates
+pub struct Member {
+    pub kind: MemberKind,
+    pub s1: f64,
+    pub z1: f64,
+    pub s2: f64,
+    pub z2: f64,
+    pub section: String,
+}
+
+impl Member {
+    /// Cut length: plates, sills and headers lie along the wall
+    pub fn length(&self) -> f64 {
+        match self.kind {
+            MemberKind::BottomPlate | MemberKind::TopPlate | MemberKind::Header | MemberKind::SillPlate => {
+                self.s2 - self.s1
+            }
+            _ => self.z2 - self.z1,
+        }
+    }
+
+    /// Stock section and number of pieces cut for this member
+    pub fn pieces(&self) -> (String, u32) {
+        match self.kind {
+            MemberKind::Header => (header_section_ply(self.z2 - self.z1), HEADER_PLIES),
+            _ => (self.section.clone(), 1),
+        }
+    }
+}
+
+pub struct WallFraming {
+    pub wall_id: &'static str,
+    pub length: f64,
+    pub height: f64,
+    pub members: Vec<Member>,
+}
+
+fn stud_section() -> String {
+    format!("{:.0}x{:.0}", STUD_WIDTH * 1000.0, STUD_DEPTH * 1000.0)
+}
+
+/// Header depth for a clear span, or the deepest in the table if it is longer
+pub fn header_depth(span: f64) -> f64 {
+    HEADER_TABLE
+        .iter()
+        .find(|&&(max_span, _)| span <= max_span)
+        .unwrap_or(&HEADER_TABLE[HEADER_TABLE.len() - 1])
+        .1
+}
+
+fn header_section_ply(depth: f64) -> String {
+    format!("{:.0}x{:.0}", STUD_WIDTH * 1000.0, depth * 1000.0)
+}
+
+fn header_section(depth: f64) -> String {
+    let size = header_section_ply(depth);
+    if HEADER_PLIES > 1 { format!("{}/{}", HEADER_PLIES, size) } else { size }
+}
+
+/// Frame one wall from its length and openings
+pub fn wall_framing(wall: &Wall) -> WallFraming {
+    let length = wall.length();
+    let height = BUILDING_HEIGHT;
+    let w = STUD_WIDTH;
+    let stud_bottom = w;
+    let stud_top = height - TOP_PLATES as f64 * w;
+    let member = |kind, s1: f64, z1: f64, s2: f64, z2: f64| Member { kind, s1, z1, s2, z2, section: stud_section() };
+
+    let mut members = vec![member(MemberKind::BottomPlate, 0.0, 0.0, length, w)];
+    for i in 0..TOP_PLATES {
+        let z = stud_top + i as f64 * w;
+        members.push(member(MemberKind::TopPlate, 0.0, z, length, z + w));
+    }
+
+    // Common stud positions (left edges), with an end stud flush at the far end
+    let mut studs = Vec::new();
+    let mut s = 0.0;
+    while s < length - 2.0 * w + 1e-9 {
+        studs.push(s);
+        s += STUD_SPACING;
+    }
+    studs.push(length - w);
+
+    // King and jack studs take the place of common studs beside an opening
+    let framed = |s: f64| {
+        wall.openings
+            .iter()
+            .any(|o| s + w > o.offset - 2.0 * w + 1e-9 && s < o.offset + o.width + 2.0 * w - 1e-9)
+    };
+    for &s in &studs {
+        if !framed(s) {
+            members.push(member(MemberKind::Stud, s, stud_bottom, s + w, stud_top));
+        }
+    }
+
+    for opening in &wall.openings {
+        let (left, right) = (opening.offset, opening.offset + opening.width);
+        let head = opening.sill + opening.height;
+        let depth = header_depth(opening.width + 2.0 * w);
+
+        members.push(member(MemberKind::KingStud, left - 2.0 * w, stud_bottom, left - w, stud_top));
+        members.push(member(MemberKind::KingStud, right + w, stud_bottom, right + 2.0 * w, stud_top));
+        members.push(member(MemberKind::JackStud, left - w, stud_bottom, left, head));
+        members.push(member(MemberKind::JackStud, right, stud_bottom, right + w, head));
+        members.push(Member {
+            kind: MemberKind::Header,
+            s1: left - w,
+            z1: head,
+            s2: right + w,
+            z2: head + depth,
+            section: header_section(depth),
+        });
+
+        // Cripples on the stud layout above the header and below the sill
+        let inside: Vec<f64> = studs.iter().copied().filter(|&s| s >= left && s + w <= right).collect();
+        if head + depth < stud_top - 1e-9 {
+            for &s in &inside {
+                members.push(member(MemberKind::Cripple, s, head + depth, s + w, stud_top));
+            }
+        }
+        if opening.kind == OpeningKind::Window {
+            members.push(member(MemberKind::SillPlate, left, opening.sill - w, right, opening.sill));
+            for &s in &inside {
+                members.push(member(MemberKind::Cripple, s, stud_bottom, s + w, opening.sill - w));
+            }
+        }
+    }
+
+    WallFraming { wall_id: wall.id, length, height, members }
+}
+
+/// Framing for every wall of the model
+pub fn model_wall_framing(model: &BuildingModel) -> Vec<WallFraming> {
+    model.walls.iter().map(wall_framing).collect()
+}
+
+/// Draw one wall's framing elevation with its start at (x, y)
+pub fn create_wall_framing_elevation(drawing: &mut Drawing, framing: &WallFraming, x: f64, y: f64) {
+    for member in &framing.members {
+        add_rectangle(drawing, LAYER_WALL_FRAMING, x + member.s1, y + member.z1, x + member.s2, y + member.z2);
+
+        // Headers get a diagonal and their size
+        if member.kind == MemberKind::Header {
+            add_line(drawing, LAYER_WALL_FRAMING, x + member.s1, y + member.z1, x + member.s2, y + member.z2);
+            add_text(
+                drawing,
+                LAYER_ANNO,
+                x + member.s1,
+                y + member.z2 + 0.05,
+                DIM_TEXT_HEIGHT,
+                format!("HDR {}", member.section),
+            );
+        }
+    }
+
+    add_text(
+        drawing,
+        LAYER_TEXT,
+        x,
+        y + framing.height + 0.30,
+        LABEL_TEXT_HEIGHT,
+        framing.wall_id.to_uppercase(),
+    );
+    add_text(
+        drawing,
+        LAYER_ANNO,
+        x,
+        y - 0.30,
+        DIM_TEXT_HEIGHT,
+        format!("{:.2} m, {} MEMBERS", framing.length, framing.members.len()),
+    );
+}
+
+/// Draw the framing elevations of every wall in a row from (x, y)
+pub fn create_wall_framing_elevations(drawing: &mut Drawing, model: &BuildingModel, x: f64, y: f64) {
+    add_text(
+        drawing,
+        LAYER_TEXT,
+        x,
+        y + BUILDING_HEIGHT + 0.80,
+        LABEL_TEXT_HEIGHT,
+        "WALL FRAMING ELEVATIONS".to_string(),
+    );
+    add_text(
+        drawing,
+        LAYER_ANNO,
+        x,
+        y + BUILDING_HEIGHT + 0.55,
+        DIM_TEXT_HEIGHT,
+        format!(
+            "{} STUDS @ {:.0} CRS ON 1 BOTTOM PLATE, {} TOP PLATES, HEADERS {} PLIES",
+            stud_section(),
+            STUD_SPACING * 1000.0,
+            TOP_PLATES,
+            HEADER_PLIES
+        ),
+    );
+
+    let mut cursor = x;
+    for framing in model_wall_framing(model) {
+        create_wall_framing_elevation(drawing, &framing, cursor, y);
+        cursor += framing.length + 0.80;
+    }
+}
@@ -0,0 +1,36 @@
+// ============================================================================
+// FEATURE TEST HELPERS
+// Scratch directories and runs of the sauna binary shared by the feature
+// tests; each test file uses its own subset of them.
+// ============================================================================
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// Empty scratch directory for one test
+pub fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("sauna-test-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Run `sauna` with the given arguments in `dir`
+pub fn sauna(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_sauna"))
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run sauna")
+}
+
+/// Run `sauna diff` in `dir` and return its report
+pub fn diff_report(dir: &Path, args: &[&str]) -> String {
+    let output = sauna(dir, &[&["diff"], args].concat());
+    assert!(output.status.success(), "sauna diff failed: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(dir.join("sauna_diff.dxf").exists(), "sauna diff wrote no overlay");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
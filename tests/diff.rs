@@ -0,0 +1,68 @@
+// ============================================================================
+// DIFF TESTS
+// Runs `sauna diff` on generated drawings and on bare DXFs without a model
+// record.
+// ============================================================================
+
+mod common;
+
+use std::fs;
+
+use dxf::entities::{Entity, EntityType, Line};
+use dxf::{Drawing, Point};
+
+use common::{diff_report, sauna, scratch_dir};
+
+/// A drawing compared with itself or the current model has no changes;
+/// adding the 3D model leaves the model unchanged and adds entities on its
+/// own layers only
+#[test]
+fn diff_between_revisions() {
+    let dir = scratch_dir("diff");
+    assert!(sauna(&dir, &[]).status.success());
+    fs::rename(dir.join("sauna_design.dxf"), dir.join("plan.dxf")).unwrap();
+    assert!(diff_report(&dir, &["plan.dxf", "plan.dxf"]).contains("No changes"));
+    assert!(diff_report(&dir, &["plan.dxf"]).contains("No changes"));
+
+    assert!(sauna(&dir, &["--3d"]).status.success());
+    let report = diff_report(&dir, &["plan.dxf", "sauna_design.dxf"]);
+    assert!(report.contains("No changes to the model"), "model changes reported:\n{}", report);
+    let layers: Vec<&str> = report
+        .lines()
+        .skip_while(|line| !line.starts_with("CHANGES BY LAYER"))
+        .skip(1)
+        .take_while(|line| line.starts_with("  ") && !line.starts_with("  ("))
+        .collect();
+    assert!(!layers.is_empty(), "no sheet changes reported:\n{}", report);
+    assert!(
+        layers.iter().all(|line| line.starts_with("  A-MODL-")),
+        "unexpected changes:\n{}",
+        report
+    );
+    let _ = fs::remove_dir_all(dir);
+}
+
+/// Drawings without a model record are compared entity by entity: moving one
+/// line reports just that line
+#[test]
+fn diff_between_bare_drawings() {
+    let dir = scratch_dir("diff-bare");
+    for (name, x) in [("old.dxf", 0.0), ("new.dxf", 0.5)] {
+        let mut drawing = Drawing::new();
+        for (y, dx) in [(0.0, x), (1.0, 0.0)] {
+            let line = Line::new(Point::new(dx, y, 0.0), Point::new(dx + 2.0, y, 0.0));
+            let mut entity = Entity::new(EntityType::Line(line));
+            entity.common.layer = "WALLS".to_string();
+            drawing.add_entity(entity);
+        }
+        drawing.save_file(dir.join(name).to_str().unwrap()).unwrap();
+    }
+    let report = diff_report(&dir, &["old.dxf", "new.dxf"]);
+    assert!(!report.contains("MODEL"), "model diff on bare drawings:\n{}", report);
+    let changes: Vec<&str> = report
+        .lines()
+        .filter(|line| ["  + ", "  - ", "  ~ "].iter().any(|mark| line.starts_with(mark)))
+        .collect();
+    assert_eq!(changes, ["  ~ WALLS LINE moved by (0.500, 0.000)"], "{}", report);
+    let _ = fs::remove_dir_all(dir);
+}
@@ -0,0 +1,293 @@
+// ============================================================================
+// GOLDEN-FILE REGRESSION TESTS
+// Runs the generator for each output variant and compares the normalised
+// result against tests/golden/<name>.txt.
+//
+// Bless new output after an intended change:
+//     BLESS=1 cargo test --test golden
+// ============================================================================
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Group codes kept when normalising an entity (layer, text, geometry, flags)
+fn keep_code(code: i32) -> bool {
+    matches!(code, 1 | 2 | 8 | 10..=59 | 62 | 70..=79)
+}
+
+/// Round numbers so platform float noise does not show up as a difference
+fn normalise_value(value: &str) -> String {
+    let value = value.trim();
+    match value.parse::<f64>() {
+        Ok(number) if value.contains('.') || value.contains('e') => {
+            let text = format!("{:.6}", number);
+            let text = text.trim_end_matches('0').trim_end_matches('.');
+            if text == "-0" { "0".to_string() } else { text.to_string() }
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// Read group code / value pairs from DXF text
+fn code_pairs(text: &str) -> Vec<(i32, String)> {
+    let lines: Vec<&str> = text.lines().collect();
+    lines
+        .chunks(2)
+        .filter(|pair| pair.len() == 2)
+        .filter_map(|pair| Some((pair[0].trim().parse().ok()?, pair[1].to_string())))
+        .collect()
+}
+
+/// One line per layer, per entity and per XRECORD string (the model record
+/// the diff reads), without handles, owners or timestamps
+fn normalise_dxf(text: &str) -> Vec<String> {
+    let pairs = code_pairs(text);
+    let mut out = Vec::new();
+    let mut section = String::new();
+    let mut current: Option<String> = None;
+
+    let flush = |current: &mut Option<String>, out: &mut Vec<String>| {
+        if let Some(line) = current.take() {
+            out.push(line);
+        }
+    };
+
+    for (i, (code, value)) in pairs.iter().enumerate() {
+        let value = value.trim();
+        if *code == 0 {
+            flush(&mut current, &mut out);
+            if value == "SECTION" {
+                if let Some((2, name)) = pairs.get(i + 1) {
+                    section = name.trim().to_string();
+                }
+            } else if section == "ENTITIES" && value != "ENDSEC" {
+                current = Some(value.to_string());
+            } else if section == "TABLES" && value == "LAYER" {
+                current = Some("LAYER".to_string());
+            } else if section == "OBJECTS" && value == "XRECORD" {
+                current = Some("XRECORD".to_string());
+            }
+            continue;
+        }
+        if current.as_deref() == Some("XRECORD") {
+            if *code == 1 {
+                out.push(format!("XRECORD {}", value));
+            }
+            continue;
+        }
+        if let Some(line) = current.as_mut() {
+            let keep = if line.starts_with("LAYER") { matches!(code, 2 | 62) } else { keep_code(*code) };
+            if keep {
+                line.push_str(&format!(" {}={}", code, normalise_value(value)));
+            }
+        }
+    }
+    flush(&mut current, &mut out);
+
+    // The LAYER table and XRECORD headers carry nothing; drop them
+    out.retain(|line| line != "LAYER" && line != "XRECORD");
+    out
+}
+
+/// Line-based LCS diff, reported as "-"/"+" lines with entity indices
+fn diff(expected: &[String], actual: &[String]) -> Vec<String> {
+    let prefix = expected.iter().zip(actual).take_while(|(a, b)| a == b).count();
+    let suffix = expected[prefix..]
+        .iter()
+        .rev()
+        .zip(actual[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old = &expected[prefix..expected.len() - suffix];
+    let new = &actual[prefix..actual.len() - suffix];
+
+    let mut report = Vec::new();
+    if old.len() * new.len() > 16_000_000 {
+        // Too large for a full LCS: list the first differing entities
+        report.push(format!("@@ entity {} (diff too large for alignment)", prefix));
+        report.extend(old.iter().take(20).map(|line| format!("- {}", line)));
+        report.extend(new.iter().take(20).map(|line| format!("+ {}", line)));
+        return report;
+    }
+
+    let mut lcs = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            report.push(format!("+ [{}] {}", prefix + j, new[j]));
+            j += 1;
+        } else {
+            report.push(format!("- [{}] {}", prefix + i, old[i]));
+            i += 1;
+        }
+    }
+    report
+}
+
+/// Per-type entity counts, to summarise a failing comparison
+fn counts(lines: &[String]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for line in lines {
+        let kind = line.split(' ').next().unwrap_or_default().to_string();
+        *counts.entry(kind).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden")
+}
+
+fn blessing() -> bool {
+    std::env::var_os("BLESS").is_some()
+}
+
+/// Run the generator with the given arguments in a scratch directory
+fn generate(name: &str, args: &[&str]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("sauna-golden-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let status = Command::new(env!("CARGO_BIN_EXE_sauna"))
+        .args(args)
+        .current_dir(&dir)
+        .output()
+        .expect("failed to run sauna");
+    assert!(status.status.success(), "sauna {:?} failed: {}", args, String::from_utf8_lossy(&status.stderr));
+    dir
+}
+
+/// Compare normalised lines with the golden file, or rewrite it when blessing
+fn check_golden(name: &str, actual: Vec<String>) {
+    let path = golden_dir().join(format!("{}.txt", name));
+    if blessing() {
+        fs::create_dir_all(golden_dir()).unwrap();
+        fs::write(&path, actual.join("\n") + "\n").unwrap();
+        return;
+    }
+
+    let expected: Vec<String> = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("missing golden file {}; run BLESS=1 cargo test --test golden", path.display()))
+        .lines()
+        .map(str::to_string)
+        .collect();
+    if expected == actual {
+        return;
+    }
+
+    let report = diff(&expected, &actual);
+    let shown: Vec<&String> = report.iter().take(60).collect();
+    let mut message = format!(
+        "{} differs from {} ({} changed lines)\n",
+        name,
+        path.display(),
+        report.len()
+    );
+    let (old_counts, new_counts) = (counts(&expected), counts(&actual));
+    let mut kinds: Vec<&String> = old_counts.keys().chain(new_counts.keys()).collect();
+    kinds.sort();
+    kinds.dedup();
+    for kind in kinds {
+        let (old, new) = (old_counts.get(kind).unwrap_or(&0), new_counts.get(kind).unwrap_or(&0));
+        if old != new {
+            message.push_str(&format!("  {}: {} -> {}\n", kind, old, new));
+        }
+    }
+    for line in shown {
+        message.push_str(line);
+        message.push('\n');
+    }
+    if report.len() > 60 {
+        message.push_str(&format!("... {} more\n", report.len() - 60));
+    }
+    message.push_str("If the change is intended, run: BLESS=1 cargo test --test golden\n");
+    panic!("{}", message);
+}
+
+fn check_dxf(name: &str, args: &[&str]) {
+    let dir = generate(name, args);
+    let text = fs::read_to_string(dir.join("sauna_design.dxf")).unwrap();
+    check_golden(name, normalise_dxf(&text));
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn plan_sheet() {
+    check_dxf("plan_sheet", &[]);
+}
+
+#[test]
+fn model_3d() {
+    check_dxf("model_3d", &["--3d"]);
+}
+
+#[test]
+fn ifc_export() {
+    let dir = generate("ifc_export", &["--ifc"]);
+    let text = fs::read_to_string(dir.join("sauna_design.ifc")).unwrap();
+    check_golden("ifc_export", text.lines().map(str::to_string).collect());
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn bom_export() {
+    let dir = generate("bom_export", &["--bom"]);
+    let text = fs::read_to_string(dir.join("sauna_bom.csv")).unwrap();
+    check_golden("bom_export", text.lines().map(str::to_string).collect());
+    let _ = fs::remove_dir_all(dir);
+}
+
+/// Generated drawings must load back and pass `sauna verify`
+#[test]
+fn generated_drawings_verify() {
+    for (name, args) in [("verify-plan", &[][..]), ("verify-3d", &["--3d"][..])] {
+        let dir = generate(name, args);
+        let output = Command::new(env!("CARGO_BIN_EXE_sauna"))
+            .args(["verify", "sauna_design.dxf"])
+            .current_dir(&dir)
+            .output()
+            .expect("failed to run sauna verify");
+        assert!(
+            output.status.success(),
+            "sauna verify failed for {:?}:\n{}",
+            args,
+            String::from_utf8_lossy(&output.stdout)
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+}
+
+/// The committed sauna_design.dxf must match what the generator produces
+#[test]
+fn committed_drawing_is_current() {
+    let dir = generate("committed", &[]);
+    let generated = fs::read_to_string(dir.join("sauna_design.dxf")).unwrap();
+    let committed_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("sauna_design.dxf");
+    if blessing() {
+        fs::write(&committed_path, &generated).unwrap();
+    } else {
+        let committed = fs::read_to_string(&committed_path).unwrap();
+        let report = diff(&normalise_dxf(&committed), &normalise_dxf(&generated));
+        assert!(
+            report.is_empty(),
+            "sauna_design.dxf is out of date ({} changed lines), first: {:?}\nRegenerate with: BLESS=1 cargo test --test golden",
+            report.len(),
+            report.first()
+        );
+    }
+    let _ = fs::remove_dir_all(dir);
+}
@@ -0,0 +1,40 @@
+// ============================================================================
+// HEATER CATALOGUE TESTS
+// Runs the generator with a custom --heaters catalogue.
+// ============================================================================
+
+mod common;
+
+use std::fs;
+
+use common::{diff_report, sauna, scratch_dir};
+
+/// A heater catalogue listing HEATER_MODEL as a wood stove gives it a flue
+/// and no control panel, and the heater check flags its output and a flue
+/// through a roof rafter; a broken catalogue is rejected with the offending
+/// line
+#[test]
+fn custom_heater_catalogue() {
+    let dir = scratch_dir("heaters");
+    fs::write(dir.join("wood.txt"), "# wood-fired\nE-ROUND-6 wood 0.40x0.50 0.70 8.0 0.20 0.20 1.10 0.115\n").unwrap();
+    let output = sauna(&dir, &["--heaters", "wood.txt"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("E-ROUND-6 WOOD, 8.0 kW, 0.40 x 0.50 m"), "{}", stdout);
+    assert!(stdout.contains("FLUE Ø115 mm TO 0.91 m ABOVE THE ROOF"), "{}", stdout);
+    assert!(!stdout.contains("HEATER-CONTROL"), "{}", stdout);
+    assert!(stdout.contains("WARNING: HEATER E-ROUND-6 8.0 kW IS BELOW THE 10.5 kW MINIMUM"), "{}", stdout);
+    assert!(stdout.contains("WARNING: FLUE 0.00 m FROM THE ROOF RAFTER AT 0.60 m"), "{}", stdout);
+    assert!(!stdout.contains("HEATER OUTPUT AND CLEARANCES OK"), "{}", stdout);
+
+    // verify and diff rebuild the model with the same catalogue
+    let output = sauna(&dir, &["verify", "sauna_design.dxf", "--heaters", "wood.txt"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    assert!(diff_report(&dir, &["--heaters", "wood.txt", "sauna_design.dxf"]).contains("No changes"));
+
+    fs::write(dir.join("broken.txt"), "E-ROUND-6 electric 0.50 0.70 6.0 0.20 0.20 1.10 0.115\n").unwrap();
+    let output = sauna(&dir, &["--heaters", "broken.txt"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("line 1: an electric heater has no flue"));
+    let _ = fs::remove_dir_all(dir);
+}
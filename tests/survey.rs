@@ -0,0 +1,76 @@
+// ============================================================================
+// SURVEY UNDERLAY TESTS
+// Runs the generator with a --survey DXF and reads the result back.
+// ============================================================================
+
+mod common;
+
+use std::fs;
+
+use dxf::entities::EntityType;
+use dxf::{Drawing, Point};
+
+use common::{sauna, scratch_dir};
+
+/// Survey layers listed in SURVEY_LAYERS are copied onto V- layers in a block
+/// kept in survey coordinates and inserted so the surveyed boundary lands on
+/// the front lot line of the site plan; other layers are left out and the
+/// drawing still verifies
+#[test]
+fn survey_underlay() {
+    let dir = scratch_dir("survey");
+    let entity = |kind: &str, layer: &str, codes: &str| format!("0\n{}\n8\n{}\n{}", kind, layer, codes);
+    let survey = [
+        "0\nSECTION\n2\nENTITIES".to_string(),
+        entity("LINE", "BOUNDARY", "10\n512340.0\n20\n6781250.0\n11\n512364.0\n21\n6781250.0"),
+        entity("CIRCLE", "Trees", "10\n512344.0\n20\n6781270.0\n40\n2.5"),
+        entity("LINE", "TITLE", "10\n0.0\n20\n0.0\n11\n1.0\n21\n1.0"),
+        "0\nENDSEC\n0\nEOF\n".to_string(),
+    ];
+    fs::write(dir.join("survey.dxf"), survey.join("\n")).unwrap();
+    let output = sauna(&dir, &["--survey", "survey.dxf"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("V-BOUNDARY           1 ENTITIES"), "{}", stdout);
+    assert!(stdout.contains("V-TREES              1 ENTITIES"), "{}", stdout);
+    assert!(!stdout.contains("V-TITLE"), "{}", stdout);
+
+    let drawing = Drawing::load_file(dir.join("sauna_design.dxf").to_str().unwrap()).unwrap();
+    let block = drawing.blocks().find(|block| block.name == "SURVEY").expect("survey block");
+    let insert = drawing
+        .entities()
+        .find_map(|entity| match &entity.specific {
+            EntityType::Insert(insert) if insert.name == "SURVEY" => Some(insert),
+            _ => None,
+        })
+        .expect("survey block inserted");
+    assert_eq!(insert.rotation, 0.0);
+    let boundary = block
+        .entities
+        .iter()
+        .find_map(|entity| match &entity.specific {
+            EntityType::Line(line) if entity.common.layer == "V-BOUNDARY" => Some(line),
+            _ => None,
+        })
+        .expect("boundary line in the survey block");
+    assert_eq!((boundary.p1.x, boundary.p1.y), (512340.0, 6781250.0));
+    let to_sheet = |p: &Point| (insert.location.x + p.x - block.base_point.x, insert.location.y + p.y - block.base_point.y);
+
+    // The lot outline starts with the front lot line, 24 m east from the first corner
+    let lot = drawing
+        .entities()
+        .find_map(|entity| match &entity.specific {
+            EntityType::LwPolyline(polyline) if entity.common.layer == "C-PROP" => Some(polyline),
+            _ => None,
+        })
+        .expect("lot outline");
+    for (point, vertex) in [(&boundary.p1, &lot.vertices[0]), (&boundary.p2, &lot.vertices[1])] {
+        let (x, y) = to_sheet(point);
+        assert!((x - vertex.x).abs() < 1e-6 && (y - vertex.y).abs() < 1e-6, "({}, {}) off the lot corner", x, y);
+    }
+
+    let output = sauna(&dir, &["verify", "sauna_design.dxf"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("differ"));
+    let _ = fs::remove_dir_all(dir);
+}
@@ -0,0 +1,40 @@
+// ============================================================================
+// TITLE BLOCK TEMPLATE TESTS
+// Runs the generator with a custom --title-block template.
+// ============================================================================
+
+mod common;
+
+use std::fs;
+
+use common::{diff_report, sauna, scratch_dir};
+
+/// A custom title block template is filled from the project metadata, and a
+/// broken one is rejected with the offending line
+#[test]
+fn custom_title_block_template() {
+    let dir = scratch_dir("title-block");
+    fs::write(
+        dir.join("custom.txt"),
+        "size 3.0 1.0\nborder\ntext 0.05 0.5 label {project_number} / {client}\ntext\t0.05  0.77 title   {project}\n",
+    )
+    .unwrap();
+    let output = sauna(&dir, &["--title-block", "custom.txt"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let text = fs::read_to_string(dir.join("sauna_design.dxf")).unwrap();
+    assert!(text.contains("2026-001 / PRIVATE CLIENT"));
+    // Tabs and aligned columns before the value are not part of it
+    assert!(text.lines().any(|line| line.trim_end() == "SAUNA BUILDING"));
+    assert!(!text.contains("title"));
+
+    // verify and diff rebuild the sheet with the same template
+    let output = sauna(&dir, &["verify", "--title-block", "custom.txt", "sauna_design.dxf"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    assert!(diff_report(&dir, &["sauna_design.dxf", "--title-block", "custom.txt"]).contains("No changes"));
+
+    fs::write(dir.join("broken.txt"), "size 3.0 1.0\ntext 0.05 0.5 label {nonsense}\n").unwrap();
+    let output = sauna(&dir, &["--title-block", "broken.txt"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("line 2: unknown placeholder {nonsense}"));
+    let _ = fs::remove_dir_all(dir);
+}